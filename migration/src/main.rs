@@ -1,4 +1,5 @@
 use csv::{ReaderBuilder, StringRecord};
+use sha2::{Digest, Sha256};
 use std::{
     env::{self, VarError},
     error,
@@ -6,6 +7,7 @@ use std::{
     io::{self, Write},
     path::Path,
     process::{Command, Stdio},
+    time::{SystemTime, UNIX_EPOCH},
 };
 use tracing::{info, warn};
 
@@ -44,6 +46,26 @@ pub fn insert_data(generated_sql_path: String) -> Result<(), Box<dyn std::error:
     Ok(())
 }
 
+// NOTE: stationapiのDataset::loadも同じ計算をするので変えるときは両方直す
+pub fn dataset_content_hash(
+    data_path: &Path,
+    file_list: &[String],
+) -> Result<String, Box<dyn std::error::Error>> {
+    let mut hasher = Sha256::new();
+    for file_name in file_list {
+        let contents = fs::read(data_path.join(file_name))?;
+        hasher.update(file_name.as_bytes());
+        hasher.update([0]);
+        hasher.update((contents.len() as u64).to_le_bytes());
+        hasher.update(&contents);
+    }
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect())
+}
+
 pub fn generate_sql() -> Result<String, Box<dyn std::error::Error>> {
     let out_path = match env::var("SQL_OUT_PATH") {
         Ok(s) => s,
@@ -138,6 +160,20 @@ pub fn generate_sql() -> Result<String, Box<dyn std::error::Error>> {
         sql_lines.push(format!("{}\nUNLOCK TABLES", sql_lines_inner.concat()));
     }
 
+    let content_hash = dataset_content_hash(data_path, &file_list)?;
+    let created_at = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    info!(
+        "Dataset version: {} (created at {})",
+        &content_hash[..16],
+        created_at
+    );
+    sql_lines.push(format!(
+        "LOCK TABLES `dataset_info` WRITE;\nINSERT INTO `dataset_info` VALUES ('{}','{}','{}');\nUNLOCK TABLES",
+        &content_hash[..16],
+        content_hash,
+        created_at
+    ));
+
     let create_sql: String =
         String::from_utf8_lossy(&fs::read(data_path.join("create_table.sql"))?).parse()?;
 
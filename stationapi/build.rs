@@ -1,5 +1,35 @@
+// REST/JSONゲートウェイでそのままJSONにするレスポンス
+const SERIALIZABLE_RESPONSES: [&str; 21] = [
+    "SingleStationResponse",
+    "MultipleStationResponse",
+    "SingleLineResponse",
+    "MultipleLineResponse",
+    "SingleTrainTypeResponse",
+    "MultipleTrainTypeResponse",
+    "SingleCompanyResponse",
+    "MultipleCompanyResponse",
+    "Prefecture",
+    "MultiplePrefectureResponse",
+    "Route",
+    "RouteResponse",
+    "JourneyLeg",
+    "Journey",
+    "JourneyResponse",
+    "Suggestion",
+    "AutocompleteResponse",
+    "DistanceResponse",
+    "StoppingPatternStation",
+    "StoppingPatternDiffResponse",
+    "DatasetInfoResponse",
+];
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    tonic_build::configure()
+    let mut builder = tonic_build::configure();
+    for message in SERIALIZABLE_RESPONSES {
+        builder = builder.type_attribute(message, "#[derive(serde::Serialize)]");
+    }
+
+    builder
         .type_attribute("Company", "#[derive(serde::Serialize, serde::Deserialize)]")
         .type_attribute(
             "LineSymbol",
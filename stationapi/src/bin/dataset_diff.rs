@@ -0,0 +1,49 @@
+use stationapi::infrastructure::memory::{dataset::Dataset, dataset_diff::diff_datasets};
+use std::{
+    env::{self, VarError},
+    path::Path,
+};
+use tracing::{info, warn};
+
+// 使い方: dataset_diff <比較元のディレクトリ> [比較先のディレクトリ]
+// 比較先を省略すると$DATASET_PATHと比べ、変更履歴をJSONで標準出力に書き出す
+fn main() -> std::result::Result<(), anyhow::Error> {
+    // NOTE: 標準出力はJSONだけにする
+    tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
+        .init();
+
+    let mut args = env::args().skip(1);
+    let Some(base_path) = args.next() else {
+        anyhow::bail!("Usage: dataset_diff <base data directory> [target data directory]");
+    };
+    let target_path = args.next().unwrap_or_else(fetch_dataset_path);
+
+    let base = Dataset::load(Path::new(&base_path))?;
+    let target = Dataset::load(Path::new(&target_path))?;
+    let changelog = diff_datasets(&base, &target);
+    info!(
+        "Compared {} with {}: {} companies, {} lines, {} stations and {} train types changed",
+        base_path,
+        target_path,
+        changelog.companies.len(),
+        changelog.lines.len(),
+        changelog.stations.len(),
+        changelog.train_types.len()
+    );
+
+    println!("{}", serde_json::to_string_pretty(&changelog)?);
+
+    Ok(())
+}
+
+fn fetch_dataset_path() -> String {
+    match env::var("DATASET_PATH") {
+        Ok(s) => s,
+        Err(env::VarError::NotPresent) => {
+            warn!("$DATASET_PATH is not set. Falling back to ./data.");
+            "data".to_string()
+        }
+        Err(VarError::NotUnicode(_)) => panic!("$DATASET_PATH should be written in Unicode."),
+    }
+}
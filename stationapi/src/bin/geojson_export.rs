@@ -0,0 +1,66 @@
+use stationapi::{
+    infrastructure::memory::{dataset::Dataset, dataset_loader::InMemoryDatasetLoader},
+    use_case::traits::query::QueryUseCase,
+};
+use std::{
+    env::{self, VarError},
+    fs,
+    path::Path,
+    sync::Arc,
+};
+use tracing::{info, warn};
+
+// 使い方: geojson_export [出力先ディレクトリ] [路線コード...]
+// 路線コードを省略すると運用中のすべての路線を `<路線コード>.geojson` として書き出す
+#[tokio::main]
+async fn main() -> std::result::Result<(), anyhow::Error> {
+    tracing_subscriber::fmt::init();
+
+    let dataset_path = fetch_dataset_path();
+    let mut args = env::args().skip(1);
+    let output_path = args.next().unwrap_or_else(|| "geojson".to_string());
+    let mut line_ids = args
+        .map(|arg| arg.parse::<u32>())
+        .collect::<Result<Vec<u32>, _>>()?;
+
+    let dataset = Arc::new(Dataset::load(Path::new(&dataset_path))?);
+    info!("Loaded the dataset from {}", dataset_path);
+
+    if line_ids.is_empty() {
+        line_ids = dataset
+            .lines()
+            .filter(|line| line.e_status == 0)
+            .map(|line| line.line_cd)
+            .collect();
+    }
+
+    let query_use_case = InMemoryDatasetLoader::build_query_interactor(dataset);
+
+    let output_path = Path::new(&output_path);
+    fs::create_dir_all(output_path)?;
+    for line_id in line_ids.iter() {
+        let feature_collection = query_use_case.get_line_geojson(*line_id).await?;
+        fs::write(
+            output_path.join(format!("{}.geojson", line_id)),
+            serde_json::to_string(&feature_collection)?,
+        )?;
+    }
+    info!(
+        "Exported {} lines to {}",
+        line_ids.len(),
+        output_path.display()
+    );
+
+    Ok(())
+}
+
+fn fetch_dataset_path() -> String {
+    match env::var("DATASET_PATH") {
+        Ok(s) => s,
+        Err(env::VarError::NotPresent) => {
+            warn!("$DATASET_PATH is not set. Falling back to ./data.");
+            "data".to_string()
+        }
+        Err(VarError::NotUnicode(_)) => panic!("$DATASET_PATH should be written in Unicode."),
+    }
+}
@@ -0,0 +1,40 @@
+use stationapi::infrastructure::{gtfs::GtfsFeed, memory::dataset::Dataset};
+use std::{
+    env::{self, VarError},
+    path::Path,
+};
+use tracing::{info, warn};
+
+// 使い方: gtfs_export [出力先ディレクトリ]
+fn main() -> std::result::Result<(), anyhow::Error> {
+    tracing_subscriber::fmt::init();
+
+    let dataset_path = fetch_dataset_path();
+    let output_path = env::args().nth(1).unwrap_or_else(|| "gtfs".to_string());
+
+    let dataset = Dataset::load(Path::new(&dataset_path))?;
+    info!("Loaded the dataset from {}", dataset_path);
+
+    let feed = GtfsFeed::from_dataset(&dataset);
+    feed.write(Path::new(&output_path))?;
+    info!(
+        "Exported {} routes, {} stops and {} trips to {}",
+        feed.routes.len(),
+        feed.stops.len(),
+        feed.trips.len(),
+        output_path
+    );
+
+    Ok(())
+}
+
+fn fetch_dataset_path() -> String {
+    match env::var("DATASET_PATH") {
+        Ok(s) => s,
+        Err(env::VarError::NotPresent) => {
+            warn!("$DATASET_PATH is not set. Falling back to ./data.");
+            "data".to_string()
+        }
+        Err(VarError::NotUnicode(_)) => panic!("$DATASET_PATH should be written in Unicode."),
+    }
+}
@@ -1,7 +1,19 @@
 pub mod company;
+pub mod dataset_changelog;
+pub mod dataset_info;
+pub mod dataset_summary;
+pub mod geojson;
+pub mod journey;
 pub mod line;
 pub mod line_symbol;
+pub mod location_tracker;
 pub mod misc;
+pub mod prefecture;
+pub mod service_day;
 pub mod station;
+pub mod station_graph;
 pub mod station_number;
+pub mod stopping_pattern;
+pub mod suggestion;
 pub mod train_type;
+pub mod travel_direction;
@@ -0,0 +1,45 @@
+use serde::Serialize;
+use serde_json::Value;
+
+#[derive(Clone, Copy, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Modified,
+}
+
+// 追加・削除のときは片方がnullになる
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct FieldChange {
+    pub field: String,
+    pub old: Value,
+    pub new: Value,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct RecordChange {
+    // company_cd, line_cd, station_cd, type_cdのいずれか
+    pub key: u32,
+    pub change: ChangeKind,
+    pub fields: Vec<FieldChange>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, PartialEq)]
+pub struct DatasetChangelog {
+    pub from_version: Option<String>,
+    pub to_version: Option<String>,
+    pub companies: Vec<RecordChange>,
+    pub lines: Vec<RecordChange>,
+    pub stations: Vec<RecordChange>,
+    pub train_types: Vec<RecordChange>,
+}
+
+impl DatasetChangelog {
+    pub fn is_empty(&self) -> bool {
+        self.companies.is_empty()
+            && self.lines.is_empty()
+            && self.stations.is_empty()
+            && self.train_types.is_empty()
+    }
+}
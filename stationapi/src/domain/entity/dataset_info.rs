@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DatasetInfo {
+    // content_hashの先頭16文字。データが変わったときだけ変わる
+    pub version: String,
+    // data/*.csvのSHA-256
+    pub content_hash: String,
+    // UNIX時間(秒)
+    pub created_at: u64,
+}
@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+
+// 読み込んだデータセットの件数。リロード前後の確認に使う
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DatasetSummary {
+    pub companies: u64,
+    pub lines: u64,
+    pub stations: u64,
+    pub train_types: u64,
+}
@@ -0,0 +1,174 @@
+use serde::Serialize;
+
+use super::{line::Line, line_symbol::LineSymbol, station::Station, station_number::StationNumber};
+
+#[derive(Clone, Debug, Serialize, PartialEq)]
+#[serde(tag = "type", content = "coordinates")]
+pub enum Geometry {
+    Point([f64; 2]),
+    LineString(Vec<[f64; 2]>),
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct LineProperties {
+    pub line_cd: u32,
+    pub company_cd: u32,
+    pub line_name: String,
+    pub line_name_r: Option<String>,
+    pub line_color_c: Option<String>,
+    pub line_type: Option<u32>,
+    pub line_symbols: Vec<LineSymbol>,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct StationProperties {
+    pub station_cd: u32,
+    pub station_g_cd: u32,
+    pub station_name: String,
+    pub station_name_r: Option<String>,
+    pub line_cd: u32,
+    pub line_color_c: Option<String>,
+    pub station_numbers: Vec<StationNumber>,
+    pub e_sort: u32,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq)]
+#[serde(untagged)]
+pub enum FeatureProperties {
+    Line(LineProperties),
+    Station(StationProperties),
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct Feature {
+    #[serde(rename = "type")]
+    pub feature_type: &'static str,
+    // NOTE: 形を持たない地物はnullにする
+    pub geometry: Option<Geometry>,
+    pub properties: FeatureProperties,
+}
+
+impl Feature {
+    pub fn new(geometry: Option<Geometry>, properties: FeatureProperties) -> Self {
+        Self {
+            feature_type: "Feature",
+            geometry,
+            properties,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct FeatureCollection {
+    #[serde(rename = "type")]
+    pub collection_type: &'static str,
+    pub features: Vec<Feature>,
+}
+
+impl FeatureCollection {
+    pub fn new(features: Vec<Feature>) -> Self {
+        Self {
+            collection_type: "FeatureCollection",
+            features,
+        }
+    }
+
+    // 先頭が路線のLineString、続いて駅のPoint。どちらもe_sort順に並べる
+    pub fn from_line(line: &Line, stations: &[Station]) -> Self {
+        let mut stations: Vec<&Station> = stations.iter().collect();
+        stations.sort_by_key(|s| (s.e_sort, s.station_cd));
+
+        // NOTE: LineStringは2点以上必要なので、駅が1つ以下の路線は形を持たない
+        let line_geometry = (stations.len() >= 2)
+            .then(|| Geometry::LineString(stations.iter().map(|s| [s.lon, s.lat]).collect()));
+        let line_feature = Feature::new(
+            line_geometry,
+            FeatureProperties::Line(LineProperties {
+                line_cd: line.line_cd,
+                company_cd: line.company_cd,
+                line_name: line.line_name.clone(),
+                line_name_r: line.line_name_r.clone(),
+                line_color_c: line.line_color_c.clone(),
+                line_type: line.line_type,
+                line_symbols: line.line_symbols.clone(),
+            }),
+        );
+        let station_features = stations.iter().map(|s| {
+            Feature::new(
+                Some(Geometry::Point([s.lon, s.lat])),
+                FeatureProperties::Station(StationProperties {
+                    station_cd: s.station_cd,
+                    station_g_cd: s.station_g_cd,
+                    station_name: s.station_name.clone(),
+                    station_name_r: s.station_name_r.clone(),
+                    line_cd: s.line_cd,
+                    line_color_c: line.line_color_c.clone(),
+                    station_numbers: s.station_numbers.clone(),
+                    e_sort: s.e_sort,
+                }),
+            )
+        });
+
+        Self::new(
+            std::iter::once(line_feature)
+                .chain(station_features)
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::FeatureCollection;
+    use crate::infrastructure::memory::dataset::Dataset;
+
+    #[test]
+    fn from_line() {
+        let dataset = Dataset::load(&Path::new(env!("CARGO_MANIFEST_DIR")).join("../data"))
+            .expect("Failed to load the dataset");
+        let line = dataset.build_line(dataset.line(1002).unwrap(), None, None);
+        let mut stations: Vec<_> = dataset
+            .stations_by_line_cd(1002)
+            .filter_map(|s| dataset.build_station(s, None, false))
+            .collect();
+        stations.reverse();
+
+        let value = serde_json::to_value(FeatureCollection::from_line(&line, &stations)).unwrap();
+        assert_eq!(value["type"], "FeatureCollection");
+
+        let features = value["features"].as_array().unwrap();
+        assert_eq!(features.len(), stations.len() + 1);
+        assert_eq!(features[0]["geometry"]["type"], "LineString");
+        assert_eq!(features[0]["properties"]["line_color_c"], "#0000FF");
+        let coordinates = features[0]["geometry"]["coordinates"].as_array().unwrap();
+        assert_eq!(coordinates.len(), stations.len());
+
+        // e_sort順に並べ直すので東京駅が先頭になる
+        assert_eq!(features[1]["type"], "Feature");
+        assert_eq!(features[1]["geometry"]["type"], "Point");
+        assert_eq!(features[1]["properties"]["station_name"], "東京");
+        assert_eq!(features[1]["geometry"]["coordinates"], coordinates[0]);
+    }
+    #[test]
+    fn from_line_without_enough_stations() {
+        let dataset = Dataset::load(&Path::new(env!("CARGO_MANIFEST_DIR")).join("../data"))
+            .expect("Failed to load the dataset");
+        let line = dataset.build_line(dataset.line(1002).unwrap(), None, None);
+        let stations: Vec<_> = dataset
+            .stations_by_line_cd(1002)
+            .filter_map(|s| dataset.build_station(s, None, false))
+            .take(1)
+            .collect();
+
+        for stations in [&stations[..], &[]] {
+            let value =
+                serde_json::to_value(FeatureCollection::from_line(&line, stations)).unwrap();
+            let features = value["features"].as_array().unwrap();
+            assert_eq!(features.len(), stations.len() + 1);
+            assert!(features[0]["geometry"].is_null());
+            assert_eq!(features[0]["properties"]["line_cd"], 1002);
+        }
+    }
+}
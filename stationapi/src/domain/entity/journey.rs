@@ -0,0 +1,72 @@
+use serde::{Deserialize, Serialize};
+
+use super::{station::Station, train_type::TrainType};
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct JourneyLeg {
+    pub line_cd: u32,
+    pub line_group_cd: Option<u32>,
+    pub station_cds: Vec<u32>,
+    pub stops: Vec<Station>,
+    pub train_type: Option<Box<TrainType>>,
+}
+
+impl JourneyLeg {
+    pub fn new(line_cd: u32, line_group_cd: Option<u32>, station_cds: Vec<u32>) -> Self {
+        Self {
+            line_cd,
+            line_group_cd,
+            station_cds,
+            stops: vec![],
+            train_type: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Journey {
+    pub legs: Vec<JourneyLeg>,
+    pub station_count: u32,
+    pub transfer_count: u32,
+}
+
+impl Journey {
+    pub fn new(legs: Vec<JourneyLeg>) -> Self {
+        let station_count = legs
+            .iter()
+            .map(|leg| leg.station_cds.len().saturating_sub(1) as u32)
+            .sum();
+        let transfer_count = legs.len().saturating_sub(1) as u32;
+
+        Self {
+            legs,
+            station_count,
+            transfer_count,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new() {
+        let journey = Journey::new(vec![
+            JourneyLeg::new(11302, None, vec![1130201, 1130202, 1130203]),
+            JourneyLeg::new(28001, Some(1), vec![2800101, 2800102]),
+        ]);
+        assert_eq!(journey.station_count, 3);
+        assert_eq!(journey.transfer_count, 1);
+        assert_eq!(
+            journey.legs[1],
+            JourneyLeg {
+                line_cd: 28001,
+                line_group_cd: Some(1),
+                station_cds: vec![2800101, 2800102],
+                stops: vec![],
+                train_type: None,
+            }
+        );
+    }
+}
@@ -0,0 +1,312 @@
+use std::collections::VecDeque;
+
+use crate::station_api::{DistanceResponseState, StopCondition};
+
+use super::{
+    misc::distance_in_km,
+    station::Station,
+    travel_direction::{LinePath, TravelDirection},
+};
+
+// NOTE: 一度入った状態は閾値のこの倍率まで離れるまで維持し、GPSのブレで状態が行き来しないようにする
+const HYSTERESIS_RATIO: f64 = 1.5;
+// NOTE: 駅間の中間付近で最寄り駅がばたつかないよう、この距離以上近い駅が現れたときだけ切り替える
+const STATION_SWITCH_MARGIN_IN_KM: f64 = 0.05;
+// 進行方向の推定に使う直近の測位数
+const DIRECTION_HISTORY_SIZE: usize = 5;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DistanceThresholds {
+    pub arrived: f64,
+    pub approaching: f64,
+}
+
+impl DistanceThresholds {
+    // average_distanceはメートル、閾値はキロメートル
+    pub fn new(average_distance: f64) -> Self {
+        let avg_distance_in_km = average_distance / 1000.0;
+        Self {
+            arrived: (avg_distance_in_km / 4.5).min(0.5),
+            approaching: (avg_distance_in_km / 2.0).min(1.0),
+        }
+    }
+
+    pub fn state(&self, distance: f64) -> DistanceResponseState {
+        if distance < self.arrived {
+            return DistanceResponseState::Arrived;
+        }
+        if distance < self.approaching && distance > self.arrived {
+            return DistanceResponseState::Approaching;
+        }
+        DistanceResponseState::Away
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LocationUpdate {
+    pub state: DistanceResponseState,
+    pub station: Station,
+    pub next_station: Option<Station>,
+    pub distance: f64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct TrackingState {
+    index: usize,
+    state: DistanceResponseState,
+    // 現在の駅に到着した後、離れたかどうか
+    departed: bool,
+    direction: Option<TravelDirection>,
+    next_index: Option<usize>,
+}
+
+pub struct LocationTracker {
+    path: LinePath,
+    history: VecDeque<(f64, f64)>,
+    tracking: Option<TrackingState>,
+}
+
+impl LocationTracker {
+    pub fn new(stations: Vec<Station>) -> Self {
+        // NOTE: 通過駅には到着しないので、停車駅だけを追跡対象にする
+        let stations = stations
+            .into_iter()
+            .filter(|station| station.stop_condition != StopCondition::Not)
+            .collect();
+
+        Self {
+            path: LinePath::new(stations),
+            history: VecDeque::with_capacity(DIRECTION_HISTORY_SIZE),
+            tracking: None,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.path.stations().is_empty()
+    }
+
+    // 状態・現在の駅・次の駅のいずれかが変わったときだけ結果を返す
+    pub fn update(&mut self, latitude: f64, longitude: f64) -> Option<LocationUpdate> {
+        let (nearest_index, nearest_distance) = self.nearest(latitude, longitude)?;
+
+        if self.history.len() == DIRECTION_HISTORY_SIZE {
+            self.history.pop_front();
+        }
+        self.history.push_back((latitude, longitude));
+        let inferred_direction = self.path.infer_direction(self.history.make_contiguous());
+
+        let mut next_tracking = match self.tracking {
+            Some(tracking) if tracking.index != nearest_index => {
+                let current_distance = self.distance_to(tracking.index, latitude, longitude);
+                if nearest_distance + STATION_SWITCH_MARGIN_IN_KM < current_distance {
+                    self.switch_station(tracking, nearest_index, nearest_distance)
+                } else {
+                    self.stay_at_station(tracking, current_distance)
+                }
+            }
+            Some(tracking) => self.stay_at_station(tracking, nearest_distance),
+            None => TrackingState {
+                index: nearest_index,
+                state: self.thresholds(nearest_index).state(nearest_distance),
+                departed: false,
+                direction: None,
+                next_index: None,
+            },
+        };
+        // NOTE: 測位履歴から向きが分からないときは、駅の切り替わりから推定した向きを使い続ける
+        next_tracking.direction = inferred_direction.or(next_tracking.direction);
+        next_tracking.next_index = next_tracking
+            .direction
+            .and_then(|direction| self.next_index(next_tracking.index, direction));
+
+        let previous_tracking = self.tracking.replace(next_tracking);
+        if previous_tracking.is_some_and(|previous| {
+            previous.index == next_tracking.index
+                && previous.state == next_tracking.state
+                && previous.next_index == next_tracking.next_index
+        }) {
+            return None;
+        }
+
+        Some(LocationUpdate {
+            state: next_tracking.state,
+            station: self.path.stations()[next_tracking.index].clone(),
+            next_station: next_tracking
+                .next_index
+                .map(|index| self.path.stations()[index].clone()),
+            distance: self.distance_to(next_tracking.index, latitude, longitude),
+        })
+    }
+
+    fn switch_station(
+        &self,
+        tracking: TrackingState,
+        index: usize,
+        distance: f64,
+    ) -> TrackingState {
+        let direction = if index > tracking.index {
+            TravelDirection::Ascending
+        } else {
+            TravelDirection::Descending
+        };
+
+        TrackingState {
+            index,
+            state: self.thresholds(index).state(distance),
+            departed: false,
+            direction: Some(direction),
+            next_index: None,
+        }
+    }
+
+    fn stay_at_station(&self, tracking: TrackingState, distance: f64) -> TrackingState {
+        let thresholds = self.thresholds(tracking.index);
+        let (state, departed) = match tracking.state {
+            DistanceResponseState::Arrived if distance <= thresholds.arrived * HYSTERESIS_RATIO => {
+                (DistanceResponseState::Arrived, false)
+            }
+            // NOTE: 到着後に離れていく場合は接近中ではなく発車済みとして扱う
+            DistanceResponseState::Arrived => (DistanceResponseState::Away, true),
+            _ if distance < thresholds.arrived => (DistanceResponseState::Arrived, false),
+            DistanceResponseState::Approaching
+                if distance <= thresholds.approaching * HYSTERESIS_RATIO =>
+            {
+                (DistanceResponseState::Approaching, false)
+            }
+            _ if tracking.departed => (DistanceResponseState::Away, true),
+            _ => (thresholds.state(distance), false),
+        };
+
+        TrackingState {
+            state,
+            departed,
+            ..tracking
+        }
+    }
+
+    fn next_index(&self, index: usize, direction: TravelDirection) -> Option<usize> {
+        match direction {
+            TravelDirection::Ascending => {
+                (index + 1 < self.path.stations().len()).then_some(index + 1)
+            }
+            TravelDirection::Descending => index.checked_sub(1),
+        }
+    }
+
+    fn nearest(&self, latitude: f64, longitude: f64) -> Option<(usize, f64)> {
+        (0..self.path.stations().len())
+            .map(|index| (index, self.distance_to(index, latitude, longitude)))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+    }
+
+    fn distance_to(&self, index: usize, latitude: f64, longitude: f64) -> f64 {
+        let station = &self.path.stations()[index];
+        distance_in_km(latitude, longitude, station.lat, station.lon)
+    }
+
+    fn thresholds(&self, index: usize) -> DistanceThresholds {
+        DistanceThresholds::new(self.path.stations()[index].average_distance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DistanceThresholds, LocationTracker};
+    use crate::{
+        domain::entity::station::Station,
+        station_api::{DistanceResponseState, StopCondition},
+    };
+
+    fn station(station_cd: u32, lon: f64, stop_condition: StopCondition) -> Station {
+        Station {
+            lon,
+            stop_condition,
+            // 駅間約1.8km
+            average_distance: 1800.0,
+            ..Station::test_fixture(station_cd)
+        }
+    }
+
+    // 東西に並ぶ4駅。3駅目は通過駅
+    fn new_tracker() -> LocationTracker {
+        LocationTracker::new(vec![
+            station(1, 139.0, StopCondition::All),
+            station(2, 139.02, StopCondition::All),
+            station(3, 139.04, StopCondition::Not),
+            station(4, 139.06, StopCondition::All),
+        ])
+    }
+
+    #[test]
+    fn thresholds() {
+        let thresholds = DistanceThresholds::new(1800.0);
+        assert!((thresholds.arrived - 0.4).abs() < 1e-9);
+        assert!((thresholds.approaching - 0.9).abs() < 1e-9);
+        assert_eq!(DistanceThresholds::new(10000.0).arrived, 0.5);
+        assert_eq!(thresholds.state(0.3), DistanceResponseState::Arrived);
+        assert_eq!(thresholds.state(0.6), DistanceResponseState::Approaching);
+        assert_eq!(thresholds.state(1.2), DistanceResponseState::Away);
+    }
+
+    #[test]
+    fn emits_only_transitions() {
+        let mut tracker = new_tracker();
+
+        let update = tracker.update(35.0, 139.0).unwrap();
+        assert_eq!(update.state, DistanceResponseState::Arrived);
+        assert_eq!(update.station.station_cd, 1);
+        assert_eq!(update.next_station, None);
+
+        // 状態が変わらなければ何も返さない
+        assert_eq!(tracker.update(35.0, 139.001), None);
+    }
+
+    #[test]
+    fn keeps_arrived_state_within_hysteresis() {
+        let mut tracker = new_tracker();
+        tracker.update(35.0, 139.0);
+
+        // 約0.46km: 到着の閾値は超えているが、ヒステリシスの範囲内。進行方向だけが分かる
+        let update = tracker.update(35.0, 139.005).unwrap();
+        assert_eq!(update.state, DistanceResponseState::Arrived);
+        assert_eq!(update.next_station.map(|s| s.station_cd), Some(2));
+
+        // 約0.73km: 到着後に離れたので接近中ではなく発車済み
+        let update = tracker.update(35.0, 139.008).unwrap();
+        assert_eq!(update.state, DistanceResponseState::Away);
+        assert_eq!(update.station.station_cd, 1);
+    }
+
+    #[test]
+    fn infers_next_station_skipping_passing_stations() {
+        let mut tracker = new_tracker();
+        tracker.update(35.0, 139.0);
+
+        let update = tracker.update(35.0, 139.014).unwrap();
+        assert_eq!(update.state, DistanceResponseState::Approaching);
+        assert_eq!(update.station.station_cd, 2);
+        assert_eq!(update.next_station.map(|s| s.station_cd), Some(4));
+
+        let update = tracker.update(35.0, 139.02).unwrap();
+        assert_eq!(update.state, DistanceResponseState::Arrived);
+        assert_eq!(update.station.station_cd, 2);
+
+        // 逆方向へ走ると次の駅も逆になる
+        let mut tracker = new_tracker();
+        tracker.update(35.0, 139.06);
+        let update = tracker.update(35.0, 139.024).unwrap();
+        assert_eq!(update.station.station_cd, 2);
+        assert_eq!(update.next_station.map(|s| s.station_cd), Some(1));
+    }
+
+    #[test]
+    fn does_not_flap_between_stations_near_midpoint() {
+        let mut tracker = new_tracker();
+        tracker.update(35.0, 139.0);
+        let update = tracker.update(35.0, 139.0099).unwrap();
+        assert_eq!(update.station.station_cd, 1);
+
+        // 中間点をわずかに越えただけでは駅を切り替えない
+        assert_eq!(tracker.update(35.0, 139.0101), None);
+    }
+}
@@ -1,5 +1,11 @@
 use serde::{Deserialize, Serialize};
 
+use super::service_day::days_in_month;
+
+pub const EARTH_RADIUS_IN_KM: f64 = 6371.0;
+// open_ymd, close_ymdが不明な駅の値
+pub const UNKNOWN_YMD: &str = "0000-00-00";
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct StationIdWithDistance {
     pub station_id: u32,
@@ -17,9 +23,236 @@ impl StationIdWithDistance {
     }
 }
 
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct StationFilter {
+    pub line_type: Option<u32>,
+    pub company_cd: Option<u32>,
+    pub e_status: u32,
+}
+
+impl StationFilter {
+    pub fn new(line_type: Option<u32>, company_cd: Option<u32>, e_status: Option<u32>) -> Self {
+        Self {
+            line_type,
+            company_cd,
+            // NOTE: 指定がなければ他のクエリと同じく運用中の駅のみを対象とする
+            e_status: e_status.unwrap_or(0),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct CompanyFilter {
+    pub company_type: Option<u32>,
+    pub rr_cd: Option<u32>,
+    pub e_status: u32,
+}
+
+impl CompanyFilter {
+    pub fn new(company_type: Option<u32>, rr_cd: Option<u32>, e_status: Option<u32>) -> Self {
+        Self {
+            company_type,
+            rr_cd,
+            // NOTE: 指定がなければ運用中の事業者のみを対象とする
+            e_status: e_status.unwrap_or(0),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct TrainTypeFilter {
+    pub type_cd: Option<u32>,
+    pub kind: Option<u32>,
+    pub name: Option<String>,
+    pub line_cd: Option<u32>,
+    pub company_cd: Option<u32>,
+}
+
+impl TrainTypeFilter {
+    pub fn new(
+        kind: Option<u32>,
+        name: Option<String>,
+        line_cd: Option<u32>,
+        company_cd: Option<u32>,
+    ) -> Self {
+        Self {
+            type_cd: None,
+            kind,
+            // NOTE: 空文字は未指定として扱う
+            name: name
+                .map(|name| name.trim().to_string())
+                .filter(|name| !name.is_empty()),
+            line_cd,
+            company_cd,
+        }
+    }
+
+    pub fn by_type_cd(type_cd: u32) -> Self {
+        Self {
+            type_cd: Some(type_cd),
+            ..Default::default()
+        }
+    }
+}
+
+// "YYYY-MM-DD"。open_ymd, close_ymdと文字列のまま比較できる
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+pub struct AsOf(String);
+
+impl AsOf {
+    pub fn parse(value: &str) -> Option<Self> {
+        let parts: Vec<&str> = value.split('-').collect();
+        let [year, month, day] = parts.as_slice() else {
+            return None;
+        };
+        if year.len() != 4 || month.len() != 2 || day.len() != 2 {
+            return None;
+        }
+        // NOTE: parseは先頭の'+'を受け付けるので、数字だけか先に確かめる
+        if !parts
+            .iter()
+            .all(|part| part.bytes().all(|b| b.is_ascii_digit()))
+        {
+            return None;
+        }
+        let year: u32 = year.parse().ok()?;
+        let month: u32 = month.parse().ok()?;
+        let day: u32 = day.parse().ok()?;
+        if year == 0
+            || !(1..=12).contains(&month)
+            || !(1..=days_in_month(year as i64, month)).contains(&day)
+        {
+            return None;
+        }
+
+        Some(Self(value.to_string()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    // その日に営業していたか。開業日・廃止日が不明な駅はe_statusで補う
+    pub fn is_operating(&self, open_ymd: &str, close_ymd: &str, e_status: u32) -> bool {
+        let opened = match open_ymd {
+            // NOTE: e_status = 1(開業予定)で開業日が不明な駅は、どの日付でも含めない
+            UNKNOWN_YMD => e_status != 1,
+            open_ymd => open_ymd <= self.as_str(),
+        };
+        let not_closed = match close_ymd {
+            // NOTE: e_status = 2(廃止)で廃止日が不明な駅は、どの日付でも含めない
+            UNKNOWN_YMD => e_status != 2,
+            close_ymd => close_ymd > self.as_str(),
+        };
+
+        matches!(e_status, 0..=2) && opened && not_closed
+    }
+}
+
+// NaNは範囲に含まれないので、これだけで弾ける
+pub fn is_valid_coordinates(latitude: f64, longitude: f64) -> bool {
+    (-90.0..=90.0).contains(&latitude) && (-180.0..=180.0).contains(&longitude)
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct BoundingBox {
+    pub min_latitude: f64,
+    pub min_longitude: f64,
+    pub max_latitude: f64,
+    pub max_longitude: f64,
+}
+
+impl BoundingBox {
+    pub fn new(
+        min_latitude: f64,
+        min_longitude: f64,
+        max_latitude: f64,
+        max_longitude: f64,
+    ) -> Self {
+        Self {
+            min_latitude,
+            min_longitude,
+            max_latitude,
+            max_longitude,
+        }
+    }
+
+    pub fn contains(&self, latitude: f64, longitude: f64) -> bool {
+        (self.min_latitude..=self.max_latitude).contains(&latitude)
+            && (self.min_longitude..=self.max_longitude).contains(&longitude)
+    }
+
+    // NOTE: 180度経線をまたぐ範囲には対応しない
+    pub fn is_valid(&self) -> bool {
+        is_valid_coordinates(self.min_latitude, self.min_longitude)
+            && is_valid_coordinates(self.max_latitude, self.max_longitude)
+            && self.min_latitude <= self.max_latitude
+            && self.min_longitude <= self.max_longitude
+    }
+}
+
+// 駅に付け加える属性。含めないものは問い合わせ自体を省く
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct StationFieldMask {
+    pub station_numbers: bool,
+    // 乗り換え路線(Station.lines)
+    pub lines: bool,
+    pub companies: bool,
+    pub train_types: bool,
+}
+
+impl Default for StationFieldMask {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+impl StationFieldMask {
+    pub const ALL: Self = Self {
+        station_numbers: true,
+        lines: true,
+        companies: true,
+        train_types: true,
+    };
+    pub const NONE: Self = Self {
+        station_numbers: false,
+        lines: false,
+        companies: false,
+        train_types: false,
+    };
+
+    // FieldMaskのpaths。指定された属性だけを含める。知らないpathはそのまま返す
+    pub fn from_paths<S: AsRef<str>>(paths: &[S]) -> Result<Self, String> {
+        let mut mask = Self::NONE;
+        for path in paths {
+            match path.as_ref().trim() {
+                "station_numbers" => mask.station_numbers = true,
+                "lines" => mask.lines = true,
+                "company" | "companies" => mask.companies = true,
+                "train_type" | "train_types" => mask.train_types = true,
+                path => return Err(path.to_string()),
+            }
+        }
+
+        Ok(mask)
+    }
+}
+
+// SQL版の `6371 * acos(...)` と同じ式
+pub fn distance_in_km(lat: f64, lon: f64, target_lat: f64, target_lon: f64) -> f64 {
+    let cos_value = target_lat.to_radians().cos()
+        * lat.to_radians().cos()
+        * (lon.to_radians() - target_lon.to_radians()).cos()
+        + target_lat.to_radians().sin() * lat.to_radians().sin();
+    EARTH_RADIUS_IN_KM * cos_value.clamp(-1.0, 1.0).acos()
+}
+
 #[cfg(test)]
 mod tests {
-    use super::StationIdWithDistance;
+    use super::{
+        AsOf, BoundingBox, CompanyFilter, StationFieldMask, StationFilter, StationIdWithDistance,
+        TrainTypeFilter,
+    };
 
     #[test]
     fn new() {
@@ -33,4 +266,123 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn station_filter_defaults_to_operating_stations() {
+        let filter = StationFilter::new(Some(2), None, None);
+        assert_eq!(
+            filter,
+            StationFilter {
+                line_type: Some(2),
+                company_cd: None,
+                e_status: 0
+            }
+        );
+    }
+
+    #[test]
+    fn company_filter_defaults_to_operating_companies() {
+        let filter = CompanyFilter::new(Some(1), Some(11), None);
+        assert_eq!(
+            filter,
+            CompanyFilter {
+                company_type: Some(1),
+                rr_cd: Some(11),
+                e_status: 0
+            }
+        );
+    }
+
+    #[test]
+    fn train_type_filter_ignores_blank_name() {
+        let filter = TrainTypeFilter::new(Some(2), Some("  ".to_string()), None, Some(2));
+        assert_eq!(filter.name, None);
+        assert_eq!(filter.kind, Some(2));
+        assert_eq!(filter.company_cd, Some(2));
+
+        let filter = TrainTypeFilter::new(None, Some(" 快速 ".to_string()), None, None);
+        assert_eq!(filter.name.as_deref(), Some("快速"));
+        assert_eq!(TrainTypeFilter::by_type_cd(101).type_cd, Some(101));
+    }
+
+    #[test]
+    fn as_of_parse() {
+        assert_eq!(AsOf::parse("2025-01-19").unwrap().as_str(), "2025-01-19");
+        assert_eq!(AsOf::parse("2024-02-29").unwrap().as_str(), "2024-02-29");
+        for value in [
+            "",
+            "2025-1-19",
+            "20250119",
+            "2025-13-01",
+            "0000-00-00",
+            "yyyy-mm-dd",
+            "+025-01-01",
+            "2025-+1-01",
+            "2025-02-29",
+            "2025-02-31",
+            "2025-04-31",
+        ] {
+            assert_eq!(AsOf::parse(value), None);
+        }
+    }
+
+    #[test]
+    fn as_of_is_operating() {
+        let as_of = AsOf::parse("2020-04-01").unwrap();
+        // 開業日・廃止日が不明な営業中の駅
+        assert!(as_of.is_operating("0000-00-00", "0000-00-00", 0));
+        assert!(as_of.is_operating("2020-04-01", "0000-00-00", 0));
+        assert!(!as_of.is_operating("2025-01-19", "0000-00-00", 0));
+        // 廃止された駅は廃止日の前日まで
+        assert!(as_of.is_operating("0000-00-00", "2022-03-12", 2));
+        assert!(!as_of.is_operating("0000-00-00", "2020-04-01", 2));
+        assert!(!as_of.is_operating("0000-00-00", "0000-00-00", 2));
+        // 開業予定の駅
+        assert!(!as_of.is_operating("0000-00-00", "0000-00-00", 1));
+        assert!(AsOf::parse("2031-04-01")
+            .unwrap()
+            .is_operating("2030-12-01", "0000-00-00", 1));
+        assert!(!as_of.is_operating("0000-00-00", "0000-00-00", 3));
+    }
+
+    #[test]
+    fn bounding_box_contains() {
+        let bounding_box = BoundingBox::new(35.6, 139.6, 35.8, 139.8);
+        assert!(bounding_box.contains(35.681236, 139.767125));
+        assert!(!bounding_box.contains(35.681236, 139.9));
+        assert!(!bounding_box.contains(35.5, 139.767125));
+    }
+
+    #[test]
+    fn bounding_box_is_valid() {
+        assert!(BoundingBox::new(35.6, 139.6, 35.8, 139.8).is_valid());
+        assert!(BoundingBox::new(35.6, 139.6, 35.6, 139.6).is_valid());
+        assert!(!BoundingBox::new(35.8, 139.6, 35.6, 139.8).is_valid());
+        assert!(!BoundingBox::new(35.6, 139.8, 35.8, 139.6).is_valid());
+        assert!(!BoundingBox::new(-91.0, 139.6, 35.8, 139.8).is_valid());
+        assert!(!BoundingBox::new(35.6, 139.6, 35.8, 181.0).is_valid());
+        assert!(!BoundingBox::new(f64::NAN, 139.6, 35.8, 139.8).is_valid());
+    }
+
+    #[test]
+    fn station_field_mask_from_paths() {
+        assert_eq!(StationFieldMask::default(), StationFieldMask::ALL);
+        assert_eq!(
+            StationFieldMask::from_paths::<&str>(&[]),
+            Ok(StationFieldMask::NONE)
+        );
+        assert_eq!(
+            StationFieldMask::from_paths(&["station_numbers", " train_type"]),
+            Ok(StationFieldMask {
+                station_numbers: true,
+                lines: false,
+                companies: false,
+                train_types: true,
+            })
+        );
+        assert_eq!(
+            StationFieldMask::from_paths(&["lines", "line.name"]),
+            Err("line.name".to_string())
+        );
+    }
 }
@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Prefecture {
+    // JIS X 0401の都道府県コード
+    pub pref_cd: u32,
+    pub pref_name: String,
+    pub pref_name_k: String,
+    pub pref_name_r: String,
+    pub pref_name_zh: String,
+    pub pref_name_ko: String,
+}
+
+impl Prefecture {
+    pub fn new(
+        pref_cd: u32,
+        pref_name: String,
+        pref_name_k: String,
+        pref_name_r: String,
+        pref_name_zh: String,
+        pref_name_ko: String,
+    ) -> Self {
+        Self {
+            pref_cd,
+            pref_name,
+            pref_name_k,
+            pref_name_r,
+            pref_name_zh,
+            pref_name_ko,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Prefecture;
+
+    #[test]
+    fn new() {
+        let prefecture = Prefecture::new(
+            13,
+            "東京都".to_string(),
+            "トウキョウト".to_string(),
+            "Tōkyō".to_string(),
+            "东京都".to_string(),
+            "도쿄도".to_string(),
+        );
+        assert_eq!(
+            prefecture,
+            Prefecture {
+                pref_cd: 13,
+                pref_name: "東京都".to_string(),
+                pref_name_k: "トウキョウト".to_string(),
+                pref_name_r: "Tōkyō".to_string(),
+                pref_name_zh: "东京都".to_string(),
+                pref_name_ko: "도쿄도".to_string(),
+            }
+        );
+    }
+}
@@ -0,0 +1,207 @@
+use crate::station_api::StopCondition;
+
+use super::misc::AsOf;
+
+// 平日ダイヤか土休日ダイヤか
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ServiceDay {
+    Weekday,
+    Holiday,
+}
+
+impl ServiceDay {
+    // "YYYY-MM-DD"の日付が平日か土休日か
+    pub fn from_ymd(value: &str) -> Option<Self> {
+        let as_of = AsOf::parse(value)?;
+        let parts: Vec<u32> = as_of
+            .as_str()
+            .split('-')
+            .filter_map(|part| part.parse().ok())
+            .collect();
+        let [year, month, day] = parts.as_slice() else {
+            return None;
+        };
+        if *day > days_in_month(*year as i64, *month) {
+            return None;
+        }
+
+        Some(Self::from_date(*year as i64, *month, *day))
+    }
+
+    pub fn from_date(year: i64, month: u32, day: u32) -> Self {
+        let days = days_from_civil(year, month, day);
+        // NOTE: 多くの事業者が年末年始(12/30〜1/3)を土休日ダイヤで運行する
+        let year_end = (month == 12 && day >= 30) || (month == 1 && day <= 3);
+
+        if matches!(weekday(days), 0 | 6) || year_end || is_public_holiday(days) {
+            ServiceDay::Holiday
+        } else {
+            ServiceDay::Weekday
+        }
+    }
+
+    // その日に停車するか。通過駅はそのまま残す
+    pub fn serves(&self, stop_condition: StopCondition) -> bool {
+        match self {
+            ServiceDay::Weekday => stop_condition != StopCondition::Holiday,
+            ServiceDay::Holiday => stop_condition != StopCondition::Weekday,
+        }
+    }
+}
+
+// 1970-01-01からの日数
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = month as i64;
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days - era * 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+// 0が日曜日
+fn weekday(days: i64) -> i64 {
+    (days + 4).rem_euclid(7)
+}
+
+pub(crate) fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// 春分日・秋分日。1980〜2099年の近似式
+fn equinox_day(year: i64, base: f64) -> u32 {
+    let elapsed = year - 1980;
+    ((base + 0.242194 * elapsed as f64).floor() as i64 - elapsed.div_euclid(4)) as u32
+}
+
+// 国民の祝日に関する法律で日付が決まる祝日。2000年以降のハッピーマンデー制度を前提にする
+fn is_national_holiday(days: i64) -> bool {
+    let (year, month, day) = civil_from_days(days);
+    let nth_monday = |n: u32| weekday(days) == 1 && (day - 1) / 7 + 1 == n;
+
+    match (month, day) {
+        (1, 1) | (2, 11) | (4, 29) | (5, 3) | (5, 4) | (5, 5) | (11, 3) | (11, 23) => true,
+        (1, _) => nth_monday(2),
+        (2, 23) => year >= 2020,
+        (3, _) => day == equinox_day(year, 20.8431),
+        // 天皇の即位の日、即位礼正殿の儀の行われる日
+        (5, 1) | (10, 22) => year == 2019,
+        // 海の日、スポーツの日。東京オリンピックの年は移動した
+        (7, _) => match year {
+            2020 => day == 23 || day == 24,
+            2021 => day == 22 || day == 23,
+            _ => year >= 2003 && nth_monday(3),
+        },
+        // 山の日
+        (8, _) => match year {
+            2020 => day == 10,
+            2021 => day == 8,
+            _ => year >= 2016 && day == 11,
+        },
+        (9, _) => day == equinox_day(year, 23.2488) || (year >= 2003 && nth_monday(3)),
+        (10, _) => !matches!(year, 2020 | 2021) && nth_monday(2),
+        (12, 23) => (1989..=2018).contains(&year),
+        _ => false,
+    }
+}
+
+fn is_public_holiday(days: i64) -> bool {
+    if is_national_holiday(days) {
+        return true;
+    }
+
+    // 振替休日: 日曜日の祝日から続く祝日の翌日
+    let mut previous = days - 1;
+    while is_national_holiday(previous) {
+        if weekday(previous) == 0 {
+            return true;
+        }
+        previous -= 1;
+    }
+
+    // 国民の休日: 前後の日が祝日の平日
+    weekday(days) != 0 && is_national_holiday(days - 1) && is_national_holiday(days + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{civil_from_days, days_from_civil, ServiceDay};
+    use crate::station_api::StopCondition;
+
+    fn holiday(value: &str) -> bool {
+        ServiceDay::from_ymd(value).unwrap() == ServiceDay::Holiday
+    }
+
+    #[test]
+    fn civil_days_round_trip() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        for (year, month, day) in [(2000, 2, 29), (2024, 12, 31), (2025, 3, 1)] {
+            assert_eq!(
+                civil_from_days(days_from_civil(year, month, day)),
+                (year, month, day)
+            );
+        }
+    }
+
+    #[test]
+    fn weekends_and_holidays() {
+        // 土曜日・日曜日
+        assert!(holiday("2025-06-07"));
+        assert!(holiday("2025-06-08"));
+        assert!(!holiday("2025-06-09"));
+        // 成人の日、春分の日、秋分の日
+        assert!(holiday("2025-01-13"));
+        assert!(holiday("2025-03-20"));
+        assert!(holiday("2024-09-22"));
+        // 振替休日
+        assert!(holiday("2025-02-24"));
+        assert!(holiday("2025-05-06"));
+        // 国民の休日
+        assert!(holiday("2026-09-22"));
+        assert!(holiday("2019-04-30"));
+        assert!(holiday("2019-05-02"));
+        // 東京オリンピックで移動したスポーツの日
+        assert!(holiday("2021-07-23"));
+        assert!(!holiday("2021-10-11"));
+        // 年末年始
+        assert!(holiday("2025-12-30"));
+        assert!(!holiday("2025-12-26"));
+
+        assert_eq!(ServiceDay::from_ymd("2025-02-29"), None);
+        assert_eq!(ServiceDay::from_ymd("2025/01/01"), None);
+    }
+
+    #[test]
+    fn serves() {
+        assert!(ServiceDay::Weekday.serves(StopCondition::Weekday));
+        assert!(!ServiceDay::Weekday.serves(StopCondition::Holiday));
+        assert!(!ServiceDay::Holiday.serves(StopCondition::Weekday));
+        assert!(ServiceDay::Holiday.serves(StopCondition::Partial));
+        assert!(ServiceDay::Holiday.serves(StopCondition::Not));
+    }
+}
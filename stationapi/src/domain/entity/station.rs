@@ -200,6 +200,75 @@ impl Station {
     }
 }
 
+// NOTE: 引数が多いので、テストでは最小限の駅を作って必要な属性だけ書き換える
+#[cfg(test)]
+impl Station {
+    pub(crate) fn test_fixture(station_cd: u32) -> Self {
+        Self::new(
+            station_cd,
+            station_cd,
+            station_cd.to_string(),
+            station_cd.to_string(),
+            None,
+            None,
+            None,
+            vec![],
+            None,
+            None,
+            None,
+            None,
+            1,
+            None,
+            vec![],
+            13,
+            "".to_string(),
+            "".to_string(),
+            139.0,
+            35.0,
+            "0000-00-00".to_string(),
+            "0000-00-00".to_string(),
+            0,
+            station_cd,
+            StopCondition::All,
+            None,
+            false,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            0.0,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::Station;
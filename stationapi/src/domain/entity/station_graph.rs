@@ -0,0 +1,407 @@
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, VecDeque},
+};
+
+use crate::station_api::JourneySearchCriterion;
+
+use super::{
+    journey::{Journey, JourneyLeg},
+    station::Station,
+};
+
+// NOTE: 代替経路の探索が膨らまないよう、1経路あたりの探索回数に上限を設ける
+const MAX_SEARCHES_PER_JOURNEY: usize = 8;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+enum Service {
+    // 路線(line_cd)を各駅に停車しながら乗り通す
+    Local(u32),
+    // station_station_typesの停車駅を種別(line_group_cd)で乗り通す
+    Train(u32),
+}
+
+impl From<&JourneyLeg> for Service {
+    fn from(leg: &JourneyLeg) -> Self {
+        match leg.line_group_cd {
+            Some(line_group_cd) => Service::Train(line_group_cd),
+            None => Service::Local(leg.line_cd),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+struct Node {
+    station_cd: u32,
+    service: Service,
+}
+
+#[derive(Debug, Default)]
+pub struct StationGraph {
+    station_g_cds: HashMap<u32, u32>,
+    line_cds: HashMap<u32, u32>,
+    station_cds_by_group: HashMap<u32, Vec<u32>>,
+    services: HashMap<u32, Vec<Service>>,
+    edges: HashMap<Node, Vec<u32>>,
+}
+
+impl StationGraph {
+    pub fn new(stations: &[Station]) -> Self {
+        let mut graph = Self::default();
+        let mut local_stops: BTreeMap<u32, BTreeSet<(u32, u32)>> = BTreeMap::new();
+        let mut train_stops: BTreeMap<u32, BTreeSet<(u32, u32)>> = BTreeMap::new();
+
+        for station in stations.iter().filter(|s| s.e_status == 0) {
+            if graph
+                .station_g_cds
+                .insert(station.station_cd, station.station_g_cd)
+                .is_none()
+            {
+                graph.line_cds.insert(station.station_cd, station.line_cd);
+                graph
+                    .station_cds_by_group
+                    .entry(station.station_g_cd)
+                    .or_default()
+                    .push(station.station_cd);
+            }
+
+            local_stops
+                .entry(station.line_cd)
+                .or_default()
+                .insert((station.e_sort, station.station_cd));
+
+            if let (Some(line_group_cd), Some(sst_id)) = (station.line_group_cd, station.sst_id) {
+                if station.pass != Some(1) {
+                    train_stops
+                        .entry(line_group_cd)
+                        .or_default()
+                        .insert((sst_id, station.station_cd));
+                }
+            }
+        }
+
+        for (line_cd, stops) in local_stops {
+            graph.connect(Service::Local(line_cd), stops);
+        }
+        for (line_group_cd, stops) in train_stops {
+            graph.connect(Service::Train(line_group_cd), stops);
+        }
+
+        graph
+    }
+
+    fn connect(&mut self, service: Service, stops: BTreeSet<(u32, u32)>) {
+        let station_cds: Vec<u32> = stops
+            .into_iter()
+            .map(|(_, station_cd)| station_cd)
+            .collect();
+
+        for station_cd in station_cds.iter() {
+            let services = self.services.entry(*station_cd).or_default();
+            if !services.contains(&service) {
+                services.push(service);
+            }
+        }
+
+        for pair in station_cds.windows(2) {
+            let (from, to) = (pair[0], pair[1]);
+            if from == to {
+                continue;
+            }
+            self.edges
+                .entry(Node {
+                    station_cd: from,
+                    service,
+                })
+                .or_default()
+                .push(to);
+            self.edges
+                .entry(Node {
+                    station_cd: to,
+                    service,
+                })
+                .or_default()
+                .push(from);
+        }
+    }
+
+    pub fn find_journeys(
+        &self,
+        from_station_g_cd: u32,
+        to_station_g_cd: u32,
+        criterion: JourneySearchCriterion,
+        limit: usize,
+    ) -> Vec<Journey> {
+        if from_station_g_cd == to_station_g_cd {
+            return vec![];
+        }
+
+        let mut journeys: Vec<Journey> = vec![];
+        let mut candidates: VecDeque<BTreeSet<Service>> = VecDeque::from([BTreeSet::new()]);
+        let mut searched: HashSet<BTreeSet<Service>> = HashSet::new();
+
+        // 見つかった経路の運転系統を1つずつ除外して探索し直すことで代替経路を得る
+        while let Some(excluded) = candidates.pop_front() {
+            if journeys.len() >= limit
+                || searched.len() >= limit.saturating_mul(MAX_SEARCHES_PER_JOURNEY)
+            {
+                break;
+            }
+            if !searched.insert(excluded.clone()) {
+                continue;
+            }
+
+            let Some(journey) =
+                self.find_journey(from_station_g_cd, to_station_g_cd, criterion, &excluded)
+            else {
+                continue;
+            };
+
+            for leg in journey.legs.iter() {
+                let mut excluded = excluded.clone();
+                excluded.insert(Service::from(leg));
+                candidates.push_back(excluded);
+            }
+            if !journeys.contains(&journey) {
+                journeys.push(journey);
+            }
+        }
+
+        journeys.sort_by_key(|journey| {
+            cost_key(criterion, journey.station_count, journey.transfer_count)
+        });
+
+        journeys
+    }
+
+    fn find_journey(
+        &self,
+        from_station_g_cd: u32,
+        to_station_g_cd: u32,
+        criterion: JourneySearchCriterion,
+        excluded: &BTreeSet<Service>,
+    ) -> Option<Journey> {
+        let mut costs: HashMap<Node, (u32, u32)> = HashMap::new();
+        let mut prev: HashMap<Node, Node> = HashMap::new();
+        let mut heap = BinaryHeap::new();
+
+        for node in self.nodes_in_group(from_station_g_cd, excluded) {
+            costs.insert(node, (0, 0));
+            heap.push(Reverse((cost_key(criterion, 0, 0), node)));
+        }
+
+        while let Some(Reverse((key, node))) = heap.pop() {
+            let (stops, transfers) = costs[&node];
+            if cost_key(criterion, stops, transfers) < key {
+                continue;
+            }
+
+            let station_g_cd = self.station_g_cds[&node.station_cd];
+            if station_g_cd == to_station_g_cd {
+                return Some(self.build_journey(node, &prev));
+            }
+
+            let rides = self
+                .edges
+                .get(&node)
+                .into_iter()
+                .flatten()
+                .map(|station_cd| {
+                    let next = Node {
+                        station_cd: *station_cd,
+                        service: node.service,
+                    };
+                    (next, (stops + 1, transfers))
+                });
+            let transfers = self
+                .nodes_in_group(station_g_cd, excluded)
+                .filter(|next| next.service != node.service)
+                .map(|next| (next, (stops, transfers + 1)));
+
+            for (next, cost) in rides.chain(transfers).collect::<Vec<_>>() {
+                let is_cheaper = costs.get(&next).is_none_or(|current| {
+                    cost_key(criterion, cost.0, cost.1) < cost_key(criterion, current.0, current.1)
+                });
+                if is_cheaper {
+                    costs.insert(next, cost);
+                    prev.insert(next, node);
+                    heap.push(Reverse((cost_key(criterion, cost.0, cost.1), next)));
+                }
+            }
+        }
+
+        None
+    }
+
+    fn nodes_in_group<'a>(
+        &'a self,
+        station_g_cd: u32,
+        excluded: &'a BTreeSet<Service>,
+    ) -> impl Iterator<Item = Node> + 'a {
+        self.station_cds_by_group
+            .get(&station_g_cd)
+            .into_iter()
+            .flatten()
+            .flat_map(move |station_cd| {
+                self.services
+                    .get(station_cd)
+                    .into_iter()
+                    .flatten()
+                    .filter(|service| !excluded.contains(service))
+                    .map(move |service| Node {
+                        station_cd: *station_cd,
+                        service: *service,
+                    })
+            })
+    }
+
+    fn build_journey(&self, goal: Node, prev: &HashMap<Node, Node>) -> Journey {
+        let mut path = vec![goal];
+        let mut node = goal;
+        while let Some(prev_node) = prev.get(&node) {
+            path.push(*prev_node);
+            node = *prev_node;
+        }
+        path.reverse();
+
+        let mut legs: Vec<JourneyLeg> = vec![];
+        let mut current: Option<(Service, Vec<u32>)> = None;
+        for node in path {
+            match current.as_mut() {
+                Some((service, station_cds)) if *service == node.service => {
+                    station_cds.push(node.station_cd)
+                }
+                _ => {
+                    if let Some(leg) = current.take().and_then(|leg| self.build_leg(leg)) {
+                        legs.push(leg);
+                    }
+                    current = Some((node.service, vec![node.station_cd]));
+                }
+            }
+        }
+        if let Some(leg) = current.and_then(|leg| self.build_leg(leg)) {
+            legs.push(leg);
+        }
+
+        Journey::new(legs)
+    }
+
+    fn build_leg(&self, (service, station_cds): (Service, Vec<u32>)) -> Option<JourneyLeg> {
+        // NOTE: 乗車せずに乗り換えただけの区間は経路に含めない
+        if station_cds.len() < 2 {
+            return None;
+        }
+
+        let leg = match service {
+            Service::Local(line_cd) => JourneyLeg::new(line_cd, None, station_cds),
+            Service::Train(line_group_cd) => {
+                let line_cd = self.line_cds[&station_cds[0]];
+                JourneyLeg::new(line_cd, Some(line_group_cd), station_cds)
+            }
+        };
+
+        Some(leg)
+    }
+}
+
+fn cost_key(criterion: JourneySearchCriterion, stops: u32, transfers: u32) -> (u32, u32) {
+    match criterion {
+        JourneySearchCriterion::FewestStops => (stops, transfers),
+        JourneySearchCriterion::FewestTransfers => (transfers, stops),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StationGraph;
+    use crate::{domain::entity::station::Station, station_api::JourneySearchCriterion};
+
+    fn station(
+        station_cd: u32,
+        station_g_cd: u32,
+        line_cd: u32,
+        e_sort: u32,
+        sst: Option<(u32, u32)>,
+    ) -> Station {
+        Station {
+            station_g_cd,
+            line_cd,
+            e_sort,
+            has_train_types: sst.is_some(),
+            line_group_cd: sst.map(|(_, line_group_cd)| line_group_cd),
+            pass: sst.map(|_| 0),
+            sst_id: sst.map(|(sst_id, _)| sst_id),
+            ..Station::test_fixture(station_cd)
+        }
+    }
+
+    // 路線1: 101-102-103-104 (種別10は101,103,104に停車)
+    // 路線2: 201(103と同一駅)-202-203
+    // 路線3: 301(101と同一駅)-302-303-304-305(203と同一駅)
+    fn graph() -> StationGraph {
+        StationGraph::new(&[
+            station(101, 101, 1, 1, None),
+            station(101, 101, 1, 1, Some((1, 10))),
+            station(102, 102, 1, 2, None),
+            station(103, 103, 1, 3, None),
+            station(103, 103, 1, 3, Some((2, 10))),
+            station(104, 104, 1, 4, None),
+            station(104, 104, 1, 4, Some((3, 10))),
+            station(201, 103, 2, 1, None),
+            station(202, 202, 2, 2, None),
+            station(203, 203, 2, 3, None),
+            station(301, 101, 3, 1, None),
+            station(302, 302, 3, 2, None),
+            station(303, 303, 3, 3, None),
+            station(304, 304, 3, 4, None),
+            station(305, 203, 3, 5, None),
+        ])
+    }
+
+    #[test]
+    fn find_journeys_by_fewest_stops() {
+        let journeys = graph().find_journeys(101, 203, JourneySearchCriterion::FewestStops, 1);
+        assert_eq!(journeys.len(), 1);
+
+        let journey = &journeys[0];
+        assert_eq!(journey.station_count, 3);
+        assert_eq!(journey.transfer_count, 1);
+        assert_eq!(journey.legs[0].line_cd, 1);
+        assert_eq!(journey.legs[0].line_group_cd, Some(10));
+        assert_eq!(journey.legs[0].station_cds, vec![101, 103]);
+        assert_eq!(journey.legs[1].line_cd, 2);
+        assert_eq!(journey.legs[1].line_group_cd, None);
+        assert_eq!(journey.legs[1].station_cds, vec![201, 202, 203]);
+    }
+
+    #[test]
+    fn find_journeys_by_fewest_transfers() {
+        let journeys = graph().find_journeys(101, 203, JourneySearchCriterion::FewestTransfers, 1);
+        assert_eq!(journeys.len(), 1);
+
+        let journey = &journeys[0];
+        assert_eq!(journey.station_count, 4);
+        assert_eq!(journey.transfer_count, 0);
+        assert_eq!(journey.legs[0].line_cd, 3);
+        assert_eq!(journey.legs[0].station_cds, vec![301, 302, 303, 304, 305]);
+    }
+
+    #[test]
+    fn find_journeys_ranked_alternatives() {
+        let journeys = graph().find_journeys(101, 203, JourneySearchCriterion::FewestStops, 3);
+        assert_eq!(journeys.len(), 3);
+        assert_eq!(
+            journeys
+                .iter()
+                .map(|j| (j.station_count, j.transfer_count))
+                .collect::<Vec<_>>(),
+            vec![(3, 1), (4, 0), (4, 1)]
+        );
+    }
+
+    #[test]
+    fn find_journeys_within_same_group() {
+        let journeys = graph().find_journeys(103, 103, JourneySearchCriterion::FewestStops, 3);
+        assert!(journeys.is_empty());
+    }
+}
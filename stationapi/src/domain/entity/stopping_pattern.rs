@@ -0,0 +1,227 @@
+use std::collections::HashSet;
+
+use crate::station_api::StopCondition;
+
+use super::{station::Station, train_type::TrainType};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct StoppingPatternStation {
+    pub station: Station,
+    // その系統が通らない駅はNone
+    pub first_stop_condition: Option<StopCondition>,
+    pub second_stop_condition: Option<StopCondition>,
+}
+
+impl StoppingPatternStation {
+    // 一方だけが停車する駅
+    pub fn differs(&self) -> bool {
+        is_stop(self.first_stop_condition) != is_stop(self.second_stop_condition)
+    }
+}
+
+// 同じ線路を走る2つの系統の停車駅を、駅の並び順に揃えたもの
+#[derive(Clone, Debug, PartialEq)]
+pub struct StoppingPattern {
+    pub first_train_type: Option<TrainType>,
+    pub second_train_type: Option<TrainType>,
+    pub stations: Vec<StoppingPatternStation>,
+}
+
+impl StoppingPattern {
+    pub fn new(first: Vec<Station>, second: Vec<Station>) -> Self {
+        let first = ordered_stations(first);
+        let mut second = ordered_stations(second);
+
+        // NOTE: 上りと下りのように逆向きの系統どうしでも比べられるよう、共通の駅の並びで向きを揃える
+        let common_positions: Vec<usize> = second
+            .iter()
+            .filter_map(|s| first.iter().position(|f| f.station_g_cd == s.station_g_cd))
+            .collect();
+        if common_positions.first() > common_positions.last() {
+            second.reverse();
+        }
+
+        let first_train_type = train_type_of(&first);
+        let second_train_type = train_type_of(&second);
+
+        let mut stations: Vec<StoppingPatternStation> = first
+            .into_iter()
+            .map(|station| StoppingPatternStation {
+                first_stop_condition: Some(station.stop_condition),
+                second_stop_condition: None,
+                station,
+            })
+            .collect();
+
+        // 片方にしかない駅は、直前の共通の駅の後ろに入れる
+        let mut cursor: Option<usize> = None;
+        for station in second {
+            match stations
+                .iter()
+                .position(|s| s.station.station_g_cd == station.station_g_cd)
+            {
+                Some(index) => {
+                    stations[index].second_stop_condition = Some(station.stop_condition);
+                    cursor = Some(index);
+                }
+                None => {
+                    let index = cursor.map_or(0, |cursor| cursor + 1);
+                    stations.insert(
+                        index,
+                        StoppingPatternStation {
+                            first_stop_condition: None,
+                            second_stop_condition: Some(station.stop_condition),
+                            station,
+                        },
+                    );
+                    cursor = Some(index);
+                }
+            }
+        }
+
+        Self {
+            first_train_type,
+            second_train_type,
+            stations,
+        }
+    }
+
+    pub fn has_common_station(&self) -> bool {
+        self.stations
+            .iter()
+            .any(|s| s.first_stop_condition.is_some() && s.second_stop_condition.is_some())
+    }
+}
+
+fn is_stop(stop_condition: Option<StopCondition>) -> bool {
+    stop_condition.is_some_and(|stop_condition| stop_condition != StopCondition::Not)
+}
+
+// station_station_typesの並び順にし、同じ駅グループは1つにまとめる
+fn ordered_stations(mut stations: Vec<Station>) -> Vec<Station> {
+    stations.sort_by_key(|s| s.sst_id);
+
+    let mut seen = HashSet::new();
+    let mut ordered: Vec<Station> = vec![];
+    for station in stations {
+        if seen.insert(station.station_g_cd) {
+            ordered.push(station);
+            continue;
+        }
+        // NOTE: 種別が切り替わる駅は2行あるので、停車する方を使う
+        if station.stop_condition != StopCondition::Not {
+            if let Some(existing) = ordered
+                .iter_mut()
+                .find(|s| s.station_g_cd == station.station_g_cd)
+            {
+                if existing.stop_condition == StopCondition::Not {
+                    *existing = station;
+                }
+            }
+        }
+    }
+    ordered
+}
+
+fn train_type_of(stations: &[Station]) -> Option<TrainType> {
+    stations
+        .iter()
+        .find_map(|s| s.train_type.as_deref().cloned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StoppingPattern;
+    use crate::{domain::entity::station::Station, station_api::StopCondition};
+
+    fn station(station_cd: u32, sst_id: u32, stop_condition: StopCondition) -> Station {
+        Station {
+            stop_condition,
+            sst_id: Some(sst_id),
+            ..Station::test_fixture(station_cd)
+        }
+    }
+
+    fn conditions(
+        pattern: &StoppingPattern,
+    ) -> Vec<(u32, Option<StopCondition>, Option<StopCondition>)> {
+        pattern
+            .stations
+            .iter()
+            .map(|s| {
+                (
+                    s.station.station_g_cd,
+                    s.first_stop_condition,
+                    s.second_stop_condition,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn aligns_by_station_order() {
+        // 快速は2を通過し、普通は4まで行かない
+        let rapid = vec![
+            station(1, 1, StopCondition::All),
+            station(3, 3, StopCondition::All),
+            station(2, 2, StopCondition::Not),
+            station(4, 4, StopCondition::All),
+        ];
+        let local = vec![
+            station(1, 11, StopCondition::All),
+            station(5, 12, StopCondition::All),
+            station(2, 13, StopCondition::All),
+            station(3, 14, StopCondition::Weekday),
+        ];
+
+        let pattern = StoppingPattern::new(rapid, local);
+        assert!(pattern.has_common_station());
+        assert_eq!(
+            conditions(&pattern),
+            vec![
+                (1, Some(StopCondition::All), Some(StopCondition::All)),
+                (5, None, Some(StopCondition::All)),
+                (2, Some(StopCondition::Not), Some(StopCondition::All)),
+                (3, Some(StopCondition::All), Some(StopCondition::Weekday)),
+                (4, Some(StopCondition::All), None),
+            ]
+        );
+        let differs: Vec<u32> = pattern
+            .stations
+            .iter()
+            .filter(|s| s.differs())
+            .map(|s| s.station.station_g_cd)
+            .collect();
+        assert_eq!(differs, vec![5, 2, 4]);
+    }
+
+    #[test]
+    fn aligns_opposite_directions() {
+        let outbound = vec![
+            station(1, 1, StopCondition::All),
+            station(2, 2, StopCondition::All),
+            station(3, 3, StopCondition::All),
+        ];
+        let inbound = vec![
+            station(3, 11, StopCondition::All),
+            station(2, 12, StopCondition::Not),
+            station(1, 13, StopCondition::All),
+        ];
+
+        let pattern = StoppingPattern::new(outbound, inbound);
+        assert_eq!(
+            conditions(&pattern),
+            vec![
+                (1, Some(StopCondition::All), Some(StopCondition::All)),
+                (2, Some(StopCondition::All), Some(StopCondition::Not)),
+                (3, Some(StopCondition::All), Some(StopCondition::All)),
+            ]
+        );
+
+        let pattern = StoppingPattern::new(
+            vec![station(1, 1, StopCondition::All)],
+            vec![station(9, 2, StopCondition::All)],
+        );
+        assert!(!pattern.has_common_station());
+    }
+}
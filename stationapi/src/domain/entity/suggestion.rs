@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SuggestionType {
+    StationGroup,
+    Line,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Suggestion {
+    pub suggestion_type: SuggestionType,
+    // 駅ならstation_g_cd、路線ならline_cd
+    pub id: u32,
+    pub name: String,
+    pub matched_name: String,
+    // 一致した列。"stations.station_name_k" など
+    pub matched_field: String,
+    // 一致した列の言語。BCP 47形式
+    pub language: String,
+}
+
+impl Suggestion {
+    pub fn new(
+        suggestion_type: SuggestionType,
+        id: u32,
+        name: String,
+        matched_name: String,
+        matched_field: String,
+        language: String,
+    ) -> Self {
+        Self {
+            suggestion_type,
+            id,
+            name,
+            matched_name,
+            matched_field,
+            language,
+        }
+    }
+}
@@ -0,0 +1,258 @@
+use crate::station_api::{StopCondition, TrainDirection};
+
+use super::{misc::EARTH_RADIUS_IN_KM, station::Station};
+
+// NOTE: GPSの誤差で向きを決めてしまわないよう、この距離以上進んだときだけ進行方向を判定する
+const MIN_PROGRESS_IN_KM: f64 = 0.1;
+// NOTE: 環状線の始点と終点をまたいだときなど、1回でこれ以上動いた分は判定に使わない
+const MAX_STEP_IN_KM: f64 = 5.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TravelDirection {
+    // e_sortの昇順。下り
+    Ascending,
+    // e_sortの降順。上り
+    Descending,
+}
+
+impl TravelDirection {
+    pub fn train_direction(&self) -> TrainDirection {
+        match self {
+            TravelDirection::Ascending => TrainDirection::Outbound,
+            TravelDirection::Descending => TrainDirection::Inbound,
+        }
+    }
+
+    // typesテーブルのdirectionと一致するか
+    pub fn matches(&self, direction: u32) -> bool {
+        direction == TrainDirection::Both as u32 || direction == self.train_direction() as u32
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TravelDirectionEstimate {
+    pub direction: TravelDirection,
+    pub next_station: Option<Station>,
+    pub train_type_matched: Option<bool>,
+}
+
+// 駅を並び順に結んだ折れ線。起点からの距離(km)で位置を表す
+pub struct LinePath {
+    stations: Vec<Station>,
+    chainages: Vec<f64>,
+}
+
+impl LinePath {
+    pub fn new(stations: Vec<Station>) -> Self {
+        let mut chainages = Vec::with_capacity(stations.len());
+        let mut chainage = 0.0;
+        for (index, station) in stations.iter().enumerate() {
+            if let Some(prev) = index.checked_sub(1).map(|prev| &stations[prev]) {
+                let (x, y) = to_plane(prev.lat, prev.lon, station.lat, station.lon);
+                chainage += x.hypot(y);
+            }
+            chainages.push(chainage);
+        }
+
+        Self {
+            stations,
+            chainages,
+        }
+    }
+
+    pub fn stations(&self) -> &[Station] {
+        &self.stations
+    }
+
+    // 最も近い区間に射影した位置
+    pub fn locate(&self, latitude: f64, longitude: f64) -> Option<f64> {
+        if self.stations.len() == 1 {
+            return Some(0.0);
+        }
+
+        self.stations
+            .windows(2)
+            .enumerate()
+            .map(|(index, pair)| {
+                let (start, end) = (&pair[0], &pair[1]);
+                let (end_x, end_y) = to_plane(start.lat, start.lon, end.lat, end.lon);
+                let (x, y) = to_plane(start.lat, start.lon, latitude, longitude);
+                let length_squared = end_x * end_x + end_y * end_y;
+                let t = if length_squared > 0.0 {
+                    ((x * end_x + y * end_y) / length_squared).clamp(0.0, 1.0)
+                } else {
+                    0.0
+                };
+                let offset = (x - t * end_x).hypot(y - t * end_y);
+                let chainage = self.chainages[index] + t * length_squared.sqrt();
+                (offset, chainage)
+            })
+            .min_by(|a, b| a.0.total_cmp(&b.0))
+            .map(|(_, chainage)| chainage)
+    }
+
+    // coordinatesは古い順
+    pub fn infer_direction(&self, coordinates: &[(f64, f64)]) -> Option<TravelDirection> {
+        let chainages: Vec<f64> = coordinates
+            .iter()
+            .filter_map(|&(latitude, longitude)| self.locate(latitude, longitude))
+            .collect();
+        let progress: f64 = chainages
+            .windows(2)
+            .map(|pair| pair[1] - pair[0])
+            .filter(|step| step.abs() <= MAX_STEP_IN_KM)
+            .sum();
+
+        if progress >= MIN_PROGRESS_IN_KM {
+            Some(TravelDirection::Ascending)
+        } else if progress <= -MIN_PROGRESS_IN_KM {
+            Some(TravelDirection::Descending)
+        } else {
+            None
+        }
+    }
+
+    pub fn estimate(&self, coordinates: &[(f64, f64)]) -> Option<TravelDirectionEstimate> {
+        let direction = self.infer_direction(coordinates)?;
+        let &(latitude, longitude) = coordinates.last()?;
+        let position = self.locate(latitude, longitude)?;
+
+        // NOTE: 停車中の駅は次の駅に含めない
+        let is_ahead = |index: &usize| match direction {
+            TravelDirection::Ascending => self.chainages[*index] > position + MIN_PROGRESS_IN_KM,
+            TravelDirection::Descending => self.chainages[*index] < position - MIN_PROGRESS_IN_KM,
+        };
+        let is_stop = |index: &usize| self.stations[*index].stop_condition != StopCondition::Not;
+        let next_index = match direction {
+            TravelDirection::Ascending => (0..self.stations.len()).filter(is_ahead).find(is_stop),
+            TravelDirection::Descending => (0..self.stations.len())
+                .rev()
+                .filter(is_ahead)
+                .find(is_stop),
+        };
+
+        Some(TravelDirectionEstimate {
+            direction,
+            next_station: next_index.map(|index| self.stations[index].clone()),
+            train_type_matched: self
+                .stations
+                .iter()
+                .find_map(|station| station.direction)
+                .map(|train_direction| direction.matches(train_direction)),
+        })
+    }
+}
+
+// 原点(lat, lon)から見た目標地点の平面座標(km)
+fn to_plane(lat: f64, lon: f64, target_lat: f64, target_lon: f64) -> (f64, f64) {
+    let x = (target_lon - lon).to_radians() * lat.to_radians().cos() * EARTH_RADIUS_IN_KM;
+    let y = (target_lat - lat).to_radians() * EARTH_RADIUS_IN_KM;
+    (x, y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LinePath, TravelDirection};
+    use crate::{
+        domain::entity::station::Station,
+        station_api::{StopCondition, TrainDirection},
+    };
+
+    fn station(station_cd: u32, lon: f64, stop_condition: StopCondition) -> Station {
+        Station {
+            lon,
+            stop_condition,
+            // 駅間約1.8km
+            average_distance: 1800.0,
+            ..Station::test_fixture(station_cd)
+        }
+    }
+
+    // 東西に並ぶ4駅。3駅目は通過駅
+    fn new_path(direction: Option<u32>) -> LinePath {
+        LinePath::new(
+            [
+                (1, 139.0, StopCondition::All),
+                (2, 139.02, StopCondition::All),
+                (3, 139.04, StopCondition::Not),
+                (4, 139.06, StopCondition::All),
+            ]
+            .into_iter()
+            .map(|(station_cd, lon, stop_condition)| {
+                let mut station = station(station_cd, lon, stop_condition);
+                station.direction = direction;
+                station
+            })
+            .collect(),
+        )
+    }
+
+    #[test]
+    fn locate() {
+        let path = new_path(None);
+        assert!(path.locate(35.0, 139.0).unwrap().abs() < 1e-9);
+        // 線路から少し外れていても最寄りの区間に射影する
+        let chainage = path.locate(35.001, 139.03).unwrap();
+        let expected = path.locate(35.0, 139.03).unwrap();
+        assert!((chainage - expected).abs() < 1e-3);
+        assert!(chainage > path.locate(35.0, 139.02).unwrap());
+    }
+
+    #[test]
+    fn infer_direction() {
+        let path = new_path(None);
+        assert_eq!(
+            path.infer_direction(&[(35.0, 139.01), (35.0, 139.012), (35.0, 139.014)]),
+            Some(TravelDirection::Ascending)
+        );
+        assert_eq!(
+            path.infer_direction(&[(35.0, 139.05), (35.0, 139.045)]),
+            Some(TravelDirection::Descending)
+        );
+        // 停車中は判定しない
+        assert_eq!(
+            path.infer_direction(&[(35.0, 139.02), (35.0, 139.0201), (35.0, 139.0199)]),
+            None
+        );
+        assert_eq!(path.infer_direction(&[(35.0, 139.02)]), None);
+    }
+
+    #[test]
+    fn estimate_next_station() {
+        let path = new_path(None);
+
+        let estimate = path.estimate(&[(35.0, 139.015), (35.0, 139.02)]).unwrap();
+        assert_eq!(estimate.direction, TravelDirection::Ascending);
+        // 停車中の駅と通過駅は次の駅にならない
+        assert_eq!(estimate.next_station.map(|s| s.station_cd), Some(4));
+        assert_eq!(estimate.train_type_matched, None);
+
+        let estimate = path.estimate(&[(35.0, 139.05), (35.0, 139.045)]).unwrap();
+        assert_eq!(estimate.direction, TravelDirection::Descending);
+        assert_eq!(estimate.next_station.map(|s| s.station_cd), Some(2));
+
+        let estimate = path.estimate(&[(35.0, 139.007), (35.0, 139.002)]).unwrap();
+        assert_eq!(estimate.next_station.map(|s| s.station_cd), Some(1));
+    }
+
+    #[test]
+    fn train_type_matched() {
+        let path = new_path(Some(TrainDirection::Inbound as u32));
+        let coordinates = [(35.0, 139.05), (35.0, 139.045)];
+        assert_eq!(
+            path.estimate(&coordinates).unwrap().train_type_matched,
+            Some(true)
+        );
+        let coordinates = [(35.0, 139.045), (35.0, 139.05)];
+        assert_eq!(
+            path.estimate(&coordinates).unwrap().train_type_matched,
+            Some(false)
+        );
+
+        let path = new_path(Some(TrainDirection::Both as u32));
+        assert_eq!(
+            path.estimate(&coordinates).unwrap().train_type_matched,
+            Some(true)
+        );
+    }
+}
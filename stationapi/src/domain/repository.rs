@@ -1,4 +1,7 @@
+pub mod autocomplete_repository;
 pub mod company_repository;
+pub mod dataset_info_repository;
 pub mod line_repository;
+pub mod prefecture_repository;
 pub mod station_repository;
 pub mod train_type_repository;
@@ -0,0 +1,12 @@
+use async_trait::async_trait;
+
+use crate::domain::{entity::suggestion::Suggestion, error::DomainError};
+
+#[async_trait]
+pub trait AutocompleteRepository: Send + Sync + 'static {
+    async fn find_by_prefix(
+        &self,
+        prefix: &str,
+        limit: u32,
+    ) -> Result<Vec<Suggestion>, DomainError>;
+}
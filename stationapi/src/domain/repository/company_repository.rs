@@ -1,8 +1,13 @@
 use async_trait::async_trait;
 
-use crate::domain::{entity::company::Company, error::DomainError};
+use crate::domain::{
+    entity::{company::Company, misc::CompanyFilter},
+    error::DomainError,
+};
 
 #[async_trait]
 pub trait CompanyRepository: Send + Sync + 'static {
+    async fn find_by_id(&self, id: u32) -> Result<Option<Company>, DomainError>;
     async fn find_by_id_vec(&self, id_vec: &[u32]) -> Result<Vec<Company>, DomainError>;
+    async fn get_all(&self, filter: &CompanyFilter) -> Result<Vec<Company>, DomainError>;
 }
@@ -0,0 +1,8 @@
+use async_trait::async_trait;
+
+use crate::domain::{entity::dataset_info::DatasetInfo, error::DomainError};
+
+#[async_trait]
+pub trait DatasetInfoRepository: Send + Sync + 'static {
+    async fn find_current(&self) -> Result<Option<DatasetInfo>, DomainError>;
+}
@@ -1,11 +1,19 @@
 use async_trait::async_trait;
 
-use crate::domain::{entity::line::Line, error::DomainError};
+use crate::domain::{
+    entity::{line::Line, misc::AsOf},
+    error::DomainError,
+};
 
 #[async_trait]
 pub trait LineRepository: Send + Sync + 'static {
     async fn find_by_id(&self, id: u32) -> Result<Option<Line>, DomainError>;
+    // その日に営業していた駅が1つでもあれば返す
+    async fn find_by_id_as_of(&self, id: u32, as_of: &AsOf) -> Result<Option<Line>, DomainError>;
     async fn find_by_station_id(&self, station_id: u32) -> Result<Option<Line>, DomainError>;
+    // find_by_station_idをまとめて引く。駅ごとに1件で、見つからなかった駅は含めない
+    async fn get_by_station_id_vec(&self, station_id_vec: &[u32])
+        -> Result<Vec<Line>, DomainError>;
     async fn get_by_ids(&self, ids: &[u32]) -> Result<Vec<Line>, DomainError>;
     async fn get_by_station_group_id(
         &self,
@@ -30,4 +38,14 @@ pub trait LineRepository: Send + Sync + 'static {
         line_name: String,
         limit: Option<u32>,
     ) -> Result<Vec<Line>, DomainError>;
+    async fn get_by_prefecture_id(
+        &self,
+        prefecture_id: u32,
+        as_of: Option<&AsOf>,
+    ) -> Result<Vec<Line>, DomainError>;
+    async fn get_by_company_id(
+        &self,
+        company_id: u32,
+        as_of: Option<&AsOf>,
+    ) -> Result<Vec<Line>, DomainError>;
 }
@@ -0,0 +1,9 @@
+use async_trait::async_trait;
+
+use crate::domain::{entity::prefecture::Prefecture, error::DomainError};
+
+#[async_trait]
+pub trait PrefectureRepository: Send + Sync + 'static {
+    async fn find_by_id(&self, id: u32) -> Result<Option<Prefecture>, DomainError>;
+    async fn get_all(&self) -> Result<Vec<Prefecture>, DomainError>;
+}
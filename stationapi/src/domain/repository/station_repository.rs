@@ -1,7 +1,10 @@
 use async_trait::async_trait;
 
 use crate::domain::{
-    entity::{misc::StationIdWithDistance, station::Station},
+    entity::{
+        misc::{AsOf, BoundingBox, StationFilter, StationIdWithDistance},
+        station::Station,
+    },
     error::DomainError,
 };
 
@@ -14,6 +17,14 @@ pub trait StationRepository: Send + Sync + 'static {
         line_id: u32,
         station_id: Option<u32>,
     ) -> Result<Vec<Station>, DomainError>;
+    // get_by_line_id(line_id, None)をまとめて引く
+    async fn get_by_line_id_vec(&self, line_id_vec: &[u32]) -> Result<Vec<Station>, DomainError>;
+    // 路線図の履歴を返すため、列車種別は付けない
+    async fn get_by_line_id_as_of(
+        &self,
+        line_id: u32,
+        as_of: &AsOf,
+    ) -> Result<Vec<Station>, DomainError>;
     async fn get_by_station_group_id(
         &self,
         station_group_id: u32,
@@ -28,6 +39,20 @@ pub trait StationRepository: Send + Sync + 'static {
         longitude: f64,
         limit: Option<u32>,
     ) -> Result<Vec<Station>, DomainError>;
+    async fn get_by_radius(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        radius_in_km: f64,
+        filter: &StationFilter,
+        limit: Option<u32>,
+    ) -> Result<Vec<Station>, DomainError>;
+    async fn get_by_bounding_box(
+        &self,
+        bounding_box: &BoundingBox,
+        filter: &StationFilter,
+        limit: Option<u32>,
+    ) -> Result<Vec<Station>, DomainError>;
     async fn get_by_name(
         &self,
         station_name: String,
@@ -35,6 +60,20 @@ pub trait StationRepository: Send + Sync + 'static {
         from_station_group_id: Option<u32>,
     ) -> Result<Vec<Station>, DomainError>;
     async fn get_by_line_group_id(&self, line_group_id: u32) -> Result<Vec<Station>, DomainError>;
+    async fn get_by_line_group_id_vec(
+        &self,
+        line_group_id_vec: &[u32],
+    ) -> Result<Vec<Station>, DomainError>;
+    async fn get_by_prefecture_id(
+        &self,
+        prefecture_id: u32,
+        as_of: Option<&AsOf>,
+    ) -> Result<Vec<Station>, DomainError>;
+    async fn get_by_company_id(
+        &self,
+        company_id: u32,
+        as_of: Option<&AsOf>,
+    ) -> Result<Vec<Station>, DomainError>;
     async fn get_station_id_and_distance_by_coordinates(
         &self,
         latitude: f64,
@@ -46,4 +85,5 @@ pub trait StationRepository: Send + Sync + 'static {
         from_station_id: u32,
         to_station_id: u32,
     ) -> Result<Vec<Station>, DomainError>;
+    async fn get_all_stops(&self) -> Result<Vec<Station>, DomainError>;
 }
@@ -1,17 +1,32 @@
+use std::collections::BTreeMap;
+
 use async_trait::async_trait;
 
-use crate::domain::{entity::train_type::TrainType, error::DomainError};
+use crate::domain::{
+    entity::{misc::TrainTypeFilter, train_type::TrainType},
+    error::DomainError,
+};
 
 #[async_trait]
 pub trait TrainTypeRepository: Send + Sync + 'static {
     async fn get_by_line_group_id(&self, line_group_id: u32)
         -> Result<Vec<TrainType>, DomainError>;
     async fn get_by_station_id(&self, station_id: u32) -> Result<Vec<TrainType>, DomainError>;
+    // get_by_station_idをまとめて引く。get_by_station_id_vecと違い系統では絞らない
+    async fn get_all_by_station_id_vec(
+        &self,
+        station_id_vec: &[u32],
+    ) -> Result<Vec<TrainType>, DomainError>;
     async fn find_by_line_group_id_and_line_id(
         &self,
         line_group_id: u32,
         line_id: u32,
     ) -> Result<Option<TrainType>, DomainError>;
+    // find_by_line_group_id_and_line_idをまとめて引く。見つからなかった組はキーごと含めない
+    async fn get_by_line_group_id_and_line_id_vec(
+        &self,
+        line_group_id_and_line_id_vec: &[(u32, u32)],
+    ) -> Result<BTreeMap<(u32, u32), TrainType>, DomainError>;
     async fn get_by_station_id_vec(
         &self,
         station_id_vec: &[u32],
@@ -26,4 +41,7 @@ pub trait TrainTypeRepository: Send + Sync + 'static {
         &self,
         line_group_id_vec: &[u32],
     ) -> Result<Vec<TrainType>, DomainError>;
+    async fn find_by_type_cd(&self, type_cd: u32) -> Result<Option<TrainType>, DomainError>;
+    // 種別と系統(line_group_cd)の組ごとに1件返す
+    async fn get_by_filter(&self, filter: &TrainTypeFilter) -> Result<Vec<TrainType>, DomainError>;
 }
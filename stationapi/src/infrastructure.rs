@@ -1,5 +1,14 @@
+pub mod autocomplete_index;
+pub mod autocomplete_repository;
 pub mod company_repository;
+pub mod dataset_info_repository;
+pub mod dataset_loader;
 pub mod error;
+pub mod gtfs;
 pub mod line_repository;
+pub mod memory;
+pub mod prefecture_repository;
+pub mod spatial_index;
+pub mod station_name_index;
 pub mod station_repository;
 pub mod train_type_repository;
@@ -0,0 +1,221 @@
+use std::collections::HashSet;
+
+use crate::{
+    domain::entity::suggestion::{Suggestion, SuggestionType},
+    infrastructure::station_name_index::normalize,
+};
+
+// 列名と言語の対応。先頭の列を表示名に使う
+pub const STATION_NAME_FIELDS: [(&str, &str); 5] = [
+    ("stations.station_name", "ja"),
+    ("stations.station_name_k", "ja-Kana"),
+    ("stations.station_name_r", "ja-Latn"),
+    ("stations.station_name_zh", "zh"),
+    ("stations.station_name_ko", "ko"),
+];
+pub const LINE_NAME_FIELDS: [(&str, &str); 6] = [
+    ("lines.line_name", "ja"),
+    ("lines.line_name_k", "ja-Kana"),
+    ("lines.line_name_h", "ja"),
+    ("lines.line_name_r", "ja-Latn"),
+    ("lines.line_name_zh", "zh"),
+    ("lines.line_name_ko", "ko"),
+];
+pub const ALIAS_NAME_FIELDS: [(&str, &str); 6] = [
+    ("aliases.line_name", "ja"),
+    ("aliases.line_name_k", "ja-Kana"),
+    ("aliases.line_name_h", "ja"),
+    ("aliases.line_name_r", "ja-Latn"),
+    ("aliases.line_name_zh", "zh"),
+    ("aliases.line_name_ko", "ko"),
+];
+
+// 1つの駅・路線の各列から候補を作る。namesはfieldsと同じ並び
+pub fn suggestions_from_names(
+    suggestion_type: SuggestionType,
+    id: u32,
+    fields: &[(&str, &str)],
+    names: &[Option<&str>],
+) -> Vec<Suggestion> {
+    let Some(Some(name)) = names.first() else {
+        return vec![];
+    };
+
+    fields
+        .iter()
+        .zip(names)
+        .filter_map(|(&(field, language), matched_name)| {
+            let matched_name = matched_name.filter(|value| !value.is_empty())?;
+            Some(Suggestion::new(
+                suggestion_type,
+                id,
+                name.to_string(),
+                matched_name.to_string(),
+                field.to_string(),
+                language.to_string(),
+            ))
+        })
+        .collect()
+}
+
+pub struct AutocompleteIndex {
+    suggestions: Vec<Suggestion>,
+    // 正規化した名前の辞書順。前方一致する範囲を二分探索で求める
+    keys: Vec<(String, usize)>,
+}
+
+impl AutocompleteIndex {
+    pub fn new(suggestions: Vec<Suggestion>) -> Self {
+        let mut seen = HashSet::new();
+        let mut unique_suggestions = vec![];
+        let mut keys = vec![];
+
+        for suggestion in suggestions {
+            let key = normalize(&suggestion.matched_name);
+            // NOTE: 同じ駅グループの駅は路線ごとに同じ名前を持つので1つにまとめる
+            if key.is_empty()
+                || !seen.insert((suggestion.suggestion_type, suggestion.id, key.clone()))
+            {
+                continue;
+            }
+            keys.push((key, unique_suggestions.len()));
+            unique_suggestions.push(suggestion);
+        }
+        keys.sort();
+
+        Self {
+            suggestions: unique_suggestions,
+            keys,
+        }
+    }
+
+    pub fn search(&self, query: &str, limit: usize) -> Vec<Suggestion> {
+        let prefix = normalize(query);
+        if prefix.is_empty() {
+            return vec![];
+        }
+
+        let start = self
+            .keys
+            .partition_point(|(key, _)| key.as_str() < prefix.as_str());
+        let mut matches: Vec<(bool, usize, SuggestionType, u32, usize)> = self.keys[start..]
+            .iter()
+            .take_while(|(key, _)| key.starts_with(&prefix))
+            .map(|(key, index)| {
+                let suggestion = &self.suggestions[*index];
+                (
+                    key != &prefix,
+                    key.chars().count(),
+                    suggestion.suggestion_type,
+                    suggestion.id,
+                    *index,
+                )
+            })
+            .collect();
+        // 完全一致、短い名前の順
+        matches.sort();
+
+        let mut seen = HashSet::new();
+        matches
+            .into_iter()
+            .filter(|(_, _, suggestion_type, id, _)| seen.insert((*suggestion_type, *id)))
+            .take(limit)
+            .map(|(_, _, _, _, index)| self.suggestions[index].clone())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{suggestions_from_names, AutocompleteIndex, LINE_NAME_FIELDS, STATION_NAME_FIELDS};
+    use crate::domain::entity::suggestion::SuggestionType;
+
+    fn index() -> AutocompleteIndex {
+        let mut suggestions = vec![];
+        for (station_g_cd, name, name_k, name_r) in [
+            (1130101, "東京", "トウキョウ", "Tōkyō"),
+            (1130101, "東京", "トウキョウ", "Tōkyō"),
+            (
+                2800101,
+                "東京テレポート",
+                "トウキョウテレポート",
+                "Tokyo Teleport",
+            ),
+            (1130102, "有楽町", "ユウラクチョウ", "Yūrakuchō"),
+        ] {
+            suggestions.extend(suggestions_from_names(
+                SuggestionType::StationGroup,
+                station_g_cd,
+                &STATION_NAME_FIELDS,
+                &[Some(name), Some(name_k), Some(name_r), None, None],
+            ));
+        }
+        suggestions.extend(suggestions_from_names(
+            SuggestionType::Line,
+            28001,
+            &LINE_NAME_FIELDS,
+            &[
+                Some("東京臨海高速鉄道りんかい線"),
+                Some("トウキョウリンカイコウソクテツドウリンカイセン"),
+                Some(""),
+                Some("Tokyo Waterfront Area Rapid Transit Rinkai Line"),
+                None,
+                None,
+            ],
+        ));
+        AutocompleteIndex::new(suggestions)
+    }
+
+    #[test]
+    fn suggestions_from_names_skip_empty_columns() {
+        let suggestions = suggestions_from_names(
+            SuggestionType::Line,
+            11302,
+            &LINE_NAME_FIELDS,
+            &[
+                Some("JR山手線"),
+                Some("ヤマノテセン"),
+                Some(""),
+                None,
+                None,
+                None,
+            ],
+        );
+        assert_eq!(suggestions.len(), 2);
+        assert_eq!(suggestions[1].name, "JR山手線");
+        assert_eq!(suggestions[1].matched_field, "lines.line_name_k");
+        assert_eq!(suggestions[1].language, "ja-Kana");
+    }
+
+    #[test]
+    fn search() {
+        let index = index();
+
+        let suggestions = index.search("とうきょう", 10);
+        let ids: Vec<_> = suggestions
+            .iter()
+            .map(|s| (s.suggestion_type, s.id))
+            .collect();
+        // 完全一致、短い名前の順。同じ駅グループ・路線は1件にまとめる
+        assert_eq!(
+            ids,
+            vec![
+                (SuggestionType::StationGroup, 1130101),
+                (SuggestionType::StationGroup, 2800101),
+                (SuggestionType::Line, 28001),
+            ]
+        );
+        assert_eq!(suggestions[0].matched_field, "stations.station_name_k");
+        assert_eq!(suggestions[0].language, "ja-Kana");
+
+        let suggestions = index.search("Yurakuc", 10);
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].name, "有楽町");
+        assert_eq!(suggestions[0].matched_name, "Yūrakuchō");
+        assert_eq!(suggestions[0].language, "ja-Latn");
+
+        assert_eq!(index.search("東京", 1).len(), 1);
+        assert!(index.search("テレポート", 10).is_empty());
+        assert!(index.search("", 10).is_empty());
+    }
+}
@@ -0,0 +1,191 @@
+use async_trait::async_trait;
+use sqlx::{MySql, MySqlConnection, Pool};
+use std::sync::Arc;
+use tokio::sync::OnceCell;
+
+use crate::{
+    domain::{
+        entity::suggestion::{Suggestion, SuggestionType},
+        error::DomainError,
+        repository::autocomplete_repository::AutocompleteRepository,
+    },
+    infrastructure::autocomplete_index::{
+        suggestions_from_names, AutocompleteIndex, ALIAS_NAME_FIELDS, LINE_NAME_FIELDS,
+        STATION_NAME_FIELDS,
+    },
+};
+
+#[derive(sqlx::FromRow, Clone)]
+struct StationNameRow {
+    station_g_cd: u32,
+    line_cd: u32,
+    station_name: String,
+    station_name_k: String,
+    station_name_r: Option<String>,
+    station_name_zh: Option<String>,
+    station_name_ko: Option<String>,
+    alias_line_name: Option<String>,
+    alias_line_name_k: Option<String>,
+    alias_line_name_h: Option<String>,
+    alias_line_name_r: Option<String>,
+    alias_line_name_zh: Option<String>,
+    alias_line_name_ko: Option<String>,
+}
+
+impl StationNameRow {
+    fn into_suggestions(self) -> Vec<Suggestion> {
+        let mut suggestions = suggestions_from_names(
+            SuggestionType::StationGroup,
+            self.station_g_cd,
+            &STATION_NAME_FIELDS,
+            &[
+                Some(self.station_name.as_str()),
+                Some(self.station_name_k.as_str()),
+                self.station_name_r.as_deref(),
+                self.station_name_zh.as_deref(),
+                self.station_name_ko.as_deref(),
+            ],
+        );
+        suggestions.extend(suggestions_from_names(
+            SuggestionType::Line,
+            self.line_cd,
+            &ALIAS_NAME_FIELDS,
+            &[
+                self.alias_line_name.as_deref(),
+                self.alias_line_name_k.as_deref(),
+                self.alias_line_name_h.as_deref(),
+                self.alias_line_name_r.as_deref(),
+                self.alias_line_name_zh.as_deref(),
+                self.alias_line_name_ko.as_deref(),
+            ],
+        ));
+        suggestions
+    }
+}
+
+#[derive(sqlx::FromRow, Clone)]
+struct LineNameRow {
+    line_cd: u32,
+    line_name: String,
+    line_name_k: String,
+    line_name_h: String,
+    line_name_r: Option<String>,
+    line_name_zh: Option<String>,
+    line_name_ko: Option<String>,
+}
+
+impl LineNameRow {
+    fn into_suggestions(self) -> Vec<Suggestion> {
+        suggestions_from_names(
+            SuggestionType::Line,
+            self.line_cd,
+            &LINE_NAME_FIELDS,
+            &[
+                Some(self.line_name.as_str()),
+                Some(self.line_name_k.as_str()),
+                Some(self.line_name_h.as_str()),
+                self.line_name_r.as_deref(),
+                self.line_name_zh.as_deref(),
+                self.line_name_ko.as_deref(),
+            ],
+        )
+    }
+}
+
+pub struct MyAutocompleteRepository {
+    pool: Arc<Pool<MySql>>,
+    index: OnceCell<AutocompleteIndex>,
+}
+
+impl MyAutocompleteRepository {
+    pub fn new(pool: Arc<Pool<MySql>>) -> Self {
+        Self {
+            pool,
+            index: OnceCell::new(),
+        }
+    }
+
+    // 駅名・路線名はマイグレーション時にしか変わらないため、初回アクセス時に読み込んで使い回す
+    // NOTE: マイグレーションをかけ直したら、バージョンの変更を見て索引ごと作り直す
+    async fn index(&self) -> Result<&AutocompleteIndex, DomainError> {
+        self.index
+            .get_or_try_init(|| async {
+                let mut conn = self.pool.acquire().await?;
+                let suggestions =
+                    InternalAutocompleteRepository::get_all_suggestions(&mut conn).await?;
+                Ok(AutocompleteIndex::new(suggestions))
+            })
+            .await
+    }
+}
+
+#[async_trait]
+impl AutocompleteRepository for MyAutocompleteRepository {
+    async fn find_by_prefix(
+        &self,
+        prefix: &str,
+        limit: u32,
+    ) -> Result<Vec<Suggestion>, DomainError> {
+        Ok(self.index().await?.search(prefix, limit as usize))
+    }
+}
+
+pub struct InternalAutocompleteRepository {}
+
+impl InternalAutocompleteRepository {
+    async fn get_all_suggestions(
+        conn: &mut MySqlConnection,
+    ) -> Result<Vec<Suggestion>, DomainError> {
+        let station_rows = sqlx::query_as::<_, StationNameRow>(
+            "SELECT
+                s.station_g_cd,
+                s.line_cd,
+                s.station_name,
+                s.station_name_k,
+                s.station_name_r,
+                s.station_name_zh,
+                s.station_name_ko,
+                a.line_name AS alias_line_name,
+                a.line_name_k AS alias_line_name_k,
+                a.line_name_h AS alias_line_name_h,
+                a.line_name_r AS alias_line_name_r,
+                a.line_name_zh AS alias_line_name_zh,
+                a.line_name_ko AS alias_line_name_ko
+              FROM `stations` AS s
+              JOIN `lines` AS l ON l.line_cd = s.line_cd
+              AND l.e_status = 0
+              LEFT JOIN `line_aliases` AS la ON la.station_cd = s.station_cd
+              LEFT JOIN `aliases` AS a ON a.id = la.alias_cd
+              WHERE s.e_status = 0",
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        let line_rows = sqlx::query_as::<_, LineNameRow>(
+            "SELECT
+                l.line_cd,
+                l.line_name,
+                l.line_name_k,
+                l.line_name_h,
+                l.line_name_r,
+                l.line_name_zh,
+                l.line_name_ko
+              FROM `lines` AS l
+              WHERE l.e_status = 0",
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        let suggestions = station_rows
+            .into_iter()
+            .flat_map(StationNameRow::into_suggestions)
+            .chain(
+                line_rows
+                    .into_iter()
+                    .flat_map(LineNameRow::into_suggestions),
+            )
+            .collect();
+
+        Ok(suggestions)
+    }
+}
@@ -3,7 +3,9 @@ use sqlx::{MySql, MySqlConnection, Pool};
 use std::sync::Arc;
 
 use crate::domain::{
-    entity::company::Company, error::DomainError, repository::company_repository::CompanyRepository,
+    entity::{company::Company, misc::CompanyFilter},
+    error::DomainError,
+    repository::company_repository::CompanyRepository,
 };
 
 #[derive(sqlx::FromRow, Clone)]
@@ -53,15 +55,35 @@ impl MyCompanyRepository {
 
 #[async_trait]
 impl CompanyRepository for MyCompanyRepository {
+    async fn find_by_id(&self, id: u32) -> Result<Option<Company>, DomainError> {
+        let mut conn = self.pool.acquire().await?;
+        InternalCompanyRepository::find_by_id(id, &mut conn).await
+    }
     async fn find_by_id_vec(&self, id_vec: &[u32]) -> Result<Vec<Company>, DomainError> {
         let mut conn = self.pool.acquire().await?;
         InternalCompanyRepository::find_by_id_vec(id_vec, &mut conn).await
     }
+    async fn get_all(&self, filter: &CompanyFilter) -> Result<Vec<Company>, DomainError> {
+        let mut conn = self.pool.acquire().await?;
+        InternalCompanyRepository::get_all(filter, &mut conn).await
+    }
 }
 
 pub struct InternalCompanyRepository {}
 
 impl InternalCompanyRepository {
+    async fn find_by_id(
+        id: u32,
+        conn: &mut MySqlConnection,
+    ) -> Result<Option<Company>, DomainError> {
+        let row = sqlx::query_as::<_, CompanyRow>("SELECT * FROM `companies` WHERE company_cd = ?")
+            .bind(id)
+            .fetch_optional(conn)
+            .await?;
+
+        Ok(row.map(|row| row.into()))
+    }
+
     async fn find_by_id_vec(
         id_vec: &[u32],
         conn: &mut MySqlConnection,
@@ -86,4 +108,26 @@ impl InternalCompanyRepository {
 
         Ok(companies)
     }
+
+    async fn get_all(
+        filter: &CompanyFilter,
+        conn: &mut MySqlConnection,
+    ) -> Result<Vec<Company>, DomainError> {
+        let rows = sqlx::query_as::<_, CompanyRow>(
+            "SELECT * FROM `companies`
+            WHERE (? IS NULL OR company_type = ?)
+              AND (? IS NULL OR rr_cd = ?)
+              AND e_status = ?
+            ORDER BY e_sort, company_cd",
+        )
+        .bind(filter.company_type)
+        .bind(filter.company_type)
+        .bind(filter.rr_cd)
+        .bind(filter.rr_cd)
+        .bind(filter.e_status)
+        .fetch_all(conn)
+        .await?;
+
+        Ok(rows.into_iter().map(|row| row.into()).collect())
+    }
 }
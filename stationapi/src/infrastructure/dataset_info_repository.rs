@@ -0,0 +1,67 @@
+use async_trait::async_trait;
+use sqlx::{MySql, MySqlConnection, Pool};
+use std::sync::Arc;
+
+use crate::domain::{
+    entity::dataset_info::DatasetInfo, error::DomainError,
+    repository::dataset_info_repository::DatasetInfoRepository,
+};
+
+// NOTE: テーブルが存在しない
+const ER_NO_SUCH_TABLE: &str = "42S02";
+
+#[derive(sqlx::FromRow, Clone)]
+pub struct DatasetInfoRow {
+    pub version: String,
+    pub content_hash: String,
+    pub created_at: u64,
+}
+
+impl From<DatasetInfoRow> for DatasetInfo {
+    fn from(row: DatasetInfoRow) -> Self {
+        Self {
+            version: row.version,
+            content_hash: row.content_hash,
+            created_at: row.created_at,
+        }
+    }
+}
+
+pub struct MyDatasetInfoRepository {
+    pool: Arc<Pool<MySql>>,
+}
+
+impl MyDatasetInfoRepository {
+    pub fn new(pool: Arc<Pool<MySql>>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl DatasetInfoRepository for MyDatasetInfoRepository {
+    async fn find_current(&self) -> Result<Option<DatasetInfo>, DomainError> {
+        let mut conn = self.pool.acquire().await?;
+        InternalDatasetInfoRepository::find_current(&mut conn).await
+    }
+}
+
+pub struct InternalDatasetInfoRepository {}
+
+impl InternalDatasetInfoRepository {
+    async fn find_current(conn: &mut MySqlConnection) -> Result<Option<DatasetInfo>, DomainError> {
+        let row = sqlx::query_as::<_, DatasetInfoRow>(
+            "SELECT * FROM `dataset_info` ORDER BY created_at DESC LIMIT 1",
+        )
+        .fetch_optional(conn)
+        .await;
+
+        match row {
+            Ok(row) => Ok(row.map(|row| row.into())),
+            // NOTE: バージョンを記録する前のmigrationで作ったDBにはテーブルがない
+            Err(sqlx::Error::Database(err)) if err.code().as_deref() == Some(ER_NO_SUCH_TABLE) => {
+                Ok(None)
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+}
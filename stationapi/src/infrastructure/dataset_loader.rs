@@ -0,0 +1,128 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sqlx::{MySql, MySqlPool, Pool};
+
+use crate::{
+    domain::{
+        entity::{dataset_changelog::DatasetChangelog, dataset_summary::DatasetSummary},
+        error::DomainError,
+    },
+    use_case::{
+        error::UseCaseError, interactor::query::QueryInteractor, traits::reload::DatasetLoader,
+    },
+};
+
+use super::{
+    autocomplete_repository::MyAutocompleteRepository, company_repository::MyCompanyRepository,
+    dataset_info_repository::MyDatasetInfoRepository, line_repository::MyLineRepository,
+    prefecture_repository::MyPrefectureRepository, station_repository::MyStationRepository,
+    train_type_repository::MyTrainTypeRepository,
+};
+
+pub type MyQueryInteractor = QueryInteractor<
+    MyStationRepository,
+    MyLineRepository,
+    MyTrainTypeRepository,
+    MyCompanyRepository,
+    MyAutocompleteRepository,
+    MyPrefectureRepository,
+    MyDatasetInfoRepository,
+>;
+
+#[derive(sqlx::FromRow)]
+struct DatasetSummaryRow {
+    companies: i64,
+    lines: i64,
+    stations: i64,
+    train_types: i64,
+    orphan_lines: i64,
+    orphan_stations: i64,
+}
+
+// sourceはデータベースのURL。migrationで別のスキーマに入れてから切り替える
+#[derive(Default)]
+pub struct MyDatasetLoader;
+
+impl MyDatasetLoader {
+    pub fn build_query_interactor(pool: Arc<Pool<MySql>>) -> MyQueryInteractor {
+        QueryInteractor {
+            station_repository: MyStationRepository::new(Arc::clone(&pool)),
+            line_repository: MyLineRepository::new(Arc::clone(&pool)),
+            train_type_repository: MyTrainTypeRepository::new(Arc::clone(&pool)),
+            company_repository: MyCompanyRepository::new(Arc::clone(&pool)),
+            autocomplete_repository: MyAutocompleteRepository::new(Arc::clone(&pool)),
+            prefecture_repository: MyPrefectureRepository::new(Arc::clone(&pool)),
+            dataset_info_repository: MyDatasetInfoRepository::new(Arc::clone(&pool)),
+            station_graph: Default::default(),
+        }
+    }
+
+    async fn validate(pool: &Pool<MySql>) -> Result<DatasetSummary, DomainError> {
+        let row = sqlx::query_as::<_, DatasetSummaryRow>(
+            "SELECT
+                (SELECT COUNT(*) FROM `companies`) AS companies,
+                (SELECT COUNT(*) FROM `lines`) AS lines,
+                (SELECT COUNT(*) FROM `stations`) AS stations,
+                (SELECT COUNT(*) FROM `types`) AS train_types,
+                (SELECT COUNT(*) FROM `lines` AS l
+                    LEFT JOIN `companies` AS c ON c.company_cd = l.company_cd
+                    WHERE c.company_cd IS NULL) AS orphan_lines,
+                (SELECT COUNT(*) FROM `stations` AS s
+                    LEFT JOIN `lines` AS l ON l.line_cd = s.line_cd
+                    WHERE l.line_cd IS NULL) AS orphan_stations",
+        )
+        .fetch_one(pool)
+        .await?;
+
+        if row.stations == 0 {
+            return Err(DomainError::Unexpected(
+                "The dataset has no stations".to_string(),
+            ));
+        }
+        if row.orphan_lines > 0 || row.orphan_stations > 0 {
+            return Err(DomainError::Unexpected(format!(
+                "{} lines and {} stations refer to unknown companies or lines",
+                row.orphan_lines, row.orphan_stations
+            )));
+        }
+
+        Ok(DatasetSummary {
+            companies: row.companies as u64,
+            lines: row.lines as u64,
+            stations: row.stations as u64,
+            train_types: row.train_types as u64,
+        })
+    }
+}
+
+#[async_trait]
+impl DatasetLoader for MyDatasetLoader {
+    type QueryUseCase = MyQueryInteractor;
+
+    async fn load(
+        &self,
+        source: &str,
+    ) -> Result<(Self::QueryUseCase, DatasetSummary), UseCaseError> {
+        let pool = MySqlPool::connect(source)
+            .await
+            .map_err(DomainError::from)?;
+        let summary = Self::validate(&pool).await.map_err(|err| match err {
+            DomainError::Unexpected(message) => UseCaseError::InvalidArgument(message),
+            err => err.into(),
+        })?;
+
+        Ok((Self::build_query_interactor(Arc::new(pool)), summary))
+    }
+
+    // NOTE: DBはCSVから作るので、差分はdataset_diffでCSVどうしを比べる
+    async fn diff(
+        &self,
+        _base_source: &str,
+        _target_source: &str,
+    ) -> Result<DatasetChangelog, UseCaseError> {
+        Err(UseCaseError::InvalidArgument(
+            "Dataset diffs are only available with the in-memory dataset. Compare the CSV directories with dataset_diff instead.".to_string(),
+        ))
+    }
+}
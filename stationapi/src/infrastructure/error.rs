@@ -6,6 +6,18 @@ impl From<sqlx::Error> for DomainError {
     }
 }
 
+impl From<csv::Error> for DomainError {
+    fn from(error: csv::Error) -> Self {
+        DomainError::InfrastructureError(anyhow::Error::new(error))
+    }
+}
+
+impl From<std::io::Error> for DomainError {
+    fn from(error: std::io::Error) -> Self {
+        DomainError::InfrastructureError(anyhow::Error::new(error))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -17,4 +29,14 @@ mod tests {
 
         assert!(matches!(domain_error, DomainError::InfrastructureError(_)));
     }
+
+    #[test]
+    fn from_csv_error() {
+        use super::DomainError;
+
+        let error = csv::Error::from(std::io::Error::from(std::io::ErrorKind::NotFound));
+        let domain_error = DomainError::from(error);
+
+        assert!(matches!(domain_error, DomainError::InfrastructureError(_)));
+    }
 }
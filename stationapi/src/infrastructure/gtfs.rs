@@ -0,0 +1,506 @@
+use csv::Writer;
+use serde::Serialize;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::Path,
+};
+
+use crate::{
+    domain::{entity::misc::distance_in_km, error::DomainError},
+    infrastructure::memory::dataset::{Dataset, StationRecord, StationStationTypeRecord},
+};
+
+const AGENCY_TIMEZONE: &str = "Asia/Tokyo";
+const AGENCY_LANG: &str = "ja";
+// NOTE: 時刻表を持っていないので、毎日運行する1つのサービスとして出力する
+const SERVICE_ID: &str = "ALL";
+const SERVICE_START_DATE: &str = "20000101";
+const SERVICE_END_DATE: &str = "20991231";
+
+// GTFSのroute_type
+const ROUTE_TYPE_TRAM: u32 = 0;
+const ROUTE_TYPE_SUBWAY: u32 = 1;
+const ROUTE_TYPE_RAIL: u32 = 2;
+const ROUTE_TYPE_MONORAIL: u32 = 12;
+
+const LOCATION_TYPE_STOP: u32 = 0;
+const LOCATION_TYPE_STATION: u32 = 1;
+
+const PICKUP_TYPE_REGULAR: u32 = 0;
+const PICKUP_TYPE_NONE: u32 = 1;
+
+// NOTE: 時刻表がないので、駅間の直線距離をこの速さで割った目安を始発からの相対時刻にする
+const NOMINAL_SPEED_IN_KMH: f64 = 60.0;
+const MIN_SECONDS_BETWEEN_STOPS: u32 = 60;
+// 相対時刻だけでは運行時刻が決まらないので、frequencies.txtで終日この間隔で走るものとする
+const FREQUENCY_START_TIME: &str = "00:00:00";
+const FREQUENCY_END_TIME: &str = "24:00:00";
+const FREQUENCY_HEADWAY_SECS: u32 = 3600;
+// frequencies.txtのexact_times。時刻は目安なので0(時刻を決めない運行)とする
+const FREQUENCY_BASED_SERVICE: u32 = 0;
+// stop_times.txtのtimepoint。0は目安の時刻
+const TIMEPOINT_APPROXIMATE: u32 = 0;
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct GtfsAgency {
+    pub agency_id: u32,
+    pub agency_name: String,
+    pub agency_url: String,
+    pub agency_timezone: &'static str,
+    pub agency_lang: &'static str,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct GtfsRoute {
+    pub route_id: u32,
+    pub agency_id: u32,
+    pub route_short_name: String,
+    pub route_long_name: String,
+    pub route_type: u32,
+    pub route_color: String,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct GtfsStop {
+    pub stop_id: String,
+    pub stop_code: String,
+    pub stop_name: String,
+    pub stop_lat: f64,
+    pub stop_lon: f64,
+    pub location_type: u32,
+    pub parent_station: String,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct GtfsCalendar {
+    pub service_id: &'static str,
+    pub monday: u32,
+    pub tuesday: u32,
+    pub wednesday: u32,
+    pub thursday: u32,
+    pub friday: u32,
+    pub saturday: u32,
+    pub sunday: u32,
+    pub start_date: &'static str,
+    pub end_date: &'static str,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct GtfsTrip {
+    pub route_id: u32,
+    pub service_id: &'static str,
+    pub trip_id: String,
+    pub trip_headsign: String,
+    pub trip_short_name: String,
+    pub block_id: String,
+}
+
+// NOTE: 到着・発車時刻は距離から見積もった始発からの相対時刻で、停車時間は取らない
+#[derive(Serialize, Debug, PartialEq)]
+pub struct GtfsStopTime {
+    pub trip_id: String,
+    pub arrival_time: String,
+    pub departure_time: String,
+    pub stop_id: String,
+    pub stop_sequence: u32,
+    pub pickup_type: u32,
+    pub drop_off_type: u32,
+    pub timepoint: u32,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct GtfsFrequency {
+    pub trip_id: String,
+    pub start_time: &'static str,
+    pub end_time: &'static str,
+    pub headway_secs: u32,
+    pub exact_times: u32,
+}
+
+// 運用中の駅・路線と、station_station_typesの停車パターンから作るGTFS風のフィード
+pub struct GtfsFeed {
+    pub agencies: Vec<GtfsAgency>,
+    pub routes: Vec<GtfsRoute>,
+    pub stops: Vec<GtfsStop>,
+    pub calendars: Vec<GtfsCalendar>,
+    pub trips: Vec<GtfsTrip>,
+    pub stop_times: Vec<GtfsStopTime>,
+    pub frequencies: Vec<GtfsFrequency>,
+}
+
+impl GtfsFeed {
+    pub fn from_dataset(dataset: &Dataset) -> Self {
+        let lines: Vec<_> = dataset.lines().filter(|line| line.e_status == 0).collect();
+        let company_cds: BTreeSet<u32> = lines.iter().map(|line| line.company_cd).collect();
+
+        let agencies = company_cds
+            .iter()
+            .filter_map(|company_cd| dataset.company(*company_cd))
+            .map(|company| GtfsAgency {
+                agency_id: company.company_cd,
+                agency_name: company.company_name.clone(),
+                agency_url: company.company_url.clone().unwrap_or_default(),
+                agency_timezone: AGENCY_TIMEZONE,
+                agency_lang: AGENCY_LANG,
+            })
+            .collect();
+
+        let routes = lines
+            .iter()
+            .map(|line| GtfsRoute {
+                route_id: line.line_cd,
+                agency_id: line.company_cd,
+                route_short_name: line.line_symbol_primary.clone().unwrap_or_default(),
+                route_long_name: line.line_name.clone(),
+                route_type: route_type(line.line_type),
+                route_color: line
+                    .line_color_c
+                    .as_deref()
+                    .unwrap_or_default()
+                    .trim_start_matches('#')
+                    .to_string(),
+            })
+            .collect();
+
+        let stations: Vec<&StationRecord> = dataset
+            .stations()
+            .filter(|station| dataset.is_active_station(station))
+            .collect();
+
+        // 駅グループの親駅は駅コードが最も小さい駅の名前と座標を使う
+        let mut parent_stations: BTreeMap<u32, &StationRecord> = BTreeMap::new();
+        for station in stations.iter() {
+            parent_stations
+                .entry(station.station_g_cd)
+                .or_insert(station);
+        }
+        let mut stops: Vec<GtfsStop> = parent_stations
+            .values()
+            .map(|station| GtfsStop {
+                stop_id: parent_stop_id(station.station_g_cd),
+                stop_code: String::new(),
+                stop_name: station.station_name.clone(),
+                stop_lat: station.lat,
+                stop_lon: station.lon,
+                location_type: LOCATION_TYPE_STATION,
+                parent_station: String::new(),
+            })
+            .collect();
+        stops.extend(stations.iter().map(|station| GtfsStop {
+            stop_id: station.station_cd.to_string(),
+            stop_code: station.primary_station_number.clone().unwrap_or_default(),
+            stop_name: station.station_name.clone(),
+            stop_lat: station.lat,
+            stop_lon: station.lon,
+            location_type: LOCATION_TYPE_STOP,
+            parent_station: parent_stop_id(station.station_g_cd),
+        }));
+
+        let calendars = vec![GtfsCalendar {
+            service_id: SERVICE_ID,
+            monday: 1,
+            tuesday: 1,
+            wednesday: 1,
+            thursday: 1,
+            friday: 1,
+            saturday: 1,
+            sunday: 1,
+            start_date: SERVICE_START_DATE,
+            end_date: SERVICE_END_DATE,
+        }];
+
+        let mut patterns: BTreeMap<u32, Vec<(&StationStationTypeRecord, &StationRecord)>> =
+            BTreeMap::new();
+        for sst in dataset.station_station_types() {
+            let Some(station) = dataset.station(sst.station_cd) else {
+                continue;
+            };
+            if !dataset.is_active_station(station) {
+                continue;
+            }
+            patterns
+                .entry(sst.line_group_cd)
+                .or_default()
+                .push((sst, station));
+        }
+
+        let mut trips = vec![];
+        let mut stop_times = vec![];
+        let mut frequencies = vec![];
+        for (line_group_cd, pattern) in patterns.iter() {
+            let Some((last_sst, last_station)) = pattern.last() else {
+                continue;
+            };
+            let trip_short_name = dataset
+                .train_type(last_sst.type_cd)
+                .map(|train_type| train_type.type_name.clone())
+                .unwrap_or_default();
+            let segments = split_by_line(pattern);
+            // NOTE: 直通運転は路線ごとのtripに分けて、同じblock_idでつなぐ
+            let block_id = if segments.len() > 1 {
+                line_group_cd.to_string()
+            } else {
+                String::new()
+            };
+
+            for (index, segment) in segments.iter().enumerate() {
+                // NOTE: 駅が1つしかない区間は次の区間の先頭に含まれるので、tripにしなくても駅は欠けない
+                let Some((_, segment_station)) = segment.last().filter(|_| segment.len() >= 2)
+                else {
+                    continue;
+                };
+                let trip_id = format!("{}_{}", line_group_cd, index + 1);
+                trips.push(GtfsTrip {
+                    route_id: segment_station.line_cd,
+                    service_id: SERVICE_ID,
+                    trip_id: trip_id.clone(),
+                    trip_headsign: last_station.station_name.clone(),
+                    trip_short_name: trip_short_name.clone(),
+                    block_id: block_id.clone(),
+                });
+                frequencies.push(GtfsFrequency {
+                    trip_id: trip_id.clone(),
+                    start_time: FREQUENCY_START_TIME,
+                    end_time: FREQUENCY_END_TIME,
+                    headway_secs: FREQUENCY_HEADWAY_SECS,
+                    exact_times: FREQUENCY_BASED_SERVICE,
+                });
+                let elapsed_secs = elapsed_secs(segment);
+                stop_times.extend(segment.iter().zip(elapsed_secs).enumerate().map(
+                    |(sequence, ((sst, station), elapsed_secs))| {
+                        // NOTE: 一部停車・平日のみ・土休日のみの駅も停車駅として出力する
+                        let pickup_type = if sst.pass == 1 {
+                            PICKUP_TYPE_NONE
+                        } else {
+                            PICKUP_TYPE_REGULAR
+                        };
+                        GtfsStopTime {
+                            trip_id: trip_id.clone(),
+                            arrival_time: format_gtfs_time(elapsed_secs),
+                            departure_time: format_gtfs_time(elapsed_secs),
+                            stop_id: station.station_cd.to_string(),
+                            stop_sequence: sequence as u32 + 1,
+                            pickup_type,
+                            drop_off_type: pickup_type,
+                            timepoint: TIMEPOINT_APPROXIMATE,
+                        }
+                    },
+                ));
+            }
+        }
+
+        Self {
+            agencies,
+            routes,
+            stops,
+            calendars,
+            trips,
+            stop_times,
+            frequencies,
+        }
+    }
+
+    pub fn write(&self, output_path: &Path) -> Result<(), DomainError> {
+        fs::create_dir_all(output_path)?;
+        write_records(&output_path.join("agency.txt"), &self.agencies)?;
+        write_records(&output_path.join("routes.txt"), &self.routes)?;
+        write_records(&output_path.join("stops.txt"), &self.stops)?;
+        write_records(&output_path.join("calendar.txt"), &self.calendars)?;
+        write_records(&output_path.join("trips.txt"), &self.trips)?;
+        write_records(&output_path.join("stop_times.txt"), &self.stop_times)?;
+        write_records(&output_path.join("frequencies.txt"), &self.frequencies)?;
+        Ok(())
+    }
+}
+
+fn route_type(line_type: Option<u32>) -> u32 {
+    match line_type {
+        Some(3) => ROUTE_TYPE_SUBWAY,
+        Some(4) => ROUTE_TYPE_TRAM,
+        // NOTE: AGTもモノレールとして扱う
+        Some(5) => ROUTE_TYPE_MONORAIL,
+        _ => ROUTE_TYPE_RAIL,
+    }
+}
+
+fn parent_stop_id(station_g_cd: u32) -> String {
+    format!("G{}", station_g_cd)
+}
+
+// NOTE: 2つ目以降の区間は前の区間の最後の駅から始め、乗り継ぐ駅を両方のtripに含める
+fn split_by_line<'a>(
+    pattern: &[(&'a StationStationTypeRecord, &'a StationRecord)],
+) -> Vec<Vec<(&'a StationStationTypeRecord, &'a StationRecord)>> {
+    let mut segments: Vec<Vec<(&StationStationTypeRecord, &StationRecord)>> = vec![];
+    for &(sst, station) in pattern {
+        match segments.last_mut() {
+            Some(segment) if segment[segment.len() - 1].1.line_cd == station.line_cd => {
+                segment.push((sst, station))
+            }
+            Some(segment) => {
+                let boundary = segment[segment.len() - 1];
+                segments.push(vec![boundary, (sst, station)]);
+            }
+            None => segments.push(vec![(sst, station)]),
+        }
+    }
+    segments
+}
+
+// 始発からの経過秒数
+fn elapsed_secs(segment: &[(&StationStationTypeRecord, &StationRecord)]) -> Vec<u32> {
+    let mut elapsed = 0;
+    let mut elapsed_secs = vec![elapsed];
+    for pair in segment.windows(2) {
+        let (from, to) = (pair[0].1, pair[1].1);
+        let distance = distance_in_km(from.lat, from.lon, to.lat, to.lon);
+        let secs = (distance / NOMINAL_SPEED_IN_KMH * 3600.0).round() as u32;
+        elapsed += secs.max(MIN_SECONDS_BETWEEN_STOPS);
+        elapsed_secs.push(elapsed);
+    }
+    elapsed_secs
+}
+
+fn format_gtfs_time(secs: u32) -> String {
+    format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+fn write_records<T: Serialize>(path: &Path, records: &[T]) -> Result<(), DomainError> {
+    let mut wtr = Writer::from_path(path)?;
+    for record in records {
+        wtr.serialize(record)?;
+    }
+    wtr.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashSet, path::Path};
+
+    use super::{format_gtfs_time, GtfsFeed, LOCATION_TYPE_STATION, ROUTE_TYPE_RAIL};
+    use crate::infrastructure::memory::dataset::Dataset;
+
+    #[test]
+    fn format_time() {
+        assert_eq!(format_gtfs_time(0), "00:00:00");
+        assert_eq!(format_gtfs_time(3723), "01:02:03");
+        // NOTE: GTFSでは24時を超える時刻も書ける
+        assert_eq!(format_gtfs_time(90000), "25:00:00");
+    }
+
+    #[test]
+    fn from_dataset() {
+        let dataset = Dataset::load(&Path::new(env!("CARGO_MANIFEST_DIR")).join("../data"))
+            .expect("Failed to load the dataset");
+        let feed = GtfsFeed::from_dataset(&dataset);
+
+        let route = feed.routes.iter().find(|r| r.route_id == 1002).unwrap();
+        assert_eq!(route.route_long_name, "東海道新幹線");
+        assert_eq!(route.route_type, ROUTE_TYPE_RAIL);
+        assert_eq!(route.route_color, "0000FF");
+        assert!(feed.agencies.iter().any(|a| a.agency_id == route.agency_id));
+        // 計画中の中央新幹線は含めない
+        assert!(feed.routes.iter().all(|r| r.route_id != 1001));
+
+        let stop = feed.stops.iter().find(|s| s.stop_id == "100201").unwrap();
+        let parent = feed
+            .stops
+            .iter()
+            .find(|s| s.stop_id == stop.parent_station)
+            .unwrap();
+        assert_eq!(parent.location_type, LOCATION_TYPE_STATION);
+
+        let trip = feed.trips.iter().find(|t| t.trip_id == "1_1").unwrap();
+        assert_eq!(trip.route_id, 1002);
+        assert_eq!(trip.trip_short_name, "のぞみ");
+        let first_stop = feed
+            .stop_times
+            .iter()
+            .find(|st| st.trip_id == trip.trip_id)
+            .unwrap();
+        assert_eq!(first_stop.stop_id, "100201");
+        assert_eq!(first_stop.stop_sequence, 1);
+        assert_eq!(first_stop.arrival_time, "00:00:00");
+
+        // 時刻は相対時刻なので、すべてのtripを運行間隔で走らせる
+        let trip_ids: HashSet<&str> = feed.trips.iter().map(|t| t.trip_id.as_str()).collect();
+        let frequency_trip_ids: HashSet<&str> = feed
+            .frequencies
+            .iter()
+            .map(|f| f.trip_id.as_str())
+            .collect();
+        assert_eq!(trip_ids, frequency_trip_ids);
+        for trip_id in trip_ids {
+            let times: Vec<&str> = feed
+                .stop_times
+                .iter()
+                .filter(|st| st.trip_id == trip_id)
+                .map(|st| st.arrival_time.as_str())
+                .collect();
+            assert!(times.len() >= 2);
+            assert!(times.iter().all(|time| time.len() >= 8));
+            assert!(times.windows(2).all(|pair| pair[0] < pair[1]));
+        }
+
+        // 停車時刻はすべて既存の駅を指す
+        let stop_ids: HashSet<&str> = feed.stops.iter().map(|s| s.stop_id.as_str()).collect();
+        assert!(feed
+            .stop_times
+            .iter()
+            .all(|st| stop_ids.contains(st.stop_id.as_str())));
+    }
+
+    #[test]
+    fn through_service_is_split_into_blocks() {
+        let dataset = Dataset::load(&Path::new(env!("CARGO_MANIFEST_DIR")).join("../data"))
+            .expect("Failed to load the dataset");
+        let feed = GtfsFeed::from_dataset(&dataset);
+
+        let block_trips: Vec<_> = feed
+            .trips
+            .iter()
+            .filter(|t| !t.block_id.is_empty())
+            .collect();
+        assert!(!block_trips.is_empty());
+        for trip in block_trips {
+            assert!(trip.trip_id.starts_with(&format!("{}_", trip.block_id)));
+        }
+
+        // 直通先で1駅しか通らない区間があっても、停車パターンの駅はどれかのtripに入る
+        for (line_group_cd, station_cds) in active_patterns(&dataset) {
+            if station_cds.len() < 2 {
+                continue;
+            }
+            let prefix = format!("{}_", line_group_cd);
+            let exported: HashSet<u32> = feed
+                .stop_times
+                .iter()
+                .filter(|st| st.trip_id.starts_with(&prefix))
+                .map(|st| st.stop_id.parse().unwrap())
+                .collect();
+            assert!(
+                station_cds.iter().all(|cd| exported.contains(cd)),
+                "line group {}",
+                line_group_cd
+            );
+        }
+    }
+
+    fn active_patterns(dataset: &Dataset) -> std::collections::BTreeMap<u32, Vec<u32>> {
+        let mut patterns: std::collections::BTreeMap<u32, Vec<u32>> = Default::default();
+        for sst in dataset.station_station_types() {
+            if dataset
+                .station(sst.station_cd)
+                .is_some_and(|station| dataset.is_active_station(station))
+            {
+                patterns
+                    .entry(sst.line_group_cd)
+                    .or_default()
+                    .push(sst.station_cd);
+            }
+        }
+        patterns
+    }
+}
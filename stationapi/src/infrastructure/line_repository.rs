@@ -1,9 +1,14 @@
 use async_trait::async_trait;
 use sqlx::{MySql, MySqlConnection, Pool};
-use std::sync::Arc;
-
-use crate::domain::{
-    entity::line::Line, error::DomainError, repository::line_repository::LineRepository,
+use std::{collections::BTreeSet, sync::Arc};
+
+use crate::{
+    domain::{
+        entity::{line::Line, misc::AsOf},
+        error::DomainError,
+        repository::line_repository::LineRepository,
+    },
+    infrastructure::station_repository::operating_station_condition,
 };
 
 #[derive(sqlx::FromRow, Clone)]
@@ -87,10 +92,21 @@ impl LineRepository for MyLineRepository {
         let mut conn = self.pool.acquire().await?;
         InternalLineRepository::find_by_id(id, &mut conn).await
     }
+    async fn find_by_id_as_of(&self, id: u32, as_of: &AsOf) -> Result<Option<Line>, DomainError> {
+        let mut conn = self.pool.acquire().await?;
+        InternalLineRepository::find_by_id_as_of(id, as_of, &mut conn).await
+    }
     async fn find_by_station_id(&self, station_id: u32) -> Result<Option<Line>, DomainError> {
         let mut conn = self.pool.acquire().await?;
         InternalLineRepository::find_by_station_id(station_id, &mut conn).await
     }
+    async fn get_by_station_id_vec(
+        &self,
+        station_id_vec: &[u32],
+    ) -> Result<Vec<Line>, DomainError> {
+        let mut conn = self.pool.acquire().await?;
+        InternalLineRepository::get_by_station_id_vec(station_id_vec, &mut conn).await
+    }
     async fn get_by_ids(&self, ids: &[u32]) -> Result<Vec<Line>, DomainError> {
         let mut conn = self.pool.acquire().await?;
         InternalLineRepository::get_by_ids(ids, &mut conn).await
@@ -133,6 +149,22 @@ impl LineRepository for MyLineRepository {
         let mut conn = self.pool.acquire().await?;
         InternalLineRepository::get_by_name(line_name, limit, &mut conn).await
     }
+    async fn get_by_prefecture_id(
+        &self,
+        prefecture_id: u32,
+        as_of: Option<&AsOf>,
+    ) -> Result<Vec<Line>, DomainError> {
+        let mut conn = self.pool.acquire().await?;
+        InternalLineRepository::get_by_prefecture_id(prefecture_id, as_of, &mut conn).await
+    }
+    async fn get_by_company_id(
+        &self,
+        company_id: u32,
+        as_of: Option<&AsOf>,
+    ) -> Result<Vec<Line>, DomainError> {
+        let mut conn = self.pool.acquire().await?;
+        InternalLineRepository::get_by_company_id(company_id, as_of, &mut conn).await
+    }
 }
 
 pub struct InternalLineRepository {}
@@ -211,6 +243,68 @@ impl InternalLineRepository {
         Ok(Some(line))
     }
 
+    async fn get_by_station_id_vec(
+        station_id_vec: &[u32],
+        conn: &mut MySqlConnection,
+    ) -> Result<Vec<Line>, DomainError> {
+        if station_id_vec.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let params = format!("?{}", ", ?".repeat(station_id_vec.len() - 1));
+        let query_str = format!(
+            "SELECT l.line_cd,
+            l.company_cd,
+            l.line_type,
+            l.line_symbol_primary,
+            l.line_symbol_secondary,
+            l.line_symbol_extra,
+            l.line_symbol_primary_color,
+            l.line_symbol_secondary_color,
+            l.line_symbol_extra_color,
+            l.line_symbol_primary_shape,
+            l.line_symbol_secondary_shape,
+            l.line_symbol_extra_shape,
+            l.e_status,
+            l.e_sort,
+            l.average_distance,
+            s.station_cd,
+            s.station_g_cd,
+            sst.line_group_cd,
+            COALESCE(a.line_name, l.line_name) AS line_name,
+            COALESCE(a.line_name_k, l.line_name_k) AS line_name_k,
+            COALESCE(a.line_name_h, l.line_name_h) AS line_name_h,
+            COALESCE(a.line_name_r, l.line_name_r) AS line_name_r,
+            COALESCE(a.line_name_zh, l.line_name_zh) AS line_name_zh,
+            COALESCE(a.line_name_ko, l.line_name_ko) AS line_name_ko,
+            COALESCE(a.line_color_c, l.line_color_c) AS line_color_c
+        FROM `lines` AS l
+            JOIN `stations` AS s ON s.station_cd IN ( {} )
+            JOIN `station_station_types` AS sst ON sst.station_cd = s.station_cd AND sst.pass <> 1
+            LEFT JOIN `line_aliases` AS la ON la.station_cd = s.station_cd
+            LEFT JOIN `aliases` AS a ON la.alias_cd = a.id
+        WHERE l.line_cd = s.line_cd
+        ORDER BY sst.id",
+            params
+        );
+
+        let mut query = sqlx::query_as::<_, LineRow>(&query_str);
+        for id in station_id_vec {
+            query = query.bind(id);
+        }
+        let rows = query.fetch_all(conn).await?;
+
+        // NOTE: find_by_station_idと同じく駅ごとに最初の行を採用する
+        let mut station_ids = BTreeSet::new();
+        let lines: Vec<Line> = rows
+            .into_iter()
+            .map(|row| -> Line { row.into() })
+            .filter(|line| line.station_cd.is_some_and(|id| station_ids.insert(id)))
+            .collect();
+
+        Ok(lines)
+    }
+
     async fn get_by_ids(ids: &[u32], conn: &mut MySqlConnection) -> Result<Vec<Line>, DomainError> {
         if ids.is_empty() {
             return Ok(vec![]);
@@ -498,4 +592,109 @@ impl InternalLineRepository {
 
         Ok(lines)
     }
+
+    async fn find_by_id_as_of(
+        id: u32,
+        as_of: &AsOf,
+        conn: &mut MySqlConnection,
+    ) -> Result<Option<Line>, DomainError> {
+        let query_str = format!(
+            "SELECT l.*,
+            CAST(NULL AS UNSIGNED INT) AS line_group_cd,
+            CAST(NULL AS UNSIGNED INT) AS station_cd,
+            CAST(NULL AS UNSIGNED INT) AS station_g_cd
+            FROM `lines` AS l
+            WHERE l.line_cd = ?
+                AND EXISTS (
+                    SELECT 1 FROM `stations` AS s
+                    WHERE s.line_cd = l.line_cd
+                    AND {}
+                )",
+            operating_station_condition("s", Some(as_of))
+        );
+        let row = sqlx::query_as::<_, LineRow>(&query_str)
+            .bind(id)
+            .bind(as_of.as_str())
+            .bind(as_of.as_str())
+            .fetch_optional(conn)
+            .await?;
+
+        Ok(row.map(|row| row.into()))
+    }
+
+    async fn get_by_prefecture_id(
+        prefecture_id: u32,
+        as_of: Option<&AsOf>,
+        conn: &mut MySqlConnection,
+    ) -> Result<Vec<Line>, DomainError> {
+        // NOTE: 日付を指定したときは、その日に営業していた駅があれば廃止された路線も返す
+        let line_condition = match as_of {
+            Some(_) => "",
+            None => "AND l.e_status = 0",
+        };
+        let query_str = format!(
+            "SELECT l.*,
+            CAST(NULL AS UNSIGNED INT) AS line_group_cd,
+            CAST(NULL AS UNSIGNED INT) AS station_cd,
+            CAST(NULL AS UNSIGNED INT) AS station_g_cd
+            FROM `lines` AS l
+            WHERE EXISTS (
+                    SELECT 1 FROM `stations` AS s
+                    WHERE s.line_cd = l.line_cd
+                    AND s.pref_cd = ?
+                    AND {}
+                )
+                {}
+            ORDER BY l.e_sort, l.line_cd",
+            operating_station_condition("s", as_of),
+            line_condition
+        );
+        let mut query = sqlx::query_as::<_, LineRow>(&query_str).bind(prefecture_id);
+        if let Some(as_of) = as_of {
+            query = query.bind(as_of.as_str()).bind(as_of.as_str());
+        }
+        let rows = query.fetch_all(conn).await?;
+
+        let lines: Vec<Line> = rows.into_iter().map(|row| row.into()).collect();
+
+        Ok(lines)
+    }
+
+    async fn get_by_company_id(
+        company_id: u32,
+        as_of: Option<&AsOf>,
+        conn: &mut MySqlConnection,
+    ) -> Result<Vec<Line>, DomainError> {
+        let condition = match as_of {
+            Some(as_of) => format!(
+                "EXISTS (
+                    SELECT 1 FROM `stations` AS s
+                    WHERE s.line_cd = l.line_cd
+                    AND {}
+                )",
+                operating_station_condition("s", Some(as_of))
+            ),
+            None => "l.e_status = 0".to_string(),
+        };
+        let query_str = format!(
+            "SELECT l.*,
+            CAST(NULL AS UNSIGNED INT) AS line_group_cd,
+            CAST(NULL AS UNSIGNED INT) AS station_cd,
+            CAST(NULL AS UNSIGNED INT) AS station_g_cd
+            FROM `lines` AS l
+            WHERE l.company_cd = ?
+                AND {}
+            ORDER BY l.e_sort, l.line_cd",
+            condition
+        );
+        let mut query = sqlx::query_as::<_, LineRow>(&query_str).bind(company_id);
+        if let Some(as_of) = as_of {
+            query = query.bind(as_of.as_str()).bind(as_of.as_str());
+        }
+        let rows = query.fetch_all(conn).await?;
+
+        let lines: Vec<Line> = rows.into_iter().map(|row| row.into()).collect();
+
+        Ok(lines)
+    }
 }
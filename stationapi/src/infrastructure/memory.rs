@@ -0,0 +1,10 @@
+pub mod autocomplete_repository;
+pub mod company_repository;
+pub mod dataset;
+pub mod dataset_diff;
+pub mod dataset_info_repository;
+pub mod dataset_loader;
+pub mod line_repository;
+pub mod prefecture_repository;
+pub mod station_repository;
+pub mod train_type_repository;
@@ -0,0 +1,33 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use crate::domain::{
+    entity::suggestion::Suggestion, error::DomainError,
+    repository::autocomplete_repository::AutocompleteRepository,
+};
+
+use super::dataset::Dataset;
+
+pub struct InMemoryAutocompleteRepository {
+    dataset: Arc<Dataset>,
+}
+
+impl InMemoryAutocompleteRepository {
+    pub fn new(dataset: Arc<Dataset>) -> Self {
+        Self { dataset }
+    }
+}
+
+#[async_trait]
+impl AutocompleteRepository for InMemoryAutocompleteRepository {
+    async fn find_by_prefix(
+        &self,
+        prefix: &str,
+        limit: u32,
+    ) -> Result<Vec<Suggestion>, DomainError> {
+        Ok(self
+            .dataset
+            .autocomplete_index()
+            .search(prefix, limit as usize))
+    }
+}
@@ -0,0 +1,112 @@
+use async_trait::async_trait;
+use std::{collections::BTreeSet, sync::Arc};
+
+use crate::domain::{
+    entity::{company::Company, misc::CompanyFilter},
+    error::DomainError,
+    repository::company_repository::CompanyRepository,
+};
+
+use super::dataset::Dataset;
+
+pub struct InMemoryCompanyRepository {
+    dataset: Arc<Dataset>,
+}
+
+impl InMemoryCompanyRepository {
+    pub fn new(dataset: Arc<Dataset>) -> Self {
+        Self { dataset }
+    }
+}
+
+#[async_trait]
+impl CompanyRepository for InMemoryCompanyRepository {
+    async fn find_by_id(&self, id: u32) -> Result<Option<Company>, DomainError> {
+        Ok(self
+            .dataset
+            .company(id)
+            .map(|c| self.dataset.build_company(c)))
+    }
+    async fn find_by_id_vec(&self, id_vec: &[u32]) -> Result<Vec<Company>, DomainError> {
+        let ids: BTreeSet<u32> = id_vec.iter().copied().collect();
+        let companies = ids
+            .into_iter()
+            .filter_map(|id| self.dataset.company(id))
+            .map(|c| self.dataset.build_company(c))
+            .collect();
+
+        Ok(companies)
+    }
+    async fn get_all(&self, filter: &CompanyFilter) -> Result<Vec<Company>, DomainError> {
+        let mut companies: Vec<_> = self
+            .dataset
+            .companies()
+            .filter(|c| filter.company_type.is_none_or(|t| c.company_type == t))
+            .filter(|c| filter.rr_cd.is_none_or(|rr_cd| c.rr_cd == rr_cd))
+            .filter(|c| c.e_status == filter.e_status)
+            .collect();
+        companies.sort_by_key(|c| (c.e_sort, c.company_cd));
+
+        Ok(companies
+            .into_iter()
+            .map(|c| self.dataset.build_company(c))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{path::Path, sync::Arc};
+
+    use super::InMemoryCompanyRepository;
+    use crate::{
+        domain::{entity::misc::CompanyFilter, repository::company_repository::CompanyRepository},
+        infrastructure::memory::dataset::Dataset,
+    };
+
+    fn repository() -> InMemoryCompanyRepository {
+        let dataset = Dataset::load(&Path::new(env!("CARGO_MANIFEST_DIR")).join("../data"))
+            .expect("Failed to load the dataset");
+        InMemoryCompanyRepository::new(Arc::new(dataset))
+    }
+
+    #[tokio::test]
+    async fn find_by_id_vec() {
+        let companies = repository().find_by_id_vec(&[2, 1, 2]).await.unwrap();
+        let company_cds: Vec<u32> = companies.iter().map(|c| c.company_cd).collect();
+        assert_eq!(company_cds, vec![1, 2]);
+    }
+
+    #[tokio::test]
+    async fn get_all() {
+        let repository = repository();
+
+        let companies = repository
+            .get_all(&CompanyFilter::new(None, None, None))
+            .await
+            .unwrap();
+        let company_cds: Vec<u32> = companies.iter().map(|c| c.company_cd).collect();
+        assert!(companies.iter().all(|c| c.e_status == 0));
+        // 廃止された北海道ちほく高原鉄道は含めない
+        assert!(!company_cds.contains(&103));
+        // 事業者コードではなくe_sort順
+        let position =
+            |company_cd: u32| company_cds.iter().position(|cd| *cd == company_cd).unwrap();
+        assert!(position(252) < position(102));
+
+        let companies = repository
+            .get_all(&CompanyFilter::new(None, None, Some(2)))
+            .await
+            .unwrap();
+        assert!(companies.iter().any(|c| c.company_cd == 103));
+        assert!(companies.iter().all(|c| c.e_status == 2));
+
+        let companies = repository
+            .get_all(&CompanyFilter::new(Some(1), None, None))
+            .await
+            .unwrap();
+        let company_cds: Vec<u32> = companies.iter().map(|c| c.company_cd).collect();
+        assert_eq!(&company_cds[..6], &[1, 2, 3, 4, 5, 6]);
+        assert!(companies.iter().all(|c| c.company_type == 1));
+    }
+}
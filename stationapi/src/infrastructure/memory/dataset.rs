@@ -0,0 +1,871 @@
+use csv::ReaderBuilder;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    path::Path,
+    time::UNIX_EPOCH,
+};
+
+use crate::{
+    domain::{
+        entity::{
+            company::Company, dataset_info::DatasetInfo, dataset_summary::DatasetSummary,
+            line::Line, misc::AsOf, prefecture::Prefecture, station::Station,
+            suggestion::SuggestionType,
+        },
+        error::DomainError,
+    },
+    infrastructure::{
+        autocomplete_index::{
+            suggestions_from_names, AutocompleteIndex, ALIAS_NAME_FIELDS, LINE_NAME_FIELDS,
+            STATION_NAME_FIELDS,
+        },
+        spatial_index::{StationPoint, StationSpatialIndex},
+        station_name_index::{StationNameIndex, StationNames},
+    },
+    station_api::StopCondition,
+};
+
+const COMPANIES_FILE_NAME: &str = "1!companies.csv";
+const LINES_FILE_NAME: &str = "2!lines.csv";
+const STATIONS_FILE_NAME: &str = "3!stations.csv";
+const TYPES_FILE_NAME: &str = "4!types.csv";
+const STATION_STATION_TYPES_FILE_NAME: &str = "5!station_station_types.csv";
+const ALIASES_FILE_NAME: &str = "6!aliases.csv";
+const LINE_ALIASES_FILE_NAME: &str = "7!line_aliases.csv";
+// NOTE: stationsが参照するので、マイグレーションでstationsより先に入るよう0番にする
+const PREFECTURES_FILE_NAME: &str = "0!prefectures.csv";
+
+#[derive(Deserialize, Serialize, Clone)]
+pub struct CompanyRecord {
+    pub company_cd: u32,
+    pub rr_cd: u32,
+    pub company_name: String,
+    pub company_name_k: String,
+    pub company_name_h: String,
+    pub company_name_r: String,
+    pub company_name_en: String,
+    pub company_name_full_en: String,
+    pub company_url: Option<String>,
+    pub company_type: u32,
+    pub e_status: u32,
+    pub e_sort: u32,
+}
+
+#[derive(Deserialize, Serialize, Clone)]
+pub struct LineRecord {
+    pub line_cd: u32,
+    pub company_cd: u32,
+    pub line_name: String,
+    pub line_name_k: String,
+    pub line_name_h: String,
+    pub line_name_r: Option<String>,
+    pub line_name_zh: Option<String>,
+    pub line_name_ko: Option<String>,
+    pub line_color_c: Option<String>,
+    pub line_type: Option<u32>,
+    pub line_symbol_primary: Option<String>,
+    pub line_symbol_secondary: Option<String>,
+    pub line_symbol_extra: Option<String>,
+    pub line_symbol_primary_color: Option<String>,
+    pub line_symbol_secondary_color: Option<String>,
+    pub line_symbol_extra_color: Option<String>,
+    pub line_symbol_primary_shape: Option<String>,
+    pub line_symbol_secondary_shape: Option<String>,
+    pub line_symbol_extra_shape: Option<String>,
+    pub e_status: u32,
+    pub e_sort: u32,
+    pub average_distance: f64,
+}
+
+#[derive(Deserialize, Serialize, Clone)]
+pub struct StationRecord {
+    pub station_cd: u32,
+    pub station_g_cd: u32,
+    pub station_name: String,
+    pub station_name_k: String,
+    pub station_name_r: Option<String>,
+    pub station_name_zh: Option<String>,
+    pub station_name_ko: Option<String>,
+    pub primary_station_number: Option<String>,
+    pub secondary_station_number: Option<String>,
+    pub extra_station_number: Option<String>,
+    pub three_letter_code: Option<String>,
+    pub line_cd: u32,
+    pub pref_cd: u32,
+    pub post: String,
+    pub address: String,
+    pub lon: f64,
+    pub lat: f64,
+    pub open_ymd: String,
+    pub close_ymd: String,
+    pub e_status: u32,
+    pub e_sort: u32,
+}
+
+#[derive(Deserialize, Serialize, Clone)]
+pub struct TypeRecord {
+    pub id: u32,
+    pub type_cd: u32,
+    pub type_name: String,
+    pub type_name_k: String,
+    pub type_name_r: Option<String>,
+    pub type_name_zh: Option<String>,
+    pub type_name_ko: Option<String>,
+    pub color: String,
+    pub direction: u32,
+    pub kind: u32,
+    pub top_priority: u32,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct StationStationTypeRecord {
+    pub id: u32,
+    pub station_cd: u32,
+    pub type_cd: u32,
+    pub line_group_cd: u32,
+    pub pass: u32,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct AliasRecord {
+    pub id: u32,
+    pub line_name: Option<String>,
+    pub line_name_k: Option<String>,
+    pub line_name_h: Option<String>,
+    pub line_name_r: Option<String>,
+    pub line_name_zh: Option<String>,
+    pub line_name_ko: Option<String>,
+    pub line_color_c: Option<String>,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct LineAliasRecord {
+    pub id: u32,
+    pub station_cd: u32,
+    pub alias_cd: u32,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct PrefectureRecord {
+    pub pref_cd: u32,
+    pub pref_name: String,
+    pub pref_name_k: String,
+    pub pref_name_r: String,
+    pub pref_name_zh: String,
+    pub pref_name_ko: String,
+}
+
+// NOTE: マイグレーションと同じく `id` が0の行はAUTO_INCREMENTと同じように採番する
+pub struct Dataset {
+    companies: BTreeMap<u32, CompanyRecord>,
+    lines: BTreeMap<u32, LineRecord>,
+    stations: BTreeMap<u32, StationRecord>,
+    types: HashMap<u32, TypeRecord>,
+    station_station_types: Vec<StationStationTypeRecord>,
+    aliases_by_station_cd: HashMap<u32, AliasRecord>,
+    prefectures: BTreeMap<u32, PrefectureRecord>,
+    station_cds_by_group_cd: HashMap<u32, Vec<u32>>,
+    station_cds_by_line_cd: HashMap<u32, Vec<u32>>,
+    station_cds_by_pref_cd: HashMap<u32, Vec<u32>>,
+    sst_indices_by_station_cd: HashMap<u32, Vec<usize>>,
+    sst_indices_by_line_group_cd: HashMap<u32, Vec<usize>>,
+    spatial_index: StationSpatialIndex,
+    name_index: StationNameIndex,
+    autocomplete_index: AutocompleteIndex,
+    info: Option<DatasetInfo>,
+}
+
+impl Dataset {
+    pub fn load(data_path: &Path) -> Result<Self, DomainError> {
+        let companies: Vec<CompanyRecord> = read_records(&data_path.join(COMPANIES_FILE_NAME))?;
+        let lines: Vec<LineRecord> = read_records(&data_path.join(LINES_FILE_NAME))?;
+        let stations: Vec<StationRecord> = read_records(&data_path.join(STATIONS_FILE_NAME))?;
+        let mut types: Vec<TypeRecord> = read_records(&data_path.join(TYPES_FILE_NAME))?;
+        let mut station_station_types: Vec<StationStationTypeRecord> =
+            read_records(&data_path.join(STATION_STATION_TYPES_FILE_NAME))?;
+        let mut aliases: Vec<AliasRecord> = read_records(&data_path.join(ALIASES_FILE_NAME))?;
+        let mut line_aliases: Vec<LineAliasRecord> =
+            read_records(&data_path.join(LINE_ALIASES_FILE_NAME))?;
+        let prefectures: Vec<PrefectureRecord> =
+            read_records(&data_path.join(PREFECTURES_FILE_NAME))?;
+
+        assign_auto_increment_ids(types.iter_mut().map(|row| &mut row.id));
+        assign_auto_increment_ids(station_station_types.iter_mut().map(|row| &mut row.id));
+        assign_auto_increment_ids(aliases.iter_mut().map(|row| &mut row.id));
+        assign_auto_increment_ids(line_aliases.iter_mut().map(|row| &mut row.id));
+        station_station_types.sort_by_key(|sst| sst.id);
+
+        let mut dataset = Self::new(
+            companies,
+            lines,
+            stations,
+            types,
+            station_station_types,
+            aliases,
+            line_aliases,
+            prefectures,
+        );
+        dataset.info = Some(read_dataset_info(data_path)?);
+        Ok(dataset)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        companies: Vec<CompanyRecord>,
+        lines: Vec<LineRecord>,
+        stations: Vec<StationRecord>,
+        types: Vec<TypeRecord>,
+        station_station_types: Vec<StationStationTypeRecord>,
+        aliases: Vec<AliasRecord>,
+        line_aliases: Vec<LineAliasRecord>,
+        prefectures: Vec<PrefectureRecord>,
+    ) -> Self {
+        let companies: BTreeMap<u32, CompanyRecord> = companies
+            .into_iter()
+            .map(|row| (row.company_cd, row))
+            .collect();
+        let lines: BTreeMap<u32, LineRecord> =
+            lines.into_iter().map(|row| (row.line_cd, row)).collect();
+        let stations: BTreeMap<u32, StationRecord> = stations
+            .into_iter()
+            .map(|row| (row.station_cd, row))
+            .collect();
+        let types: HashMap<u32, TypeRecord> =
+            types.into_iter().map(|row| (row.type_cd, row)).collect();
+
+        let aliases: HashMap<u32, AliasRecord> =
+            aliases.into_iter().map(|row| (row.id, row)).collect();
+        let aliases_by_station_cd = line_aliases
+            .iter()
+            .filter_map(|la| Some((la.station_cd, aliases.get(&la.alias_cd)?.clone())))
+            .collect();
+        let prefectures: BTreeMap<u32, PrefectureRecord> = prefectures
+            .into_iter()
+            .map(|row| (row.pref_cd, row))
+            .collect();
+
+        let mut station_cds_by_group_cd: HashMap<u32, Vec<u32>> = HashMap::new();
+        let mut station_cds_by_line_cd: HashMap<u32, Vec<u32>> = HashMap::new();
+        let mut station_cds_by_pref_cd: HashMap<u32, Vec<u32>> = HashMap::new();
+        for station in stations.values() {
+            station_cds_by_group_cd
+                .entry(station.station_g_cd)
+                .or_default()
+                .push(station.station_cd);
+            station_cds_by_line_cd
+                .entry(station.line_cd)
+                .or_default()
+                .push(station.station_cd);
+            station_cds_by_pref_cd
+                .entry(station.pref_cd)
+                .or_default()
+                .push(station.station_cd);
+        }
+
+        let mut sst_indices_by_station_cd: HashMap<u32, Vec<usize>> = HashMap::new();
+        let mut sst_indices_by_line_group_cd: HashMap<u32, Vec<usize>> = HashMap::new();
+        for (index, sst) in station_station_types.iter().enumerate() {
+            sst_indices_by_station_cd
+                .entry(sst.station_cd)
+                .or_default()
+                .push(index);
+            sst_indices_by_line_group_cd
+                .entry(sst.line_group_cd)
+                .or_default()
+                .push(index);
+        }
+
+        // SQL版と同じくlinesとJOINできる駅のみを対象とする
+        let spatial_index = StationSpatialIndex::new(
+            stations
+                .values()
+                .filter_map(|s| {
+                    let line = lines.get(&s.line_cd)?;
+                    Some(StationPoint {
+                        station_cd: s.station_cd,
+                        station_g_cd: s.station_g_cd,
+                        line_cd: s.line_cd,
+                        lat: s.lat,
+                        lon: s.lon,
+                        e_status: s.e_status,
+                        company_cd: line.company_cd,
+                        line_type: line.line_type.unwrap_or_default(),
+                        average_distance: line.average_distance,
+                    })
+                })
+                .collect(),
+        );
+
+        let name_index = StationNameIndex::new(
+            stations
+                .values()
+                .map(|s| StationNames {
+                    station_cd: s.station_cd,
+                    names: [
+                        Some(&s.station_name),
+                        Some(&s.station_name_k),
+                        s.station_name_r.as_ref(),
+                        s.station_name_zh.as_ref(),
+                        s.station_name_ko.as_ref(),
+                    ]
+                    .into_iter()
+                    .flatten()
+                    .cloned()
+                    .collect(),
+                })
+                .collect(),
+        );
+
+        let autocomplete_index =
+            Self::build_autocomplete_index(&lines, &stations, &aliases_by_station_cd);
+
+        Self {
+            companies,
+            lines,
+            stations,
+            types,
+            station_station_types,
+            aliases_by_station_cd,
+            prefectures,
+            station_cds_by_group_cd,
+            station_cds_by_line_cd,
+            station_cds_by_pref_cd,
+            sst_indices_by_station_cd,
+            sst_indices_by_line_group_cd,
+            spatial_index,
+            name_index,
+            autocomplete_index,
+            info: None,
+        }
+    }
+
+    // SQL版と同じく運用中の駅・路線と、運用中の駅に付いた路線の別名を対象とする
+    fn build_autocomplete_index(
+        lines: &BTreeMap<u32, LineRecord>,
+        stations: &BTreeMap<u32, StationRecord>,
+        aliases_by_station_cd: &HashMap<u32, AliasRecord>,
+    ) -> AutocompleteIndex {
+        let active_stations = stations.values().filter(|s| {
+            s.e_status == 0 && lines.get(&s.line_cd).is_some_and(|line| line.e_status == 0)
+        });
+
+        let mut suggestions = vec![];
+        for station in active_stations {
+            suggestions.extend(suggestions_from_names(
+                SuggestionType::StationGroup,
+                station.station_g_cd,
+                &STATION_NAME_FIELDS,
+                &[
+                    Some(station.station_name.as_str()),
+                    Some(station.station_name_k.as_str()),
+                    station.station_name_r.as_deref(),
+                    station.station_name_zh.as_deref(),
+                    station.station_name_ko.as_deref(),
+                ],
+            ));
+            if let Some(alias) = aliases_by_station_cd.get(&station.station_cd) {
+                suggestions.extend(suggestions_from_names(
+                    SuggestionType::Line,
+                    station.line_cd,
+                    &ALIAS_NAME_FIELDS,
+                    &[
+                        alias.line_name.as_deref(),
+                        alias.line_name_k.as_deref(),
+                        alias.line_name_h.as_deref(),
+                        alias.line_name_r.as_deref(),
+                        alias.line_name_zh.as_deref(),
+                        alias.line_name_ko.as_deref(),
+                    ],
+                ));
+            }
+        }
+        for line in lines.values().filter(|line| line.e_status == 0) {
+            suggestions.extend(suggestions_from_names(
+                SuggestionType::Line,
+                line.line_cd,
+                &LINE_NAME_FIELDS,
+                &[
+                    Some(line.line_name.as_str()),
+                    Some(line.line_name_k.as_str()),
+                    Some(line.line_name_h.as_str()),
+                    line.line_name_r.as_deref(),
+                    line.line_name_zh.as_deref(),
+                    line.line_name_ko.as_deref(),
+                ],
+            ));
+        }
+
+        AutocompleteIndex::new(suggestions)
+    }
+
+    pub fn summary(&self) -> DatasetSummary {
+        DatasetSummary {
+            companies: self.companies.len() as u64,
+            lines: self.lines.len() as u64,
+            stations: self.stations.len() as u64,
+            train_types: self.types.len() as u64,
+        }
+    }
+
+    // NOTE: 差し替える前に、参照先が欠けていないかだけ確かめる
+    pub fn validate(&self) -> Result<(), DomainError> {
+        if self.stations.is_empty() {
+            return Err(DomainError::Unexpected(
+                "The dataset has no stations".to_string(),
+            ));
+        }
+        if let Some(line) = self
+            .lines
+            .values()
+            .find(|line| !self.companies.contains_key(&line.company_cd))
+        {
+            return Err(DomainError::Unexpected(format!(
+                "Line {} refers to unknown company {}",
+                line.line_cd, line.company_cd
+            )));
+        }
+        if let Some(station) = self.stations.values().find(|station| {
+            !self.lines.contains_key(&station.line_cd)
+                || !self.prefectures.contains_key(&station.pref_cd)
+        }) {
+            return Err(DomainError::Unexpected(format!(
+                "Station {} refers to unknown line {} or prefecture {}",
+                station.station_cd, station.line_cd, station.pref_cd
+            )));
+        }
+        if let Some(sst) = self.station_station_types.iter().find(|sst| {
+            !self.stations.contains_key(&sst.station_cd) || !self.types.contains_key(&sst.type_cd)
+        }) {
+            return Err(DomainError::Unexpected(format!(
+                "station_station_types {} refers to unknown station {} or type {}",
+                sst.id, sst.station_cd, sst.type_cd
+            )));
+        }
+        Ok(())
+    }
+
+    pub fn info(&self) -> Option<&DatasetInfo> {
+        self.info.as_ref()
+    }
+
+    pub fn companies(&self) -> impl Iterator<Item = &CompanyRecord> {
+        self.companies.values()
+    }
+    pub fn company(&self, company_cd: u32) -> Option<&CompanyRecord> {
+        self.companies.get(&company_cd)
+    }
+    pub fn lines(&self) -> impl Iterator<Item = &LineRecord> {
+        self.lines.values()
+    }
+    pub fn line(&self, line_cd: u32) -> Option<&LineRecord> {
+        self.lines.get(&line_cd)
+    }
+    pub fn stations(&self) -> impl Iterator<Item = &StationRecord> {
+        self.stations.values()
+    }
+    pub fn station(&self, station_cd: u32) -> Option<&StationRecord> {
+        self.stations.get(&station_cd)
+    }
+    pub fn stations_by_group_cd(&self, station_g_cd: u32) -> impl Iterator<Item = &StationRecord> {
+        self.station_cds_by_group_cd
+            .get(&station_g_cd)
+            .into_iter()
+            .flatten()
+            .filter_map(|station_cd| self.stations.get(station_cd))
+    }
+    pub fn stations_by_line_cd(&self, line_cd: u32) -> impl Iterator<Item = &StationRecord> {
+        self.station_cds_by_line_cd
+            .get(&line_cd)
+            .into_iter()
+            .flatten()
+            .filter_map(|station_cd| self.stations.get(station_cd))
+    }
+    pub fn stations_by_pref_cd(&self, pref_cd: u32) -> impl Iterator<Item = &StationRecord> {
+        self.station_cds_by_pref_cd
+            .get(&pref_cd)
+            .into_iter()
+            .flatten()
+            .filter_map(|station_cd| self.stations.get(station_cd))
+    }
+    pub fn prefectures(&self) -> impl Iterator<Item = &PrefectureRecord> {
+        self.prefectures.values()
+    }
+    pub fn prefecture(&self, pref_cd: u32) -> Option<&PrefectureRecord> {
+        self.prefectures.get(&pref_cd)
+    }
+    pub fn train_types(&self) -> impl Iterator<Item = &TypeRecord> {
+        self.types.values()
+    }
+    pub fn train_type(&self, type_cd: u32) -> Option<&TypeRecord> {
+        self.types.get(&type_cd)
+    }
+    pub fn station_station_types(&self) -> impl Iterator<Item = &StationStationTypeRecord> {
+        self.station_station_types.iter()
+    }
+    pub fn sst_by_station_cd(
+        &self,
+        station_cd: u32,
+    ) -> impl Iterator<Item = &StationStationTypeRecord> {
+        self.sst_indices_by_station_cd
+            .get(&station_cd)
+            .into_iter()
+            .flatten()
+            .map(|&index| &self.station_station_types[index])
+    }
+    pub fn sst_by_line_group_cd(
+        &self,
+        line_group_cd: u32,
+    ) -> impl Iterator<Item = &StationStationTypeRecord> {
+        self.sst_indices_by_line_group_cd
+            .get(&line_group_cd)
+            .into_iter()
+            .flatten()
+            .map(|&index| &self.station_station_types[index])
+    }
+    pub fn alias_by_station_cd(&self, station_cd: u32) -> Option<&AliasRecord> {
+        self.aliases_by_station_cd.get(&station_cd)
+    }
+    pub fn spatial_index(&self) -> &StationSpatialIndex {
+        &self.spatial_index
+    }
+    pub fn name_index(&self) -> &StationNameIndex {
+        &self.name_index
+    }
+    pub fn autocomplete_index(&self) -> &AutocompleteIndex {
+        &self.autocomplete_index
+    }
+
+    // SQL版の `StationRow` と同じくlines, aliases, station_station_types, typesをJOINした形にする
+    pub fn build_station(
+        &self,
+        station: &StationRecord,
+        sst: Option<&StationStationTypeRecord>,
+        has_train_types: bool,
+    ) -> Option<Station> {
+        let line = self.lines.get(&station.line_cd)?;
+        let alias = self.alias_by_station_cd(station.station_cd);
+        let train_type = sst.and_then(|sst| self.types.get(&sst.type_cd));
+
+        let stop_condition = match sst.map(|sst| sst.pass).unwrap_or(0) {
+            0 => StopCondition::All,
+            1 => StopCondition::Not,
+            2 => StopCondition::Partial,
+            3 => StopCondition::Weekday,
+            4 => StopCondition::Holiday,
+            5 => StopCondition::PartialStop,
+            _ => StopCondition::All,
+        };
+
+        Some(Station {
+            station_cd: station.station_cd,
+            station_g_cd: station.station_g_cd,
+            station_name: station.station_name.clone(),
+            station_name_k: station.station_name_k.clone(),
+            station_name_r: station.station_name_r.clone(),
+            station_name_zh: station.station_name_zh.clone(),
+            station_name_ko: station.station_name_ko.clone(),
+            station_numbers: vec![],
+            primary_station_number: station.primary_station_number.clone(),
+            secondary_station_number: station.secondary_station_number.clone(),
+            extra_station_number: station.extra_station_number.clone(),
+            three_letter_code: station.three_letter_code.clone(),
+            line_cd: station.line_cd,
+            line: None,
+            lines: vec![],
+            pref_cd: station.pref_cd,
+            post: station.post.clone(),
+            address: station.address.clone(),
+            lon: station.lon,
+            lat: station.lat,
+            open_ymd: station.open_ymd.clone(),
+            close_ymd: station.close_ymd.clone(),
+            e_status: station.e_status,
+            e_sort: station.e_sort,
+            stop_condition,
+            distance: None,
+            train_type: None,
+            has_train_types,
+            company_cd: Some(line.company_cd),
+            line_name: coalesce(alias.and_then(|a| a.line_name.as_ref()), &line.line_name),
+            line_name_k: coalesce(
+                alias.and_then(|a| a.line_name_k.as_ref()),
+                &line.line_name_k,
+            ),
+            line_name_h: coalesce(
+                alias.and_then(|a| a.line_name_h.as_ref()),
+                &line.line_name_h,
+            ),
+            line_name_r: alias
+                .and_then(|a| a.line_name_r.clone())
+                .or_else(|| line.line_name_r.clone()),
+            line_name_zh: alias
+                .and_then(|a| a.line_name_zh.clone())
+                .or_else(|| line.line_name_zh.clone()),
+            line_name_ko: alias
+                .and_then(|a| a.line_name_ko.clone())
+                .or_else(|| line.line_name_ko.clone()),
+            line_color_c: alias
+                .and_then(|a| a.line_color_c.clone())
+                .or_else(|| line.line_color_c.clone()),
+            line_type: line.line_type,
+            line_symbol_primary: line.line_symbol_primary.clone(),
+            line_symbol_secondary: line.line_symbol_secondary.clone(),
+            line_symbol_extra: line.line_symbol_extra.clone(),
+            line_symbol_primary_color: line.line_symbol_primary_color.clone(),
+            line_symbol_secondary_color: line.line_symbol_secondary_color.clone(),
+            line_symbol_extra_color: line.line_symbol_extra_color.clone(),
+            line_symbol_primary_shape: line.line_symbol_primary_shape.clone(),
+            line_symbol_secondary_shape: line.line_symbol_secondary_shape.clone(),
+            line_symbol_extra_shape: line.line_symbol_extra_shape.clone(),
+            average_distance: line.average_distance,
+            type_id: train_type.map(|t| t.id),
+            sst_id: sst.map(|sst| sst.id),
+            type_cd: sst.map(|sst| sst.type_cd),
+            line_group_cd: sst.map(|sst| sst.line_group_cd),
+            pass: sst.map(|sst| sst.pass),
+            type_name: train_type.map(|t| t.type_name.clone()),
+            type_name_k: train_type.map(|t| t.type_name_k.clone()),
+            type_name_r: train_type.and_then(|t| t.type_name_r.clone()),
+            type_name_zh: train_type.and_then(|t| t.type_name_zh.clone()),
+            type_name_ko: train_type.and_then(|t| t.type_name_ko.clone()),
+            color: train_type.map(|t| t.color.clone()),
+            direction: train_type.map(|t| t.direction),
+            kind: train_type.map(|t| t.kind),
+        })
+    }
+
+    // NOTE: `station` がある場合はSQL版と同じくline_aliasesの路線名を優先する
+    pub fn build_line(
+        &self,
+        line: &LineRecord,
+        station: Option<&StationRecord>,
+        line_group_cd: Option<u32>,
+    ) -> Line {
+        let alias = station.and_then(|s| self.alias_by_station_cd(s.station_cd));
+
+        Line {
+            line_cd: line.line_cd,
+            company_cd: line.company_cd,
+            company: None,
+            line_name: alias
+                .and_then(|a| a.line_name.clone())
+                .unwrap_or_else(|| line.line_name.clone()),
+            line_name_k: alias
+                .and_then(|a| a.line_name_k.clone())
+                .unwrap_or_else(|| line.line_name_k.clone()),
+            line_name_h: alias
+                .and_then(|a| a.line_name_h.clone())
+                .unwrap_or_else(|| line.line_name_h.clone()),
+            line_name_r: alias
+                .and_then(|a| a.line_name_r.clone())
+                .or_else(|| line.line_name_r.clone()),
+            line_name_zh: alias
+                .and_then(|a| a.line_name_zh.clone())
+                .or_else(|| line.line_name_zh.clone()),
+            line_name_ko: alias
+                .and_then(|a| a.line_name_ko.clone())
+                .or_else(|| line.line_name_ko.clone()),
+            line_color_c: alias
+                .and_then(|a| a.line_color_c.clone())
+                .or_else(|| line.line_color_c.clone()),
+            line_type: line.line_type,
+            line_symbols: vec![],
+            line_symbol_primary: line.line_symbol_primary.clone(),
+            line_symbol_secondary: line.line_symbol_secondary.clone(),
+            line_symbol_extra: line.line_symbol_extra.clone(),
+            line_symbol_primary_color: line.line_symbol_primary_color.clone(),
+            line_symbol_secondary_color: line.line_symbol_secondary_color.clone(),
+            line_symbol_extra_color: line.line_symbol_extra_color.clone(),
+            line_symbol_primary_shape: line.line_symbol_primary_shape.clone(),
+            line_symbol_secondary_shape: line.line_symbol_secondary_shape.clone(),
+            line_symbol_extra_shape: line.line_symbol_extra_shape.clone(),
+            e_status: line.e_status,
+            e_sort: line.e_sort,
+            average_distance: line.average_distance,
+            station: None,
+            train_type: None,
+            line_group_cd,
+            station_cd: station.map(|s| s.station_cd),
+            station_g_cd: station.map(|s| s.station_g_cd),
+        }
+    }
+
+    pub fn build_company(&self, company: &CompanyRecord) -> Company {
+        Company {
+            company_cd: company.company_cd,
+            rr_cd: company.rr_cd,
+            company_name: company.company_name.clone(),
+            company_name_k: company.company_name_k.clone(),
+            company_name_h: company.company_name_h.clone(),
+            company_name_r: company.company_name_r.clone(),
+            company_name_en: company.company_name_en.clone(),
+            company_name_full_en: company.company_name_full_en.clone(),
+            company_url: company.company_url.clone(),
+            company_type: company.company_type,
+            e_status: company.e_status,
+            e_sort: company.e_sort,
+        }
+    }
+
+    pub fn build_prefecture(&self, prefecture: &PrefectureRecord) -> Prefecture {
+        Prefecture {
+            pref_cd: prefecture.pref_cd,
+            pref_name: prefecture.pref_name.clone(),
+            pref_name_k: prefecture.pref_name_k.clone(),
+            pref_name_r: prefecture.pref_name_r.clone(),
+            pref_name_zh: prefecture.pref_name_zh.clone(),
+            pref_name_ko: prefecture.pref_name_ko.clone(),
+        }
+    }
+
+    pub fn is_active_station(&self, station: &StationRecord) -> bool {
+        station.e_status == 0
+            && self
+                .lines
+                .get(&station.line_cd)
+                .is_some_and(|line| line.e_status == 0)
+    }
+    // as_ofを指定しなければis_active_stationと同じ
+    pub fn is_operating_station(&self, station: &StationRecord, as_of: Option<&AsOf>) -> bool {
+        match as_of {
+            Some(as_of) => {
+                self.lines.contains_key(&station.line_cd)
+                    && as_of.is_operating(&station.open_ymd, &station.close_ymd, station.e_status)
+            }
+            None => self.is_active_station(station),
+        }
+    }
+}
+
+// `LIKE '%keyword%'` 相当
+pub fn contains_ignore_case(haystack: &str, keyword: &str) -> bool {
+    haystack.to_lowercase().contains(&keyword.to_lowercase())
+}
+
+fn coalesce(alias_value: Option<&String>, value: &str) -> Option<String> {
+    Some(alias_value.cloned().unwrap_or_else(|| value.to_string()))
+}
+
+fn read_records<T: DeserializeOwned>(path: &Path) -> Result<Vec<T>, DomainError> {
+    let mut rdr = ReaderBuilder::new().from_path(path)?;
+    let records = rdr.deserialize().collect::<Result<Vec<T>, csv::Error>>()?;
+    Ok(records)
+}
+
+// NOTE: migrationのdataset_content_hashと同じ計算。作成日時はCSVの最終更新日時にする
+fn read_dataset_info(data_path: &Path) -> Result<DatasetInfo, DomainError> {
+    let mut file_names: Vec<String> = fs::read_dir(data_path)?
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.is_file() && path.extension()? == "csv" {
+                Some(path.file_name()?.to_string_lossy().into_owned())
+            } else {
+                None
+            }
+        })
+        .collect();
+    file_names.sort();
+
+    let mut hasher = Sha256::new();
+    let mut created_at = 0;
+    for file_name in &file_names {
+        let path = data_path.join(file_name);
+        let contents = fs::read(&path)?;
+        hasher.update(file_name.as_bytes());
+        hasher.update([0]);
+        hasher.update((contents.len() as u64).to_le_bytes());
+        hasher.update(&contents);
+
+        let modified = fs::metadata(&path)?.modified()?;
+        created_at = created_at.max(
+            modified
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |duration| duration.as_secs()),
+        );
+    }
+    let content_hash: String = hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+
+    Ok(DatasetInfo {
+        version: content_hash[..16].to_string(),
+        content_hash,
+        created_at,
+    })
+}
+
+fn assign_auto_increment_ids<'a>(ids: impl Iterator<Item = &'a mut u32>) {
+    let mut next_id = 1;
+    for id in ids {
+        if *id == 0 {
+            *id = next_id;
+        }
+        next_id = next_id.max(*id + 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::Dataset;
+
+    #[test]
+    fn load() {
+        let dataset = Dataset::load(&Path::new(env!("CARGO_MANIFEST_DIR")).join("../data"))
+            .expect("Failed to load the dataset");
+
+        let station = dataset.station(100201).unwrap();
+        assert_eq!(station.station_name, "東京");
+        assert_eq!(station.line_cd, 1002);
+        assert!(dataset.is_active_station(station));
+
+        let sst = dataset.sst_by_station_cd(100201).next().unwrap();
+        assert_eq!(sst.id, 1);
+        assert_eq!(dataset.train_type(sst.type_cd).unwrap().type_name, "のぞみ");
+
+        let station = dataset.build_station(station, Some(sst), true).unwrap();
+        assert_eq!(station.line_name, Some("東海道新幹線".to_string()));
+        assert_eq!(station.type_name, Some("のぞみ".to_string()));
+        assert_eq!(station.line_group_cd, Some(1));
+
+        let prefecture = dataset.prefecture(station.pref_cd).unwrap();
+        assert_eq!(prefecture.pref_name, "東京都");
+        assert_eq!(dataset.prefectures().count(), 47);
+
+        dataset.validate().expect("The dataset should be valid");
+        let info = dataset.info().unwrap();
+        assert_eq!(info.content_hash.len(), 64);
+        assert!(info.content_hash.starts_with(&info.version));
+        assert!(dataset.summary().stations > 0);
+    }
+
+    #[test]
+    fn spatial_index() {
+        let dataset = Dataset::load(&Path::new(env!("CARGO_MANIFEST_DIR")).join("../data"))
+            .expect("Failed to load the dataset");
+        let station = dataset.station(100201).unwrap();
+
+        let (nearest, distance) = dataset
+            .spatial_index()
+            .nearest_station(station.lat, station.lon, Some(1002))
+            .unwrap();
+        assert_eq!(nearest.station_cd, 100201);
+        assert!(distance < 0.001);
+    }
+
+    #[test]
+    fn name_index() {
+        let dataset = Dataset::load(&Path::new(env!("CARGO_MANIFEST_DIR")).join("../data"))
+            .expect("Failed to load the dataset");
+
+        let station_cds = dataset.name_index().search("とうきょう");
+        let station = dataset.station(station_cds[0]).unwrap();
+        assert_eq!(station.station_name, "東京");
+    }
+}
@@ -0,0 +1,212 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::domain::entity::dataset_changelog::{
+    ChangeKind, DatasetChangelog, FieldChange, RecordChange,
+};
+
+use super::dataset::Dataset;
+
+// NOTE: types.idはCSVの行順で採番されるので、行を足すだけで後ろがすべて変わってしまう
+const IGNORED_TYPE_FIELDS: [&str; 1] = ["id"];
+
+pub fn diff_datasets(old: &Dataset, new: &Dataset) -> DatasetChangelog {
+    DatasetChangelog {
+        from_version: old.info().map(|info| info.version.clone()),
+        to_version: new.info().map(|info| info.version.clone()),
+        companies: diff_records(
+            old.companies().map(|company| (company.company_cd, company)),
+            new.companies().map(|company| (company.company_cd, company)),
+            &[],
+        ),
+        lines: diff_records(
+            old.lines().map(|line| (line.line_cd, line)),
+            new.lines().map(|line| (line.line_cd, line)),
+            &[],
+        ),
+        stations: diff_records(
+            old.stations().map(|station| (station.station_cd, station)),
+            new.stations().map(|station| (station.station_cd, station)),
+            &[],
+        ),
+        train_types: diff_records(
+            old.train_types()
+                .map(|train_type| (train_type.type_cd, train_type)),
+            new.train_types()
+                .map(|train_type| (train_type.type_cd, train_type)),
+            &IGNORED_TYPE_FIELDS,
+        ),
+    }
+}
+
+// キーの昇順に並べる
+pub fn diff_records<'a, R: Serialize + 'a>(
+    old: impl Iterator<Item = (u32, &'a R)>,
+    new: impl Iterator<Item = (u32, &'a R)>,
+    ignored_fields: &[&str],
+) -> Vec<RecordChange> {
+    let old: BTreeMap<u32, Map<String, Value>> = old
+        .map(|(key, record)| (key, to_fields(record, ignored_fields)))
+        .collect();
+    let new: BTreeMap<u32, Map<String, Value>> = new
+        .map(|(key, record)| (key, to_fields(record, ignored_fields)))
+        .collect();
+    let empty = Map::new();
+
+    old.keys()
+        .chain(new.keys())
+        .copied()
+        .collect::<BTreeSet<u32>>()
+        .into_iter()
+        .filter_map(|key| {
+            let (change, fields) = match (old.get(&key), new.get(&key)) {
+                (None, Some(new)) => (ChangeKind::Added, field_changes(&empty, new)),
+                (Some(old), None) => (ChangeKind::Removed, field_changes(old, &empty)),
+                (Some(old), Some(new)) => (ChangeKind::Modified, field_changes(old, new)),
+                (None, None) => return None,
+            };
+            if change == ChangeKind::Modified && fields.is_empty() {
+                return None;
+            }
+            Some(RecordChange {
+                key,
+                change,
+                fields,
+            })
+        })
+        .collect()
+}
+
+fn to_fields<R: Serialize>(record: &R, ignored_fields: &[&str]) -> Map<String, Value> {
+    match serde_json::to_value(record) {
+        Ok(Value::Object(mut fields)) => {
+            for field in ignored_fields {
+                fields.remove(*field);
+            }
+            fields
+        }
+        _ => Map::new(),
+    }
+}
+
+fn field_changes(old: &Map<String, Value>, new: &Map<String, Value>) -> Vec<FieldChange> {
+    old.keys()
+        .chain(new.keys())
+        .collect::<BTreeSet<&String>>()
+        .into_iter()
+        .filter_map(|field| {
+            let old = old.get(field).unwrap_or(&Value::Null);
+            let new = new.get(field).unwrap_or(&Value::Null);
+            (old != new).then(|| FieldChange {
+                field: field.clone(),
+                old: old.clone(),
+                new: new.clone(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use serde::Serialize;
+    use serde_json::{json, Value};
+
+    use crate::{
+        domain::entity::dataset_changelog::ChangeKind, infrastructure::memory::dataset::Dataset,
+    };
+
+    use super::{diff_datasets, diff_records};
+
+    #[derive(Serialize)]
+    struct Record {
+        id: u32,
+        name: &'static str,
+        close_ymd: Option<&'static str>,
+    }
+
+    #[test]
+    fn diff_records_by_key() {
+        let old = [
+            Record {
+                id: 1,
+                name: "東京",
+                close_ymd: None,
+            },
+            Record {
+                id: 2,
+                name: "有楽町",
+                close_ymd: None,
+            },
+            Record {
+                id: 3,
+                name: "新橋",
+                close_ymd: None,
+            },
+        ];
+        let new = [
+            Record {
+                id: 10,
+                name: "東京",
+                close_ymd: None,
+            },
+            Record {
+                id: 3,
+                name: "新橋",
+                close_ymd: Some("2026-10-18"),
+            },
+            Record {
+                id: 4,
+                name: "浜松町",
+                close_ymd: None,
+            },
+        ];
+        // 無視する項目しか変わっていなければ変更なし
+        let changes = diff_records(
+            std::iter::once((1, &old[0])),
+            std::iter::once((1, &new[0])),
+            &["id"],
+        );
+        assert!(changes.is_empty());
+
+        let changes = diff_records(
+            old.iter().map(|record| (record.id, record)),
+            new.iter().map(|record| (record.id, record)),
+            &[],
+        );
+        let summary: Vec<(u32, ChangeKind)> = changes
+            .iter()
+            .map(|change| (change.key, change.change))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (1, ChangeKind::Removed),
+                (2, ChangeKind::Removed),
+                (3, ChangeKind::Modified),
+                (4, ChangeKind::Added),
+                (10, ChangeKind::Added),
+            ]
+        );
+        assert_eq!(changes[2].fields.len(), 1);
+        assert_eq!(changes[2].fields[0].field, "close_ymd");
+        assert_eq!(changes[2].fields[0].old, Value::Null);
+        assert_eq!(changes[2].fields[0].new, json!("2026-10-18"));
+        // 追加したレコードはnullでない項目だけ並べる
+        assert_eq!(changes[3].fields.len(), 2);
+    }
+
+    #[test]
+    fn diff_same_dataset() {
+        let data_path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../data");
+        let old = Dataset::load(&data_path).expect("Failed to load the dataset");
+        let new = Dataset::load(&data_path).expect("Failed to load the dataset");
+
+        let changelog = diff_datasets(&old, &new);
+        assert!(changelog.is_empty());
+        assert_eq!(changelog.from_version, changelog.to_version);
+    }
+}
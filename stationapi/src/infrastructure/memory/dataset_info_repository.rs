@@ -0,0 +1,26 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use crate::domain::{
+    entity::dataset_info::DatasetInfo, error::DomainError,
+    repository::dataset_info_repository::DatasetInfoRepository,
+};
+
+use super::dataset::Dataset;
+
+pub struct InMemoryDatasetInfoRepository {
+    dataset: Arc<Dataset>,
+}
+
+impl InMemoryDatasetInfoRepository {
+    pub fn new(dataset: Arc<Dataset>) -> Self {
+        Self { dataset }
+    }
+}
+
+#[async_trait]
+impl DatasetInfoRepository for InMemoryDatasetInfoRepository {
+    async fn find_current(&self) -> Result<Option<DatasetInfo>, DomainError> {
+        Ok(self.dataset.info().cloned())
+    }
+}
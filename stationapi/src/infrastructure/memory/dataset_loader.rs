@@ -0,0 +1,170 @@
+use std::{path::PathBuf, sync::Arc};
+
+use async_trait::async_trait;
+
+use crate::{
+    domain::{
+        entity::{dataset_changelog::DatasetChangelog, dataset_summary::DatasetSummary},
+        error::DomainError,
+    },
+    use_case::{
+        error::UseCaseError, interactor::query::QueryInteractor, traits::reload::DatasetLoader,
+    },
+};
+
+use super::{
+    autocomplete_repository::InMemoryAutocompleteRepository,
+    company_repository::InMemoryCompanyRepository, dataset::Dataset, dataset_diff::diff_datasets,
+    dataset_info_repository::InMemoryDatasetInfoRepository,
+    line_repository::InMemoryLineRepository, prefecture_repository::InMemoryPrefectureRepository,
+    station_repository::InMemoryStationRepository,
+    train_type_repository::InMemoryTrainTypeRepository,
+};
+
+pub type InMemoryQueryInteractor = QueryInteractor<
+    InMemoryStationRepository,
+    InMemoryLineRepository,
+    InMemoryTrainTypeRepository,
+    InMemoryCompanyRepository,
+    InMemoryAutocompleteRepository,
+    InMemoryPrefectureRepository,
+    InMemoryDatasetInfoRepository,
+>;
+
+// sourceはCSVのあるディレクトリ
+#[derive(Default)]
+pub struct InMemoryDatasetLoader;
+
+impl InMemoryDatasetLoader {
+    pub fn build_query_interactor(dataset: Arc<Dataset>) -> InMemoryQueryInteractor {
+        QueryInteractor {
+            station_repository: InMemoryStationRepository::new(Arc::clone(&dataset)),
+            line_repository: InMemoryLineRepository::new(Arc::clone(&dataset)),
+            train_type_repository: InMemoryTrainTypeRepository::new(Arc::clone(&dataset)),
+            company_repository: InMemoryCompanyRepository::new(Arc::clone(&dataset)),
+            autocomplete_repository: InMemoryAutocompleteRepository::new(Arc::clone(&dataset)),
+            prefecture_repository: InMemoryPrefectureRepository::new(Arc::clone(&dataset)),
+            dataset_info_repository: InMemoryDatasetInfoRepository::new(Arc::clone(&dataset)),
+            station_graph: Default::default(),
+        }
+    }
+}
+
+#[async_trait]
+impl DatasetLoader for InMemoryDatasetLoader {
+    type QueryUseCase = InMemoryQueryInteractor;
+
+    async fn load(
+        &self,
+        source: &str,
+    ) -> Result<(Self::QueryUseCase, DatasetSummary), UseCaseError> {
+        let dataset = load_dataset(source).await?;
+        dataset.validate().map_err(|err| match err {
+            DomainError::Unexpected(message) => UseCaseError::InvalidArgument(message),
+            err => err.into(),
+        })?;
+
+        let summary = dataset.summary();
+        Ok((Self::build_query_interactor(Arc::new(dataset)), summary))
+    }
+
+    async fn diff(
+        &self,
+        base_source: &str,
+        target_source: &str,
+    ) -> Result<DatasetChangelog, UseCaseError> {
+        let (base, target) =
+            tokio::try_join!(load_dataset(base_source), load_dataset(target_source))?;
+        Ok(diff_datasets(&base, &target))
+    }
+}
+
+// NOTE: CSVの読み込みと索引の構築は重いのでランタイムのスレッドを塞がないようにする
+async fn load_dataset(source: &str) -> Result<Dataset, UseCaseError> {
+    let data_path = PathBuf::from(source);
+    let dataset = tokio::task::spawn_blocking(move || Dataset::load(&data_path))
+        .await
+        .map_err(|err| UseCaseError::Other(err.into()))??;
+    Ok(dataset)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{path::Path, sync::Arc};
+
+    use crate::{
+        domain::entity::misc::StationFieldMask,
+        use_case::{
+            cache::{CacheConfig, QueryCache},
+            error::UseCaseError,
+            interactor::{
+                cached_query::CachedQueryInteractor, reload::ReloadInteractor,
+                reloadable_query::ReloadableQueryInteractor,
+            },
+            traits::{
+                query::QueryUseCase,
+                reload::{DatasetLoader, ReloadUseCase},
+            },
+        },
+    };
+
+    use super::InMemoryDatasetLoader;
+
+    #[tokio::test]
+    async fn reload_dataset() {
+        let data_path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../data")
+            .to_string_lossy()
+            .to_string();
+        let (query_interactor, summary) = InMemoryDatasetLoader.load(&data_path).await.unwrap();
+        assert!(summary.stations > 0);
+
+        let dataset_info = query_interactor.get_dataset_info().await.unwrap();
+        let reloadable = ReloadableQueryInteractor::new(query_interactor, dataset_info);
+        let query_cache = Arc::new(QueryCache::new(CacheConfig::default()));
+        let query_use_case =
+            CachedQueryInteractor::new(reloadable.clone(), Arc::clone(&query_cache));
+        let reload_interactor = ReloadInteractor::new(
+            InMemoryDatasetLoader,
+            reloadable,
+            Arc::clone(&query_cache),
+            data_path.clone(),
+        );
+
+        let before = query_use_case
+            .find_station_by_id(100201, &StationFieldMask::ALL)
+            .await
+            .unwrap();
+        assert_eq!(query_cache.stats().entries, 1);
+
+        assert_eq!(
+            reload_interactor.reload_dataset(None).await.unwrap(),
+            summary
+        );
+        assert_eq!(query_cache.stats().entries, 0);
+        let after = query_use_case
+            .find_station_by_id(100201, &StationFieldMask::ALL)
+            .await
+            .unwrap();
+        assert_eq!(before, after);
+
+        // 読み込めなければ今のデータのまま
+        assert!(matches!(
+            reload_interactor
+                .reload_dataset(Some("/nonexistent".to_string()))
+                .await,
+            Err(UseCaseError::Other(_))
+        ));
+        assert!(query_use_case
+            .find_station_by_id(100201, &StationFieldMask::ALL)
+            .await
+            .unwrap()
+            .is_some());
+
+        let changelog = reload_interactor
+            .diff_dataset(None, data_path)
+            .await
+            .unwrap();
+        assert!(changelog.is_empty());
+    }
+}
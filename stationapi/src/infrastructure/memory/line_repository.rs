@@ -0,0 +1,335 @@
+use async_trait::async_trait;
+use std::{collections::BTreeSet, sync::Arc};
+
+use crate::domain::{
+    entity::{line::Line, misc::AsOf},
+    error::DomainError,
+    repository::line_repository::LineRepository,
+};
+
+use super::dataset::{contains_ignore_case, Dataset};
+
+pub struct InMemoryLineRepository {
+    dataset: Arc<Dataset>,
+}
+
+impl InMemoryLineRepository {
+    pub fn new(dataset: Arc<Dataset>) -> Self {
+        Self { dataset }
+    }
+
+    fn has_operating_station(&self, line_cd: u32, as_of: &AsOf) -> bool {
+        self.dataset
+            .stations_by_line_cd(line_cd)
+            .any(|s| self.dataset.is_operating_station(s, Some(as_of)))
+    }
+
+    fn line_by_station_id(&self, station_id: u32) -> Option<Line> {
+        let station = self.dataset.station(station_id)?;
+        let line = self.dataset.line(station.line_cd)?;
+        self.dataset
+            .sst_by_station_cd(station_id)
+            .find(|sst| sst.pass != 1)
+            .map(|sst| {
+                self.dataset
+                    .build_line(line, Some(station), Some(sst.line_group_cd))
+            })
+    }
+
+    // NOTE: SQL版の `GROUP BY` と同じく最初に見つかった行を採用する
+    fn get_by_line_group_id_vec_grouped_by<K: Ord>(
+        &self,
+        line_group_id_vec: &[u32],
+        group_key: impl Fn(u32, u32) -> K,
+    ) -> Vec<Line> {
+        let mut keys = BTreeSet::new();
+
+        line_group_id_vec
+            .iter()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .flat_map(|line_group_cd| self.dataset.sst_by_line_group_cd(*line_group_cd))
+            .filter(|sst| sst.pass != 1)
+            .filter_map(|sst| {
+                let station = self.dataset.station(sst.station_cd)?;
+                if station.e_status != 0 {
+                    return None;
+                }
+                let line = self.dataset.line(station.line_cd)?;
+                if line.e_status != 0 {
+                    return None;
+                }
+                if !keys.insert(group_key(sst.line_group_cd, line.line_cd)) {
+                    return None;
+                }
+                Some(
+                    self.dataset
+                        .build_line(line, Some(station), Some(sst.line_group_cd)),
+                )
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl LineRepository for InMemoryLineRepository {
+    async fn find_by_id(&self, id: u32) -> Result<Option<Line>, DomainError> {
+        let line = self
+            .dataset
+            .line(id)
+            .filter(|l| l.e_status == 0)
+            .map(|l| self.dataset.build_line(l, None, None));
+
+        Ok(line)
+    }
+    async fn find_by_id_as_of(&self, id: u32, as_of: &AsOf) -> Result<Option<Line>, DomainError> {
+        let line = self
+            .dataset
+            .line(id)
+            .filter(|l| self.has_operating_station(l.line_cd, as_of))
+            .map(|l| self.dataset.build_line(l, None, None));
+
+        Ok(line)
+    }
+    async fn find_by_station_id(&self, station_id: u32) -> Result<Option<Line>, DomainError> {
+        Ok(self.line_by_station_id(station_id))
+    }
+    async fn get_by_station_id_vec(
+        &self,
+        station_id_vec: &[u32],
+    ) -> Result<Vec<Line>, DomainError> {
+        let station_ids: BTreeSet<u32> = station_id_vec.iter().copied().collect();
+        let lines = station_ids
+            .into_iter()
+            .filter_map(|station_id| self.line_by_station_id(station_id))
+            .collect();
+
+        Ok(lines)
+    }
+    async fn get_by_ids(&self, ids: &[u32]) -> Result<Vec<Line>, DomainError> {
+        let ids: BTreeSet<u32> = ids.iter().copied().collect();
+        let lines = ids
+            .into_iter()
+            .filter_map(|id| self.dataset.line(id))
+            .filter(|l| l.e_status == 0)
+            .map(|l| self.dataset.build_line(l, None, None))
+            .collect();
+
+        Ok(lines)
+    }
+    async fn get_by_station_group_id(
+        &self,
+        station_group_id: u32,
+    ) -> Result<Vec<Line>, DomainError> {
+        let mut keys = BTreeSet::new();
+        let lines = self
+            .dataset
+            .stations_by_group_cd(station_group_id)
+            .filter(|s| self.dataset.is_active_station(s))
+            .flat_map(|s| {
+                self.dataset
+                    .sst_by_station_cd(s.station_cd)
+                    .filter(|sst| sst.pass != 1)
+                    .map(move |sst| (s, sst.line_group_cd))
+            })
+            .filter(|(s, line_group_cd)| keys.insert((s.station_cd, *line_group_cd)))
+            .filter_map(|(s, line_group_cd)| {
+                let line = self.dataset.line(s.line_cd)?;
+                Some(self.dataset.build_line(line, Some(s), Some(line_group_cd)))
+            })
+            .collect();
+
+        Ok(lines)
+    }
+    async fn get_by_station_group_id_vec(
+        &self,
+        station_group_id_vec: &[u32],
+    ) -> Result<Vec<Line>, DomainError> {
+        let group_ids: BTreeSet<u32> = station_group_id_vec.iter().copied().collect();
+        let lines = self
+            .dataset
+            .stations()
+            .filter(|s| group_ids.contains(&s.station_g_cd))
+            .filter(|s| self.dataset.is_active_station(s))
+            .filter_map(|s| {
+                let line = self.dataset.line(s.line_cd)?;
+                let mut ssts = self.dataset.sst_by_station_cd(s.station_cd).peekable();
+                // NOTE: 停車しない種別しか持たない駅はSQL版と同じく除外される
+                let line_group_cd = match ssts.peek().is_some() {
+                    true => Some(ssts.find(|sst| sst.pass != 1)?.line_group_cd),
+                    false => None,
+                };
+                Some(self.dataset.build_line(line, Some(s), line_group_cd))
+            })
+            .collect();
+
+        Ok(lines)
+    }
+    async fn get_by_line_group_id(&self, line_group_id: u32) -> Result<Vec<Line>, DomainError> {
+        Ok(self.get_by_line_group_id_vec_grouped_by(&[line_group_id], |_, line_cd| line_cd))
+    }
+    async fn get_by_line_group_id_vec(
+        &self,
+        line_group_id_vec: &[u32],
+    ) -> Result<Vec<Line>, DomainError> {
+        Ok(
+            self.get_by_line_group_id_vec_grouped_by(
+                line_group_id_vec,
+                |line_group_cd, line_cd| (line_group_cd, line_cd),
+            ),
+        )
+    }
+    async fn get_by_line_group_id_vec_for_routes(
+        &self,
+        line_group_id_vec: &[u32],
+    ) -> Result<Vec<Line>, DomainError> {
+        Ok(self.get_by_line_group_id_vec_grouped_by(line_group_id_vec, |_, line_cd| line_cd))
+    }
+    async fn get_by_name(
+        &self,
+        line_name: String,
+        limit: Option<u32>,
+    ) -> Result<Vec<Line>, DomainError> {
+        let lines = self
+            .dataset
+            .lines()
+            .filter(|l| l.e_status == 0)
+            .filter(|l| {
+                [
+                    Some(&l.line_name),
+                    l.line_name_r.as_ref(),
+                    Some(&l.line_name_k),
+                    l.line_name_zh.as_ref(),
+                    l.line_name_ko.as_ref(),
+                ]
+                .into_iter()
+                .flatten()
+                .any(|name| contains_ignore_case(name, &line_name))
+            })
+            .take(limit.unwrap_or(1) as usize)
+            .map(|l| self.dataset.build_line(l, None, None))
+            .collect();
+
+        Ok(lines)
+    }
+    async fn get_by_prefecture_id(
+        &self,
+        prefecture_id: u32,
+        as_of: Option<&AsOf>,
+    ) -> Result<Vec<Line>, DomainError> {
+        let line_cds: BTreeSet<u32> = self
+            .dataset
+            .stations_by_pref_cd(prefecture_id)
+            .filter(|s| match as_of {
+                Some(_) => self.dataset.is_operating_station(s, as_of),
+                // NOTE: 路線の運用状況は下で見る
+                None => s.e_status == 0,
+            })
+            .map(|s| s.line_cd)
+            .collect();
+        let mut lines: Vec<_> = line_cds
+            .into_iter()
+            .filter_map(|line_cd| self.dataset.line(line_cd))
+            .filter(|l| as_of.is_some() || l.e_status == 0)
+            .collect();
+        lines.sort_by_key(|l| (l.e_sort, l.line_cd));
+
+        Ok(lines
+            .into_iter()
+            .map(|l| self.dataset.build_line(l, None, None))
+            .collect())
+    }
+    async fn get_by_company_id(
+        &self,
+        company_id: u32,
+        as_of: Option<&AsOf>,
+    ) -> Result<Vec<Line>, DomainError> {
+        let mut lines: Vec<_> = self
+            .dataset
+            .lines()
+            .filter(|l| l.company_cd == company_id)
+            .filter(|l| match as_of {
+                Some(as_of) => self.has_operating_station(l.line_cd, as_of),
+                None => l.e_status == 0,
+            })
+            .collect();
+        lines.sort_by_key(|l| (l.e_sort, l.line_cd));
+
+        Ok(lines
+            .into_iter()
+            .map(|l| self.dataset.build_line(l, None, None))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{path::Path, sync::Arc};
+
+    use super::InMemoryLineRepository;
+    use crate::{
+        domain::repository::line_repository::LineRepository,
+        infrastructure::memory::dataset::Dataset,
+    };
+
+    fn repository() -> InMemoryLineRepository {
+        let dataset = Dataset::load(&Path::new(env!("CARGO_MANIFEST_DIR")).join("../data"))
+            .expect("Failed to load the dataset");
+        InMemoryLineRepository::new(Arc::new(dataset))
+    }
+
+    #[tokio::test]
+    async fn find_by_id() {
+        let repository = repository();
+
+        let line = repository.find_by_id(11302).await.unwrap().unwrap();
+        assert_eq!(line.line_name, "山手線");
+        // 計画中の中央新幹線は返さない
+        assert_eq!(repository.find_by_id(1001).await.unwrap(), None);
+        // 駅から引いたときはline_aliasesの路線名を使う
+        let line = repository
+            .find_by_station_id(2100201)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(line.line_cd, 21002);
+        assert_eq!(line.line_name, "東武スカイツリーライン");
+    }
+
+    #[tokio::test]
+    async fn get_by_name() {
+        let repository = repository();
+
+        let lines = repository
+            .get_by_name("山手".to_string(), Some(10))
+            .await
+            .unwrap();
+        let line_cds: Vec<u32> = lines.iter().map(|l| l.line_cd).collect();
+        assert_eq!(line_cds, vec![11302, 99646]);
+        // 件数を指定しなければ1件
+        let lines = repository
+            .get_by_name("山手".to_string(), None)
+            .await
+            .unwrap();
+        assert_eq!(lines.len(), 1);
+        // 廃止された成田エクスプレスは見つからない
+        let lines = repository
+            .get_by_name("成田エクスプレス".to_string(), Some(10))
+            .await
+            .unwrap();
+        assert!(lines.is_empty());
+    }
+
+    #[tokio::test]
+    async fn get_by_company_id() {
+        let lines = repository().get_by_company_id(2, None).await.unwrap();
+        let line_cds: Vec<u32> = lines.iter().map(|l| l.line_cd).collect();
+        assert_eq!(&line_cds[..3], &[1004, 1005, 1006]);
+        assert!(lines
+            .windows(2)
+            .all(|pair| (pair[0].e_sort, pair[0].line_cd) < (pair[1].e_sort, pair[1].line_cd)));
+        // 廃止された路線は含めない
+        assert!(!line_cds.contains(&11201));
+        assert!(!line_cds.contains(&11328));
+    }
+}
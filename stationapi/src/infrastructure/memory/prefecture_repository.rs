@@ -0,0 +1,36 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use crate::domain::{
+    entity::prefecture::Prefecture, error::DomainError,
+    repository::prefecture_repository::PrefectureRepository,
+};
+
+use super::dataset::Dataset;
+
+pub struct InMemoryPrefectureRepository {
+    dataset: Arc<Dataset>,
+}
+
+impl InMemoryPrefectureRepository {
+    pub fn new(dataset: Arc<Dataset>) -> Self {
+        Self { dataset }
+    }
+}
+
+#[async_trait]
+impl PrefectureRepository for InMemoryPrefectureRepository {
+    async fn find_by_id(&self, id: u32) -> Result<Option<Prefecture>, DomainError> {
+        Ok(self
+            .dataset
+            .prefecture(id)
+            .map(|p| self.dataset.build_prefecture(p)))
+    }
+    async fn get_all(&self) -> Result<Vec<Prefecture>, DomainError> {
+        Ok(self
+            .dataset
+            .prefectures()
+            .map(|p| self.dataset.build_prefecture(p))
+            .collect())
+    }
+}
@@ -0,0 +1,566 @@
+use async_trait::async_trait;
+use std::{collections::BTreeSet, sync::Arc};
+
+use crate::domain::{
+    entity::{
+        misc::{AsOf, BoundingBox, StationFilter, StationIdWithDistance},
+        station::Station,
+    },
+    error::DomainError,
+    repository::station_repository::StationRepository,
+};
+
+use super::dataset::{Dataset, StationRecord};
+
+pub struct InMemoryStationRepository {
+    dataset: Arc<Dataset>,
+}
+
+impl InMemoryStationRepository {
+    pub fn new(dataset: Arc<Dataset>) -> Self {
+        Self { dataset }
+    }
+
+    fn has_train_types(&self, station_cd: u32) -> bool {
+        self.dataset.sst_by_station_cd(station_cd).next().is_some()
+    }
+
+    // NOTE: SQL版と同じくstation_station_typesをLEFT JOINした行数分返す
+    fn stations_with_train_types(&self, station: &StationRecord) -> Vec<Station> {
+        let ssts: Vec<_> = self.dataset.sst_by_station_cd(station.station_cd).collect();
+        if ssts.is_empty() {
+            return self
+                .dataset
+                .build_station(station, None, false)
+                .into_iter()
+                .collect();
+        }
+        ssts.into_iter()
+            .filter_map(|sst| self.dataset.build_station(station, Some(sst), true))
+            .collect()
+    }
+
+    fn get_by_line_id_without_train_types(&self, line_id: u32) -> Vec<Station> {
+        let mut stations: Vec<&StationRecord> = self
+            .dataset
+            .stations_by_line_cd(line_id)
+            .filter(|s| self.dataset.is_active_station(s))
+            .collect();
+        stations.sort_by_key(|s| (s.e_sort, s.station_cd));
+
+        stations
+            .into_iter()
+            .filter_map(|s| self.dataset.build_station(s, None, false))
+            .collect()
+    }
+
+    // NOTE: SQL版の `ORDER BY s.e_sort, s.station_cd` と同じ順にする
+    fn sorted_without_train_types<'a>(
+        &self,
+        stations: impl Iterator<Item = &'a StationRecord>,
+        as_of: Option<&AsOf>,
+    ) -> Vec<Station> {
+        let mut stations: Vec<&StationRecord> = stations
+            .filter(|s| self.dataset.is_operating_station(s, as_of))
+            .collect();
+        stations.sort_by_key(|s| (s.e_sort, s.station_cd));
+
+        stations
+            .into_iter()
+            .filter_map(|s| self.dataset.build_station(s, None, false))
+            .collect()
+    }
+
+    fn get_by_line_group_id_with_train_types(&self, line_group_id: u32) -> Vec<Station> {
+        self.dataset
+            .sst_by_line_group_cd(line_group_id)
+            .filter_map(|sst| {
+                let station = self.dataset.station(sst.station_cd)?;
+                if !self.dataset.is_active_station(station) {
+                    return None;
+                }
+                self.dataset.build_station(station, Some(sst), true)
+            })
+            .collect()
+    }
+
+    fn fetch_has_local_train_types_by_station_id(&self, station_id: u32) -> bool {
+        self.dataset.sst_by_station_cd(station_id).any(|sst| {
+            self.dataset
+                .train_type(sst.type_cd)
+                .is_some_and(|t| matches!(t.kind, 0 | 1) || t.top_priority == 1)
+        })
+    }
+
+    fn get_by_line_id_and_station_id(&self, line_id: u32, station_id: u32) -> Vec<Station> {
+        if !self.fetch_has_local_train_types_by_station_id(station_id) {
+            return self.get_by_line_id_without_train_types(line_id);
+        }
+
+        let Some(line_group_cd) = self
+            .dataset
+            .sst_by_station_cd(station_id)
+            .find(|sst| {
+                self.dataset.train_type(sst.type_cd).is_some_and(|t| {
+                    match t.top_priority == 1 && sst.pass != 1 {
+                        true => true,
+                        false => matches!(t.kind, 0 | 1),
+                    }
+                })
+            })
+            .map(|sst| sst.line_group_cd)
+        else {
+            return vec![];
+        };
+
+        self.dataset
+            .sst_by_line_group_cd(line_group_cd)
+            .filter_map(|sst| {
+                let station = self.dataset.station(sst.station_cd)?;
+                if !self.dataset.is_active_station(station) {
+                    return None;
+                }
+                self.dataset.build_station(station, Some(sst), true)
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl StationRepository for InMemoryStationRepository {
+    async fn find_by_id(&self, id: u32) -> Result<Option<Station>, DomainError> {
+        let Some(station) = self.dataset.station(id) else {
+            return Ok(None);
+        };
+        if !self.dataset.is_active_station(station) {
+            return Ok(None);
+        }
+        let sst = self.dataset.sst_by_station_cd(id).next();
+
+        Ok(self.dataset.build_station(station, sst, sst.is_some()))
+    }
+    async fn get_by_id_vec(&self, ids: &[u32]) -> Result<Vec<Station>, DomainError> {
+        let stations = ids
+            .iter()
+            .filter_map(|id| self.dataset.station(*id))
+            .filter(|s| self.dataset.is_active_station(s))
+            .filter_map(|s| {
+                self.dataset
+                    .build_station(s, None, self.has_train_types(s.station_cd))
+            })
+            .collect();
+
+        Ok(stations)
+    }
+    async fn get_by_line_id(
+        &self,
+        line_id: u32,
+        station_id: Option<u32>,
+    ) -> Result<Vec<Station>, DomainError> {
+        match station_id {
+            Some(station_id) => Ok(self.get_by_line_id_and_station_id(line_id, station_id)),
+            None => Ok(self.get_by_line_id_without_train_types(line_id)),
+        }
+    }
+    async fn get_by_line_id_vec(&self, line_id_vec: &[u32]) -> Result<Vec<Station>, DomainError> {
+        let line_ids: BTreeSet<u32> = line_id_vec.iter().copied().collect();
+        let stations = line_ids
+            .into_iter()
+            .flat_map(|line_id| self.get_by_line_id_without_train_types(line_id))
+            .collect();
+
+        Ok(stations)
+    }
+    async fn get_by_line_id_as_of(
+        &self,
+        line_id: u32,
+        as_of: &AsOf,
+    ) -> Result<Vec<Station>, DomainError> {
+        Ok(self.sorted_without_train_types(self.dataset.stations_by_line_cd(line_id), Some(as_of)))
+    }
+    async fn get_by_station_group_id(
+        &self,
+        station_group_id: u32,
+    ) -> Result<Vec<Station>, DomainError> {
+        let stations = self
+            .dataset
+            .stations_by_group_cd(station_group_id)
+            .filter(|s| self.dataset.is_active_station(s))
+            .flat_map(|s| self.stations_with_train_types(s))
+            .collect();
+
+        Ok(stations)
+    }
+    async fn get_by_station_group_id_vec(
+        &self,
+        station_group_id_vec: &[u32],
+    ) -> Result<Vec<Station>, DomainError> {
+        let group_ids: BTreeSet<u32> = station_group_id_vec.iter().copied().collect();
+        let stations = self
+            .dataset
+            .stations()
+            .filter(|s| group_ids.contains(&s.station_g_cd))
+            .filter(|s| self.dataset.is_active_station(s))
+            .filter_map(|s| {
+                self.dataset
+                    .build_station(s, None, self.has_train_types(s.station_cd))
+            })
+            .collect();
+
+        Ok(stations)
+    }
+    async fn get_by_coordinates(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        limit: Option<u32>,
+    ) -> Result<Vec<Station>, DomainError> {
+        let stations = self
+            .dataset
+            .spatial_index()
+            .nearest_group_representatives(latitude, longitude, limit.unwrap_or(1) as usize)
+            .into_iter()
+            .filter_map(|(point, _)| self.dataset.station(point.station_cd))
+            .filter_map(|s| self.dataset.build_station(s, None, false))
+            .collect();
+
+        Ok(stations)
+    }
+    async fn get_by_radius(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        radius_in_km: f64,
+        filter: &StationFilter,
+        limit: Option<u32>,
+    ) -> Result<Vec<Station>, DomainError> {
+        let stations = self
+            .dataset
+            .spatial_index()
+            .search_within_radius(
+                latitude,
+                longitude,
+                radius_in_km,
+                filter,
+                limit.map(|limit| limit as usize),
+            )
+            .into_iter()
+            .filter_map(|point| self.dataset.station(point.station_cd))
+            .filter_map(|s| self.dataset.build_station(s, None, false))
+            .collect();
+
+        Ok(stations)
+    }
+    async fn get_by_bounding_box(
+        &self,
+        bounding_box: &BoundingBox,
+        filter: &StationFilter,
+        limit: Option<u32>,
+    ) -> Result<Vec<Station>, DomainError> {
+        let stations = self
+            .dataset
+            .spatial_index()
+            .search_within_bounding_box(bounding_box, filter, limit.map(|limit| limit as usize))
+            .into_iter()
+            .filter_map(|point| self.dataset.station(point.station_cd))
+            .filter_map(|s| self.dataset.build_station(s, None, false))
+            .collect();
+
+        Ok(stations)
+    }
+    async fn get_by_name(
+        &self,
+        station_name: String,
+        limit: Option<u32>,
+        from_station_group_id: Option<u32>,
+    ) -> Result<Vec<Station>, DomainError> {
+        let from_stations: Vec<&StationRecord> = from_station_group_id
+            .map(|id| {
+                self.dataset
+                    .stations_by_group_cd(id)
+                    .filter(|s| s.e_status == 0)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let mut group_ids = BTreeSet::new();
+        let mut stations = vec![];
+
+        // 一致度の高い駅から順に見ていく
+        let candidates = self
+            .dataset
+            .name_index()
+            .search(&station_name)
+            .into_iter()
+            .filter_map(|station_cd| self.dataset.station(station_cd));
+
+        for station in candidates {
+            if stations.len() >= limit.unwrap_or(1) as usize {
+                break;
+            }
+            if group_ids.contains(&station.station_g_cd) {
+                continue;
+            }
+            if !self.dataset.is_active_station(station) {
+                continue;
+            }
+
+            let dst_ssts: Vec<_> = self.dataset.sst_by_station_cd(station.station_cd).collect();
+            let has_train_types = !dst_ssts.is_empty();
+
+            let from_rows: Vec<(Option<&StationRecord>, Option<u32>)> =
+                match from_stations.is_empty() {
+                    true => vec![(None, None)],
+                    false => from_stations
+                        .iter()
+                        .flat_map(|fs| {
+                            let from_ssts: Vec<_> = self
+                                .dataset
+                                .sst_by_station_cd(fs.station_cd)
+                                .map(|sst| Some(sst.line_group_cd))
+                                .collect();
+                            match from_ssts.is_empty() {
+                                true => vec![(Some(*fs), None)],
+                                false => from_ssts.into_iter().map(|lg| (Some(*fs), lg)).collect(),
+                            }
+                        })
+                        .collect(),
+                };
+            let dst_rows: Vec<_> = match dst_ssts.is_empty() {
+                true => vec![None],
+                false => dst_ssts.into_iter().map(Some).collect(),
+            };
+
+            let matched_sst = from_rows.iter().find_map(|(fs, from_line_group_cd)| {
+                dst_rows.iter().find_map(|dst_sst| {
+                    let matched = match (from_line_group_cd, dst_sst) {
+                        (Some(from_line_group_cd), Some(dst_sst)) => {
+                            *from_line_group_cd == dst_sst.line_group_cd && dst_sst.pass != 1
+                        }
+                        _ => fs.is_none_or(|fs| fs.line_cd == station.line_cd),
+                    };
+                    matched.then_some(*dst_sst)
+                })
+            });
+            let Some(dst_sst) = matched_sst else {
+                continue;
+            };
+
+            if let Some(station) = self
+                .dataset
+                .build_station(station, dst_sst, has_train_types)
+            {
+                group_ids.insert(station.station_g_cd);
+                stations.push(station);
+            }
+        }
+
+        Ok(stations)
+    }
+    async fn get_by_prefecture_id(
+        &self,
+        prefecture_id: u32,
+        as_of: Option<&AsOf>,
+    ) -> Result<Vec<Station>, DomainError> {
+        Ok(self.sorted_without_train_types(self.dataset.stations_by_pref_cd(prefecture_id), as_of))
+    }
+    async fn get_by_company_id(
+        &self,
+        company_id: u32,
+        as_of: Option<&AsOf>,
+    ) -> Result<Vec<Station>, DomainError> {
+        let stations = self
+            .dataset
+            .lines()
+            .filter(|l| l.company_cd == company_id)
+            .flat_map(|l| self.dataset.stations_by_line_cd(l.line_cd));
+
+        Ok(self.sorted_without_train_types(stations, as_of))
+    }
+    async fn get_by_line_group_id(&self, line_group_id: u32) -> Result<Vec<Station>, DomainError> {
+        Ok(self.get_by_line_group_id_with_train_types(line_group_id))
+    }
+    async fn get_by_line_group_id_vec(
+        &self,
+        line_group_id_vec: &[u32],
+    ) -> Result<Vec<Station>, DomainError> {
+        let line_group_ids: BTreeSet<u32> = line_group_id_vec.iter().copied().collect();
+        let stations = line_group_ids
+            .into_iter()
+            .flat_map(|line_group_id| self.get_by_line_group_id_with_train_types(line_group_id))
+            .collect();
+
+        Ok(stations)
+    }
+    async fn get_station_id_and_distance_by_coordinates(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        line_id: Option<u32>,
+    ) -> Result<StationIdWithDistance, DomainError> {
+        let Some((point, distance)) = self
+            .dataset
+            .spatial_index()
+            .nearest_station(latitude, longitude, line_id)
+        else {
+            return Err(DomainError::NotFound {
+                entity_type: "Station",
+                entity_id: format!(
+                    "latitude: {}, longitude: {}, line_id: {:?}",
+                    latitude, longitude, line_id
+                ),
+            });
+        };
+
+        Ok(StationIdWithDistance {
+            station_id: point.station_cd,
+            distance,
+            average_distance: point.average_distance,
+        })
+    }
+    async fn get_route_stops(
+        &self,
+        from_station_id: u32,
+        to_station_id: u32,
+    ) -> Result<Vec<Station>, DomainError> {
+        let stopping_line_group_cds = |station_group_id: u32| -> BTreeSet<u32> {
+            self.dataset
+                .stations_by_group_cd(station_group_id)
+                .filter(|s| s.e_status == 0)
+                .flat_map(|s| self.dataset.sst_by_station_cd(s.station_cd))
+                .filter(|sst| sst.pass != 1)
+                .map(|sst| sst.line_group_cd)
+                .collect()
+        };
+        let line_group_cds: BTreeSet<u32> = stopping_line_group_cds(from_station_id)
+            .intersection(&stopping_line_group_cds(to_station_id))
+            .copied()
+            .collect();
+
+        let line_cds = |station_group_id: u32| -> BTreeSet<u32> {
+            self.dataset
+                .stations_by_group_cd(station_group_id)
+                .filter(|s| s.e_status == 0)
+                .map(|s| s.line_cd)
+                .collect()
+        };
+        let shared_line_cds: BTreeSet<u32> = line_cds(from_station_id)
+            .intersection(&line_cds(to_station_id))
+            .copied()
+            .collect();
+
+        let mut local_stations: Vec<&StationRecord> = shared_line_cds
+            .iter()
+            .flat_map(|line_cd| self.dataset.stations_by_line_cd(*line_cd))
+            .filter(|s| self.dataset.is_active_station(s))
+            .filter(|s| {
+                !self
+                    .dataset
+                    .sst_by_station_cd(s.station_cd)
+                    .any(|sst| line_group_cds.contains(&sst.line_group_cd))
+            })
+            .collect();
+        local_stations.sort_by_key(|s| (s.e_sort, s.station_cd));
+
+        let mut stops: Vec<Station> = local_stations
+            .into_iter()
+            .filter_map(|s| self.dataset.build_station(s, None, false))
+            .collect();
+
+        let mut typed_stops: Vec<Station> = self
+            .dataset
+            .station_station_types()
+            .filter(|sst| line_group_cds.contains(&sst.line_group_cd))
+            .filter_map(|sst| {
+                let station = self.dataset.station(sst.station_cd)?;
+                if !self.dataset.is_active_station(station) {
+                    return None;
+                }
+                self.dataset.build_station(station, Some(sst), true)
+            })
+            .collect();
+
+        stops.append(&mut typed_stops);
+
+        Ok(stops)
+    }
+    async fn get_all_stops(&self) -> Result<Vec<Station>, DomainError> {
+        let mut stations: Vec<&StationRecord> = self
+            .dataset
+            .stations()
+            .filter(|s| self.dataset.is_active_station(s))
+            .collect();
+        stations.sort_by_key(|s| (s.line_cd, s.e_sort, s.station_cd));
+
+        let stops = stations
+            .into_iter()
+            .flat_map(|station| {
+                let ssts: Vec<_> = self
+                    .dataset
+                    .sst_by_station_cd(station.station_cd)
+                    .filter(|sst| sst.pass != 1)
+                    .collect();
+                if ssts.is_empty() {
+                    return self
+                        .dataset
+                        .build_station(station, None, false)
+                        .into_iter()
+                        .collect::<Vec<_>>();
+                }
+                ssts.into_iter()
+                    .filter_map(|sst| self.dataset.build_station(station, Some(sst), true))
+                    .collect()
+            })
+            .collect();
+
+        Ok(stops)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeSet, path::Path, sync::Arc};
+
+    use super::InMemoryStationRepository;
+    use crate::{
+        domain::repository::station_repository::StationRepository,
+        infrastructure::memory::dataset::Dataset,
+    };
+
+    fn repository() -> InMemoryStationRepository {
+        let dataset = Dataset::load(&Path::new(env!("CARGO_MANIFEST_DIR")).join("../data"))
+            .expect("Failed to load the dataset");
+        InMemoryStationRepository::new(Arc::new(dataset))
+    }
+
+    #[tokio::test]
+    async fn find_by_id() {
+        let repository = repository();
+
+        let station = repository.find_by_id(1130208).await.unwrap().unwrap();
+        assert_eq!(station.station_name, "新宿");
+        // 廃駅と、廃止・計画中の路線の駅は返さない
+        assert_eq!(repository.find_by_id(1110112).await.unwrap(), None);
+        assert_eq!(repository.find_by_id(1132801).await.unwrap(), None);
+        // line_aliasesがあれば路線名を差し替える
+        let station = repository.find_by_id(2100201).await.unwrap().unwrap();
+        assert_eq!(station.line_name.as_deref(), Some("東武スカイツリーライン"));
+    }
+
+    #[tokio::test]
+    async fn get_by_line_id() {
+        let stations = repository().get_by_line_id(11322, None).await.unwrap();
+        let station_cds: Vec<u32> = stations.iter().map(|s| s.station_cd).collect();
+        // 後から開業した西大宮は駅コードではなくe_sort順で日進の次に来る
+        assert_eq!(&station_cds[..4], &[1132201, 1132202, 1132211, 1132203]);
+        assert!(stations.iter().all(|s| s.e_status == 0));
+    }
+
+    #[tokio::test]
+    async fn get_by_station_group_id() {
+        // 那須塩原の山形・秋田新幹線の駅は廃止扱い
+        let stations = repository().get_by_station_group_id(1131932).await.unwrap();
+        let station_cds: BTreeSet<u32> = stations.iter().map(|s| s.station_cd).collect();
+        assert_eq!(station_cds, BTreeSet::from([100406, 1131932]));
+    }
+}
@@ -0,0 +1,325 @@
+use async_trait::async_trait;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+};
+
+use crate::domain::{
+    entity::{misc::TrainTypeFilter, train_type::TrainType},
+    error::DomainError,
+    repository::train_type_repository::TrainTypeRepository,
+};
+
+use super::dataset::{contains_ignore_case, Dataset, StationStationTypeRecord, TypeRecord};
+
+pub struct InMemoryTrainTypeRepository {
+    dataset: Arc<Dataset>,
+}
+
+impl InMemoryTrainTypeRepository {
+    pub fn new(dataset: Arc<Dataset>) -> Self {
+        Self { dataset }
+    }
+
+    fn build_train_type(&self, sst: &StationStationTypeRecord) -> Option<TrainType> {
+        let train_type = self.dataset.train_type(sst.type_cd)?;
+
+        Some(TrainType::new(
+            sst.id,
+            sst.station_cd,
+            sst.type_cd,
+            sst.line_group_cd,
+            sst.pass,
+            train_type.type_name.clone(),
+            train_type.type_name_k.clone(),
+            train_type.type_name_r.clone(),
+            train_type.type_name_zh.clone(),
+            train_type.type_name_ko.clone(),
+            train_type.color.clone(),
+            train_type.direction,
+            train_type.kind,
+        ))
+    }
+
+    fn matches_name(train_type: &TypeRecord, name: &str) -> bool {
+        [
+            Some(&train_type.type_name),
+            Some(&train_type.type_name_k),
+            train_type.type_name_r.as_ref(),
+            train_type.type_name_zh.as_ref(),
+            train_type.type_name_ko.as_ref(),
+        ]
+        .into_iter()
+        .flatten()
+        .any(|value| contains_ignore_case(value, name))
+    }
+
+    fn stopping_train_types(&self, station_cd: u32) -> Vec<TrainType> {
+        if !self.is_active_station(station_cd) {
+            return vec![];
+        }
+
+        self.dataset
+            .sst_by_station_cd(station_cd)
+            .filter(|sst| sst.pass != 1)
+            .filter_map(|sst| self.build_train_type(sst))
+            .collect()
+    }
+
+    fn is_active_station(&self, station_cd: u32) -> bool {
+        self.dataset
+            .station(station_cd)
+            .is_some_and(|s| s.e_status == 0)
+    }
+}
+
+#[async_trait]
+impl TrainTypeRepository for InMemoryTrainTypeRepository {
+    async fn get_by_line_group_id(
+        &self,
+        line_group_id: u32,
+    ) -> Result<Vec<TrainType>, DomainError> {
+        let mut train_types: Vec<TrainType> = self
+            .dataset
+            .sst_by_line_group_cd(line_group_id)
+            .filter_map(|sst| self.build_train_type(sst))
+            .collect();
+        train_types.sort_by_key(|tt| (tt.kind, tt.id));
+
+        Ok(train_types)
+    }
+    async fn get_by_station_id(&self, station_id: u32) -> Result<Vec<TrainType>, DomainError> {
+        Ok(self.stopping_train_types(station_id))
+    }
+    async fn get_all_by_station_id_vec(
+        &self,
+        station_id_vec: &[u32],
+    ) -> Result<Vec<TrainType>, DomainError> {
+        let station_ids: BTreeSet<u32> = station_id_vec.iter().copied().collect();
+        let train_types = station_ids
+            .into_iter()
+            .flat_map(|station_id| self.stopping_train_types(station_id))
+            .collect();
+
+        Ok(train_types)
+    }
+    async fn find_by_line_group_id_and_line_id(
+        &self,
+        line_group_id: u32,
+        line_id: u32,
+    ) -> Result<Option<TrainType>, DomainError> {
+        let train_type = self
+            .dataset
+            .sst_by_line_group_cd(line_group_id)
+            .find(|sst| {
+                self.dataset
+                    .station(sst.station_cd)
+                    .is_some_and(|s| s.line_cd == line_id && s.e_status == 0)
+            })
+            .and_then(|sst| self.build_train_type(sst));
+
+        Ok(train_type)
+    }
+    async fn get_by_line_group_id_and_line_id_vec(
+        &self,
+        line_group_id_and_line_id_vec: &[(u32, u32)],
+    ) -> Result<BTreeMap<(u32, u32), TrainType>, DomainError> {
+        let keys: BTreeSet<(u32, u32)> = line_group_id_and_line_id_vec.iter().copied().collect();
+        let line_group_ids: BTreeSet<u32> = keys
+            .iter()
+            .map(|(line_group_cd, _)| *line_group_cd)
+            .collect();
+
+        let mut found = BTreeSet::new();
+        let mut train_types = BTreeMap::new();
+        for line_group_id in line_group_ids {
+            for sst in self.dataset.sst_by_line_group_cd(line_group_id) {
+                let Some(station) = self
+                    .dataset
+                    .station(sst.station_cd)
+                    .filter(|s| s.e_status == 0)
+                else {
+                    continue;
+                };
+                let key = (line_group_id, station.line_cd);
+                // NOTE: find_by_line_group_id_and_line_idと同じく系統内で最初の駅の種別を使う
+                if !keys.contains(&key) || !found.insert(key) {
+                    continue;
+                }
+                if let Some(train_type) = self.build_train_type(sst) {
+                    train_types.insert(key, train_type);
+                }
+            }
+        }
+
+        Ok(train_types)
+    }
+    async fn get_by_station_id_vec(
+        &self,
+        station_id_vec: &[u32],
+        line_group_id: Option<u32>,
+    ) -> Result<Vec<TrainType>, DomainError> {
+        let Some(line_group_id) = line_group_id else {
+            return Ok(vec![]);
+        };
+        let station_ids: BTreeSet<u32> = station_id_vec
+            .iter()
+            .copied()
+            .filter(|id| self.is_active_station(*id))
+            .collect();
+
+        let train_types = self
+            .dataset
+            .sst_by_line_group_cd(line_group_id)
+            .filter(|sst| station_ids.contains(&sst.station_cd) && sst.pass != 1)
+            .filter_map(|sst| self.build_train_type(sst))
+            .collect();
+
+        Ok(train_types)
+    }
+    async fn get_types_by_station_id_vec(
+        &self,
+        station_id_vec: &[u32],
+        line_group_id: Option<u32>,
+    ) -> Result<Vec<TrainType>, DomainError> {
+        let mut train_types = self
+            .get_by_station_id_vec(station_id_vec, line_group_id)
+            .await?;
+        train_types.sort_by_key(|tt| (tt.kind, tt.id));
+
+        Ok(train_types)
+    }
+    async fn get_by_line_group_id_vec(
+        &self,
+        line_group_id_vec: &[u32],
+    ) -> Result<Vec<TrainType>, DomainError> {
+        let line_group_ids: BTreeSet<u32> = line_group_id_vec.iter().copied().collect();
+        let train_types = self
+            .dataset
+            .station_station_types()
+            .filter(|sst| line_group_ids.contains(&sst.line_group_cd) && sst.pass != 1)
+            .filter_map(|sst| self.build_train_type(sst))
+            .collect();
+
+        Ok(train_types)
+    }
+    async fn find_by_type_cd(&self, type_cd: u32) -> Result<Option<TrainType>, DomainError> {
+        let train_type = self.dataset.train_type(type_cd).map(|train_type| {
+            TrainType::new(
+                train_type.id,
+                0,
+                train_type.type_cd,
+                0,
+                0,
+                train_type.type_name.clone(),
+                train_type.type_name_k.clone(),
+                train_type.type_name_r.clone(),
+                train_type.type_name_zh.clone(),
+                train_type.type_name_ko.clone(),
+                train_type.color.clone(),
+                train_type.direction,
+                train_type.kind,
+            )
+        });
+
+        Ok(train_type)
+    }
+    async fn get_by_filter(&self, filter: &TrainTypeFilter) -> Result<Vec<TrainType>, DomainError> {
+        // (type_cd, line_group_cd) -> (最初に停車する駅の行, 停車する路線)
+        let mut services: BTreeMap<(u32, u32), (&StationStationTypeRecord, BTreeSet<u32>)> =
+            BTreeMap::new();
+        for sst in self.dataset.station_station_types() {
+            if sst.pass == 1 || filter.type_cd.is_some_and(|type_cd| type_cd != sst.type_cd) {
+                continue;
+            }
+            let Some(station) = self
+                .dataset
+                .station(sst.station_cd)
+                .filter(|s| s.e_status == 0)
+            else {
+                continue;
+            };
+            if self
+                .dataset
+                .line(station.line_cd)
+                .is_none_or(|l| l.e_status != 0)
+            {
+                continue;
+            }
+            services
+                .entry((sst.type_cd, sst.line_group_cd))
+                .or_insert_with(|| (sst, BTreeSet::new()))
+                .1
+                .insert(station.line_cd);
+        }
+
+        let train_types = services
+            .into_values()
+            .filter(|(sst, line_cds)| {
+                let Some(train_type) = self.dataset.train_type(sst.type_cd) else {
+                    return false;
+                };
+                filter.kind.is_none_or(|kind| kind == train_type.kind)
+                    && filter
+                        .name
+                        .as_deref()
+                        .is_none_or(|name| Self::matches_name(train_type, name))
+                    && filter
+                        .line_cd
+                        .is_none_or(|line_cd| line_cds.contains(&line_cd))
+                    && filter.company_cd.is_none_or(|company_cd| {
+                        line_cds.iter().any(|line_cd| {
+                            self.dataset
+                                .line(*line_cd)
+                                .is_some_and(|l| l.company_cd == company_cd)
+                        })
+                    })
+            })
+            .filter_map(|(sst, _)| self.build_train_type(sst))
+            .collect();
+
+        Ok(train_types)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{path::Path, sync::Arc};
+
+    use super::InMemoryTrainTypeRepository;
+    use crate::{
+        domain::repository::train_type_repository::TrainTypeRepository,
+        infrastructure::memory::dataset::Dataset,
+    };
+
+    #[tokio::test]
+    async fn get_by_line_group_id_and_line_id_vec() {
+        let dataset = Dataset::load(&Path::new(env!("CARGO_MANIFEST_DIR")).join("../data"))
+            .expect("Failed to load the dataset");
+        let repository = InMemoryTrainTypeRepository::new(Arc::new(dataset));
+
+        // 東京駅(東海道新幹線)に停まる系統と、その系統が走る路線の組
+        let mut keys = vec![];
+        for tt in repository.get_by_station_id(100201).await.unwrap() {
+            for line_cd in [1002, 1003, 11302] {
+                keys.push((tt.line_group_cd, line_cd));
+            }
+        }
+        let train_types = repository
+            .get_by_line_group_id_and_line_id_vec(&keys)
+            .await
+            .unwrap();
+        assert!(!train_types.is_empty());
+
+        for (line_group_cd, line_cd) in keys {
+            let expected = repository
+                .find_by_line_group_id_and_line_id(line_group_cd, line_cd)
+                .await
+                .unwrap();
+            assert_eq!(
+                train_types.get(&(line_group_cd, line_cd)),
+                expected.as_ref()
+            );
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use async_trait::async_trait;
+use sqlx::{MySql, MySqlConnection, Pool};
+use std::sync::Arc;
+
+use crate::domain::{
+    entity::prefecture::Prefecture, error::DomainError,
+    repository::prefecture_repository::PrefectureRepository,
+};
+
+#[derive(sqlx::FromRow, Clone)]
+pub struct PrefectureRow {
+    pub pref_cd: u32,
+    pub pref_name: String,
+    pub pref_name_k: String,
+    pub pref_name_r: String,
+    pub pref_name_zh: String,
+    pub pref_name_ko: String,
+}
+
+impl From<PrefectureRow> for Prefecture {
+    fn from(row: PrefectureRow) -> Self {
+        Self {
+            pref_cd: row.pref_cd,
+            pref_name: row.pref_name,
+            pref_name_k: row.pref_name_k,
+            pref_name_r: row.pref_name_r,
+            pref_name_zh: row.pref_name_zh,
+            pref_name_ko: row.pref_name_ko,
+        }
+    }
+}
+
+pub struct MyPrefectureRepository {
+    pool: Arc<Pool<MySql>>,
+}
+
+impl MyPrefectureRepository {
+    pub fn new(pool: Arc<Pool<MySql>>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl PrefectureRepository for MyPrefectureRepository {
+    async fn find_by_id(&self, id: u32) -> Result<Option<Prefecture>, DomainError> {
+        let mut conn = self.pool.acquire().await?;
+        InternalPrefectureRepository::find_by_id(id, &mut conn).await
+    }
+    async fn get_all(&self) -> Result<Vec<Prefecture>, DomainError> {
+        let mut conn = self.pool.acquire().await?;
+        InternalPrefectureRepository::get_all(&mut conn).await
+    }
+}
+
+pub struct InternalPrefectureRepository {}
+
+impl InternalPrefectureRepository {
+    async fn find_by_id(
+        id: u32,
+        conn: &mut MySqlConnection,
+    ) -> Result<Option<Prefecture>, DomainError> {
+        let row =
+            sqlx::query_as::<_, PrefectureRow>("SELECT * FROM `prefectures` WHERE pref_cd = ?")
+                .bind(id)
+                .fetch_optional(conn)
+                .await?;
+
+        Ok(row.map(|row| row.into()))
+    }
+
+    async fn get_all(conn: &mut MySqlConnection) -> Result<Vec<Prefecture>, DomainError> {
+        let rows =
+            sqlx::query_as::<_, PrefectureRow>("SELECT * FROM `prefectures` ORDER BY pref_cd")
+                .fetch_all(conn)
+                .await?;
+
+        Ok(rows.into_iter().map(|row| row.into()).collect())
+    }
+}
@@ -0,0 +1,508 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use crate::domain::entity::misc::{distance_in_km, BoundingBox, StationFilter, EARTH_RADIUS_IN_KM};
+
+// 緯度方向で約5.5km四方のセルに分割する
+const CELL_SIZE_IN_DEGREES: f64 = 0.05;
+// NOTE: acosの丸め誤差で境界上の駅を取りこぼさないよう、探索範囲を少し広げる
+const BOUNDING_BOX_MARGIN_IN_DEGREES: f64 = 1e-5;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StationPoint {
+    pub station_cd: u32,
+    pub station_g_cd: u32,
+    pub line_cd: u32,
+    pub lat: f64,
+    pub lon: f64,
+    pub e_status: u32,
+    // linesからJOIN
+    pub company_cd: u32,
+    pub line_type: u32,
+    pub average_distance: f64,
+}
+
+impl StationPoint {
+    fn matches(&self, filter: &StationFilter) -> bool {
+        self.e_status == filter.e_status
+            && filter
+                .line_type
+                .is_none_or(|line_type| line_type == self.line_type)
+            && filter
+                .company_cd
+                .is_none_or(|company_cd| company_cd == self.company_cd)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct SpatialIndex {
+    cells: HashMap<(i64, i64), Vec<StationPoint>>,
+    min_cell: (i64, i64),
+    max_cell: (i64, i64),
+}
+
+impl SpatialIndex {
+    pub fn new(points: impl IntoIterator<Item = StationPoint>) -> Self {
+        let mut index = Self::default();
+        for point in points {
+            let cell = cell_of(point.lat, point.lon);
+            if index.cells.is_empty() {
+                index.min_cell = cell;
+                index.max_cell = cell;
+            }
+            index.min_cell = (index.min_cell.0.min(cell.0), index.min_cell.1.min(cell.1));
+            index.max_cell = (index.max_cell.0.max(cell.0), index.max_cell.1.max(cell.1));
+            index.cells.entry(cell).or_default().push(point);
+        }
+        index
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    // SQL版の `ORDER BY distance LIMIT ?` と同じ順序で返す
+    pub fn nearest(&self, lat: f64, lon: f64, limit: usize) -> Vec<(StationPoint, f64)> {
+        if limit == 0 || self.is_empty() {
+            return vec![];
+        }
+
+        // 近いセルから順に候補を集め、limit件目の距離を探索半径とする
+        let center = cell_of(lat, lon);
+        let (min_ring, max_ring) = self.ring_range(center);
+
+        let mut distances: Vec<f64> = vec![];
+        for ring in min_ring..=max_ring {
+            distances.extend(
+                self.ring_cells(center, ring)
+                    .filter_map(|cell| self.cells.get(&cell))
+                    .flatten()
+                    .map(|point| distance_in_km(lat, lon, point.lat, point.lon)),
+            );
+            if distances.len() >= limit {
+                break;
+            }
+        }
+        distances.sort_by(|a, b| a.total_cmp(b));
+
+        let Some(radius) = distances.get(limit - 1).or(distances.last()) else {
+            return vec![];
+        };
+
+        let mut points = self.within_radius(lat, lon, *radius);
+        points.truncate(limit);
+        points
+    }
+
+    pub fn within_radius(&self, lat: f64, lon: f64, radius_in_km: f64) -> Vec<(StationPoint, f64)> {
+        let ((min_lat, min_lon), (max_lat, max_lon)) = bounding_box(lat, lon, radius_in_km);
+        let (min_row, min_col) = cell_of(min_lat, min_lon);
+        let (max_row, max_col) = cell_of(max_lat, max_lon);
+
+        let cell_count = (max_row - min_row + 1).saturating_mul(max_col - min_col + 1);
+        let candidates: Vec<&StationPoint> = if cell_count > self.cells.len() as i64 {
+            self.cells.values().flatten().collect()
+        } else {
+            (min_row..=max_row)
+                .flat_map(|row| (min_col..=max_col).map(move |col| (row, col)))
+                .filter_map(|cell| self.cells.get(&cell))
+                .flatten()
+                .collect()
+        };
+
+        let mut points: Vec<(StationPoint, f64)> = candidates
+            .into_iter()
+            .map(|point| (*point, distance_in_km(lat, lon, point.lat, point.lon)))
+            .filter(|(_, distance)| *distance <= radius_in_km)
+            .collect();
+        points.sort_by(|(a, a_distance), (b, b_distance)| {
+            a_distance
+                .total_cmp(b_distance)
+                .then(a.station_cd.cmp(&b.station_cd))
+        });
+
+        points
+    }
+
+    pub fn within_bounding_box(&self, bounding_box: &BoundingBox) -> Vec<StationPoint> {
+        let (min_row, min_col) = cell_of(bounding_box.min_latitude, bounding_box.min_longitude);
+        let (max_row, max_col) = cell_of(bounding_box.max_latitude, bounding_box.max_longitude);
+        if min_row > max_row || min_col > max_col {
+            return vec![];
+        }
+
+        let cell_count = (max_row - min_row + 1).saturating_mul(max_col - min_col + 1);
+        let candidates: Vec<&StationPoint> = if cell_count > self.cells.len() as i64 {
+            self.cells.values().flatten().collect()
+        } else {
+            (min_row..=max_row)
+                .flat_map(|row| (min_col..=max_col).map(move |col| (row, col)))
+                .filter_map(|cell| self.cells.get(&cell))
+                .flatten()
+                .collect()
+        };
+
+        let mut points: Vec<StationPoint> = candidates
+            .into_iter()
+            .filter(|point| bounding_box.contains(point.lat, point.lon))
+            .copied()
+            .collect();
+        points.sort_by_key(|point| point.station_cd);
+
+        points
+    }
+
+    // 中心のセルから見て、点が存在しうる範囲のリングの番号
+    fn ring_range(&self, center: (i64, i64)) -> (i64, i64) {
+        let min_ring = [
+            self.min_cell.0 - center.0,
+            center.0 - self.max_cell.0,
+            self.min_cell.1 - center.1,
+            center.1 - self.max_cell.1,
+            0,
+        ]
+        .into_iter()
+        .max()
+        .unwrap_or(0);
+        let max_ring = [
+            center.0 - self.min_cell.0,
+            self.max_cell.0 - center.0,
+            center.1 - self.min_cell.1,
+            self.max_cell.1 - center.1,
+        ]
+        .into_iter()
+        .max()
+        .unwrap_or(0)
+        .max(min_ring);
+
+        (min_ring, max_ring)
+    }
+
+    // 中心からring個離れたセルのうち、点が存在しうる範囲のもの
+    fn ring_cells(&self, center: (i64, i64), ring: i64) -> impl Iterator<Item = (i64, i64)> {
+        let (row, col) = center;
+        let (min_row, max_row) = (
+            (row - ring).max(self.min_cell.0),
+            (row + ring).min(self.max_cell.0),
+        );
+        let (min_col, max_col) = (
+            (col - ring).max(self.min_cell.1),
+            (col + ring).min(self.max_cell.1),
+        );
+
+        let horizontal = [row - ring, row + ring]
+            .into_iter()
+            .filter(move |r| (min_row..=max_row).contains(r))
+            .flat_map(move |r| (min_col..=max_col).map(move |c| (r, c)));
+        let vertical = [col - ring, col + ring]
+            .into_iter()
+            .filter(move |c| (min_col..=max_col).contains(c))
+            .flat_map(move |c| {
+                ((row - ring + 1).max(min_row)..=(row + ring - 1).min(max_row)).map(move |r| (r, c))
+            });
+
+        horizontal
+            .chain(vertical)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct StationSpatialIndex {
+    all_stations: SpatialIndex,
+    stations: SpatialIndex,
+    group_representatives: SpatialIndex,
+    stations_by_line_cd: HashMap<u32, SpatialIndex>,
+}
+
+impl StationSpatialIndex {
+    pub fn new(points: Vec<StationPoint>) -> Self {
+        let active_points: Vec<StationPoint> = points
+            .iter()
+            .filter(|point| point.e_status == 0)
+            .copied()
+            .collect();
+
+        let mut points_by_line_cd: HashMap<u32, Vec<StationPoint>> = HashMap::new();
+        for point in active_points.iter() {
+            points_by_line_cd
+                .entry(point.line_cd)
+                .or_default()
+                .push(*point);
+        }
+
+        Self {
+            all_stations: SpatialIndex::new(points),
+            group_representatives: SpatialIndex::new(
+                active_points
+                    .iter()
+                    .filter(|point| point.station_cd == point.station_g_cd)
+                    .copied(),
+            ),
+            stations: SpatialIndex::new(active_points),
+            stations_by_line_cd: points_by_line_cd
+                .into_iter()
+                .map(|(line_cd, points)| (line_cd, SpatialIndex::new(points)))
+                .collect(),
+        }
+    }
+
+    pub fn nearest_group_representatives(
+        &self,
+        lat: f64,
+        lon: f64,
+        limit: usize,
+    ) -> Vec<(StationPoint, f64)> {
+        self.group_representatives.nearest(lat, lon, limit)
+    }
+
+    pub fn nearest_station(
+        &self,
+        lat: f64,
+        lon: f64,
+        line_cd: Option<u32>,
+    ) -> Option<(StationPoint, f64)> {
+        let index = match line_cd {
+            Some(line_cd) => self.stations_by_line_cd.get(&line_cd)?,
+            None => &self.stations,
+        };
+        index.nearest(lat, lon, 1).into_iter().next()
+    }
+
+    // 近い順に、駅グループごとに1駅ずつ返す
+    pub fn search_within_radius(
+        &self,
+        lat: f64,
+        lon: f64,
+        radius_in_km: f64,
+        filter: &StationFilter,
+        limit: Option<usize>,
+    ) -> Vec<StationPoint> {
+        let points = self
+            .all_stations
+            .within_radius(lat, lon, radius_in_km)
+            .into_iter()
+            .map(|(point, _)| point);
+
+        pick_group_representatives(points, filter, limit)
+    }
+
+    // station_g_cd順に、駅グループごとに1駅ずつ返す
+    pub fn search_within_bounding_box(
+        &self,
+        bounding_box: &BoundingBox,
+        filter: &StationFilter,
+        limit: Option<usize>,
+    ) -> Vec<StationPoint> {
+        let mut points = self.all_stations.within_bounding_box(bounding_box);
+        points.sort_by_key(|point| (point.station_g_cd, point.station_cd));
+
+        pick_group_representatives(points.into_iter(), filter, limit)
+    }
+}
+
+// NOTE: 条件に合う駅が代表駅(station_cd = station_g_cd)であればそれを優先し、
+// そうでなければ最初に見つかった駅をそのグループの駅とする
+fn pick_group_representatives(
+    points: impl Iterator<Item = StationPoint>,
+    filter: &StationFilter,
+    limit: Option<usize>,
+) -> Vec<StationPoint> {
+    let points: Vec<StationPoint> = points.filter(|point| point.matches(filter)).collect();
+    let representative_group_cds: HashSet<u32> = points
+        .iter()
+        .filter(|point| point.station_cd == point.station_g_cd)
+        .map(|point| point.station_g_cd)
+        .collect();
+
+    let mut picked_group_cds: HashSet<u32> = HashSet::new();
+    points
+        .into_iter()
+        .filter(|point| {
+            !representative_group_cds.contains(&point.station_g_cd)
+                || point.station_cd == point.station_g_cd
+        })
+        .filter(|point| picked_group_cds.insert(point.station_g_cd))
+        .take(limit.unwrap_or(usize::MAX))
+        .collect()
+}
+
+fn cell_of(lat: f64, lon: f64) -> (i64, i64) {
+    (
+        (lat / CELL_SIZE_IN_DEGREES).floor() as i64,
+        (lon / CELL_SIZE_IN_DEGREES).floor() as i64,
+    )
+}
+
+// 中心から半径radius_in_km以内の点をすべて含む緯度経度の範囲
+fn bounding_box(lat: f64, lon: f64, radius_in_km: f64) -> ((f64, f64), (f64, f64)) {
+    let angular_radius = radius_in_km / EARTH_RADIUS_IN_KM;
+    let delta_lat = angular_radius.to_degrees() + BOUNDING_BOX_MARGIN_IN_DEGREES;
+    let (min_lat, max_lat) = (lat - delta_lat, lat + delta_lat);
+
+    let sin_delta_lon = angular_radius.sin() / lat.to_radians().cos();
+    if min_lat <= -90.0 || max_lat >= 90.0 || !(0.0..1.0).contains(&sin_delta_lon) {
+        return ((min_lat, -180.0), (max_lat, 180.0));
+    }
+    let delta_lon = sin_delta_lon.asin().to_degrees() + BOUNDING_BOX_MARGIN_IN_DEGREES;
+
+    ((min_lat, lon - delta_lon), (max_lat, lon + delta_lon))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SpatialIndex, StationPoint, StationSpatialIndex};
+    use crate::domain::entity::misc::distance_in_km;
+    use crate::domain::entity::misc::{BoundingBox, StationFilter};
+
+    fn points() -> Vec<StationPoint> {
+        // NOTE: 再現性のため疑似乱数で東京近郊に点を散らばらせる
+        let mut seed: u64 = 42;
+        let mut next = move || {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+            (seed >> 33) as f64 / (1u64 << 31) as f64
+        };
+        (1..=500)
+            .map(|station_cd| StationPoint {
+                station_cd,
+                station_g_cd: station_cd - station_cd % 2,
+                line_cd: station_cd % 7,
+                lat: 35.0 + next(),
+                lon: 139.0 + next() * 1.5,
+                e_status: if station_cd % 11 == 0 { 2 } else { 0 },
+                company_cd: station_cd % 3,
+                line_type: station_cd % 4,
+                average_distance: 1000.0,
+            })
+            .collect()
+    }
+
+    fn brute_force(points: &[StationPoint], lat: f64, lon: f64) -> Vec<(u32, f64)> {
+        let mut expected: Vec<(u32, f64)> = points
+            .iter()
+            .map(|p| (p.station_cd, distance_in_km(lat, lon, p.lat, p.lon)))
+            .collect();
+        expected.sort_by(|(a, a_distance), (b, b_distance)| {
+            a_distance.total_cmp(b_distance).then(a.cmp(b))
+        });
+        expected
+    }
+
+    #[test]
+    fn nearest() {
+        let points = points();
+        let index = SpatialIndex::new(points.clone());
+
+        for (lat, lon) in [
+            (35.681236, 139.767125),
+            (35.5, 139.2),
+            (34.0, 141.0),
+            (0.0, 0.0),
+        ] {
+            let expected = brute_force(&points, lat, lon);
+            for limit in [1, 5, 50, 600] {
+                let actual: Vec<(u32, f64)> = index
+                    .nearest(lat, lon, limit)
+                    .into_iter()
+                    .map(|(p, distance)| (p.station_cd, distance))
+                    .collect();
+                assert_eq!(
+                    actual,
+                    expected.iter().take(limit).copied().collect::<Vec<_>>()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn within_radius() {
+        let points = points();
+        let index = SpatialIndex::new(points.clone());
+
+        let expected: Vec<(u32, f64)> = brute_force(&points, 35.681236, 139.767125)
+            .into_iter()
+            .filter(|(_, distance)| *distance <= 10.0)
+            .collect();
+        let actual: Vec<(u32, f64)> = index
+            .within_radius(35.681236, 139.767125, 10.0)
+            .into_iter()
+            .map(|(p, distance)| (p.station_cd, distance))
+            .collect();
+        assert!(!actual.is_empty());
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn station_spatial_index() {
+        let points = points();
+        let index = StationSpatialIndex::new(points.clone());
+
+        let nearest = index.nearest_group_representatives(35.681236, 139.767125, 3);
+        assert_eq!(nearest.len(), 3);
+        assert!(nearest.iter().all(|(p, _)| p.station_cd == p.station_g_cd));
+
+        let (station, _) = index
+            .nearest_station(35.681236, 139.767125, Some(3))
+            .unwrap();
+        let expected = brute_force(
+            &points
+                .into_iter()
+                .filter(|p| p.line_cd == 3 && p.e_status == 0)
+                .collect::<Vec<_>>(),
+            35.681236,
+            139.767125,
+        );
+        assert_eq!(station.station_cd, expected[0].0);
+        assert!(index
+            .nearest_station(35.681236, 139.767125, Some(99))
+            .is_none());
+    }
+
+    #[test]
+    fn search_within_radius() {
+        let index = StationSpatialIndex::new(points());
+        let filter = StationFilter::new(None, Some(1), None);
+
+        let stations = index.search_within_radius(35.5, 139.75, 20.0, &filter, None);
+        assert!(!stations.is_empty());
+        assert!(stations
+            .iter()
+            .all(|p| p.company_cd == 1 && p.e_status == 0));
+        assert!(stations
+            .iter()
+            .all(|p| distance_in_km(35.5, 139.75, p.lat, p.lon) <= 20.0));
+        assert!(stations.windows(2).all(|pair| {
+            distance_in_km(35.5, 139.75, pair[0].lat, pair[0].lon)
+                <= distance_in_km(35.5, 139.75, pair[1].lat, pair[1].lon)
+        }));
+
+        let mut group_cds: Vec<u32> = stations.iter().map(|p| p.station_g_cd).collect();
+        group_cds.sort();
+        group_cds.dedup();
+        assert_eq!(group_cds.len(), stations.len());
+
+        let limited = index.search_within_radius(35.5, 139.75, 20.0, &filter, Some(2));
+        assert_eq!(limited, stations[..2]);
+    }
+
+    #[test]
+    fn search_within_bounding_box() {
+        let points = points();
+        let index = StationSpatialIndex::new(points.clone());
+        let bounding_box = BoundingBox::new(35.0, 139.0, 35.8, 140.0);
+        let filter = StationFilter::new(Some(2), None, Some(2));
+
+        let stations = index.search_within_bounding_box(&bounding_box, &filter, None);
+        let expected: Vec<u32> = points
+            .iter()
+            .filter(|p| bounding_box.contains(p.lat, p.lon))
+            .filter(|p| p.line_type == 2 && p.e_status == 2)
+            .map(|p| p.station_cd)
+            .collect();
+        assert!(!expected.is_empty());
+        assert_eq!(
+            stations.iter().map(|p| p.station_cd).collect::<Vec<_>>(),
+            expected
+        );
+    }
+}
@@ -0,0 +1,227 @@
+use unicode_normalization::UnicodeNormalization;
+
+// この文字数未満の検索語では曖昧一致を行わない
+const MIN_FUZZY_QUERY_LENGTH: usize = 3;
+// この文字数以上の検索語では2文字までの誤りを許容する
+const LONG_QUERY_LENGTH: usize = 6;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct StationNames {
+    pub station_cd: u32,
+    pub names: Vec<String>,
+}
+
+// 一致度。小さいほど良い
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum MatchQuality {
+    Exact,
+    Prefix,
+    Substring,
+    Fuzzy(usize),
+}
+
+struct IndexedStation {
+    station_cd: u32,
+    names: Vec<Vec<char>>,
+}
+
+pub struct StationNameIndex {
+    stations: Vec<IndexedStation>,
+}
+
+impl StationNameIndex {
+    pub fn new(stations: Vec<StationNames>) -> Self {
+        let stations = stations
+            .into_iter()
+            .map(|station| IndexedStation {
+                station_cd: station.station_cd,
+                names: station
+                    .names
+                    .iter()
+                    .map(|name| normalize(name).chars().collect())
+                    .filter(|name: &Vec<char>| !name.is_empty())
+                    .collect(),
+            })
+            .collect();
+
+        Self { stations }
+    }
+
+    // 一致度の高い順に駅コードを返す
+    pub fn search(&self, query: &str) -> Vec<u32> {
+        let query: Vec<char> = normalize(query).chars().collect();
+        if query.is_empty() {
+            return vec![];
+        }
+
+        let mut matches: Vec<(MatchQuality, usize, u32)> = self
+            .stations
+            .iter()
+            .filter_map(|station| {
+                station
+                    .names
+                    .iter()
+                    .filter_map(|name| {
+                        match_quality(name, &query)
+                            .map(|quality| (quality, name.len().abs_diff(query.len())))
+                    })
+                    .min()
+                    .map(|(quality, length_diff)| (quality, length_diff, station.station_cd))
+            })
+            .collect();
+        matches.sort();
+
+        matches
+            .into_iter()
+            .map(|(_, _, station_cd)| station_cd)
+            .collect()
+    }
+}
+
+// かな・ローマ字・全角半角・ダイアクリティカルマークの表記揺れを吸収する
+pub fn normalize(value: &str) -> String {
+    let folded: String = value
+        .nfkd()
+        // NOTE: "Tōkyō" の長音記号などを落とす。濁点(U+3099)はこの範囲外なので残る
+        .filter(|c| !('\u{0300}'..='\u{036F}').contains(c))
+        .nfc()
+        .flat_map(char::to_lowercase)
+        .map(|c| match c {
+            // ひらがなをカタカナに寄せる
+            '\u{3041}'..='\u{3096}' => char::from_u32(c as u32 + 0x60).unwrap_or(c),
+            'ヶ' => 'ケ',
+            'ヵ' => 'カ',
+            _ => c,
+        })
+        .filter(|c| c.is_alphanumeric())
+        .collect();
+
+    // NOTE: ローマ字の長音の書き方("Toukyou", "Ooka")を揃える
+    folded
+        .replace("ou", "o")
+        .replace("oo", "o")
+        .replace("uu", "u")
+}
+
+fn match_quality(name: &[char], query: &[char]) -> Option<MatchQuality> {
+    if name == query {
+        return Some(MatchQuality::Exact);
+    }
+    if name.starts_with(query) {
+        return Some(MatchQuality::Prefix);
+    }
+    if name.windows(query.len()).any(|window| window == query) {
+        return Some(MatchQuality::Substring);
+    }
+
+    let allowed_distance = match query.len() {
+        len if len < MIN_FUZZY_QUERY_LENGTH => return None,
+        len if len < LONG_QUERY_LENGTH => 1,
+        _ => 2,
+    };
+
+    // NOTE: 駅名に含まれない文字はそれぞれ1回以上の編集が必要なので、編集距離を計算する前に候補を絞る
+    let missing_chars = query.iter().filter(|c| !name.contains(c)).count();
+    if missing_chars > allowed_distance {
+        return None;
+    }
+
+    // 駅名全体と、入力途中を想定した先頭部分の両方と比べる
+    let prefix_distance = (query.len().saturating_sub(1)..=query.len() + 1)
+        .filter(|len| *len < name.len())
+        .map(|len| edit_distance(&name[..len], query));
+    let distance = std::iter::once(edit_distance(name, query))
+        .chain(prefix_distance)
+        .min()?;
+
+    (distance <= allowed_distance).then_some(MatchQuality::Fuzzy(distance))
+}
+
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, a_char) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = prev[j] + usize::from(a_char != b_char);
+            current[j + 1] = substitution.min(prev[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut current);
+    }
+
+    prev[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{edit_distance, normalize, StationNameIndex, StationNames};
+
+    fn index() -> StationNameIndex {
+        StationNameIndex::new(
+            [
+                (1130101, "東京", "トウキョウ", "Tōkyō"),
+                (1130102, "有楽町", "ユウラクチョウ", "Yūrakuchō"),
+                (
+                    2800101,
+                    "東京テレポート",
+                    "トウキョウテレポート",
+                    "Tokyo Teleport",
+                ),
+                (2800102, "霞ケ関", "カスミガセキ", "Kasumigaseki"),
+                (2800103, "渋谷", "シブヤ", "Shibuya"),
+            ]
+            .into_iter()
+            .map(|(station_cd, name, name_k, name_r)| StationNames {
+                station_cd,
+                names: vec![name.to_string(), name_k.to_string(), name_r.to_string()],
+            })
+            .collect(),
+        )
+    }
+
+    #[test]
+    fn normalize_variants() {
+        assert_eq!(normalize("Tōkyō"), normalize("Tokyo"));
+        assert_eq!(normalize("Toukyou"), normalize("Tokyo"));
+        assert_eq!(normalize("ＴＯＫＹＯ"), normalize("tokyo"));
+        assert_eq!(normalize("とうきょう"), normalize("トウキョウ"));
+        assert_eq!(normalize("ﾄｳｷｮｳ"), normalize("トウキョウ"));
+        assert_eq!(normalize("がっこう"), normalize("ガッコウ"));
+        assert_eq!(normalize("霞ヶ関"), normalize("霞ケ関"));
+        assert_eq!(normalize("Tokyo Teleport"), "tokyoteleport");
+    }
+
+    #[test]
+    fn edit_distances() {
+        let chars = |value: &str| value.chars().collect::<Vec<char>>();
+        assert_eq!(edit_distance(&chars("shibuya"), &chars("shibuya")), 0);
+        assert_eq!(edit_distance(&chars("shibuya"), &chars("shibya")), 1);
+        assert_eq!(edit_distance(&chars("shibuya"), &chars("shubiya")), 2);
+        assert_eq!(edit_distance(&chars(""), &chars("abc")), 3);
+    }
+
+    #[test]
+    fn search_ranks_by_match_quality() {
+        let index = index();
+        // 完全一致が部分一致より先に来る
+        assert_eq!(index.search("東京"), vec![1130101, 2800101]);
+        assert_eq!(index.search("とうきょう"), vec![1130101, 2800101]);
+        assert_eq!(index.search("Tokyo"), vec![1130101, 2800101]);
+        assert_eq!(index.search("yurakucho"), vec![1130102]);
+        assert_eq!(index.search("霞ヶ関"), vec![2800102]);
+        assert_eq!(index.search("テレポート"), vec![2800101]);
+    }
+
+    #[test]
+    fn search_tolerates_typos() {
+        let index = index();
+        assert_eq!(index.search("shibya"), vec![2800103]);
+        assert_eq!(index.search("kasumigasek"), vec![2800102]);
+        assert_eq!(index.search("kasumigazeki"), vec![2800102]);
+        // 短い検索語では曖昧一致しない
+        assert_eq!(index.search("しぶ"), vec![2800103]);
+        assert!(index.search("しぼ").is_empty());
+        assert!(index.search("").is_empty());
+    }
+}
@@ -1,16 +1,42 @@
 use async_trait::async_trait;
 use sqlx::{MySql, MySqlConnection, Pool};
-use std::sync::Arc;
+use std::{
+    collections::{BTreeSet, HashMap},
+    sync::Arc,
+};
+use tokio::sync::OnceCell;
 
 use crate::{
     domain::{
-        entity::{misc::StationIdWithDistance, station::Station},
+        entity::{
+            misc::{AsOf, BoundingBox, StationFilter, StationIdWithDistance, UNKNOWN_YMD},
+            station::Station,
+        },
         error::DomainError,
         repository::station_repository::StationRepository,
     },
+    infrastructure::{
+        spatial_index::{StationPoint, StationSpatialIndex},
+        station_name_index::{StationNameIndex, StationNames},
+    },
     station_api::StopCondition,
 };
 
+// as_ofを指定したときは開業日・廃止日で、しなければe_statusで営業中の駅に絞る
+// NOTE: AsOf::is_operatingと同じ条件。as_ofを指定したときは?が2つあるので2回bindすること
+pub fn operating_station_condition(alias: &str, as_of: Option<&AsOf>) -> String {
+    match as_of {
+        Some(_) => format!(
+            "{alias}.e_status IN (0, 1, 2)
+              AND IF({alias}.open_ymd = '{unknown}', {alias}.e_status <> 1, {alias}.open_ymd <= ?)
+              AND IF({alias}.close_ymd = '{unknown}', {alias}.e_status <> 2, {alias}.close_ymd > ?)",
+            alias = alias,
+            unknown = UNKNOWN_YMD
+        ),
+        None => format!("{}.e_status = 0", alias),
+    }
+}
+
 #[derive(sqlx::FromRow)]
 struct TrainTypesCountRow {
     train_types_count: i64,
@@ -169,19 +195,114 @@ impl From<StationRow> for Station {
 }
 
 #[derive(sqlx::FromRow, Clone)]
-struct DistanceWithIdRow {
+struct StationPointRow {
     station_cd: u32,
-    distance: f64,
+    station_g_cd: u32,
+    line_cd: u32,
+    lat: f64,
+    lon: f64,
+    e_status: u32,
+    company_cd: u32,
+    line_type: u32,
     average_distance: f64,
 }
 
+impl From<StationPointRow> for StationPoint {
+    fn from(row: StationPointRow) -> Self {
+        Self {
+            station_cd: row.station_cd,
+            station_g_cd: row.station_g_cd,
+            line_cd: row.line_cd,
+            lat: row.lat,
+            lon: row.lon,
+            e_status: row.e_status,
+            company_cd: row.company_cd,
+            line_type: row.line_type,
+            average_distance: row.average_distance,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow, Clone)]
+struct StationNamesRow {
+    station_cd: u32,
+    station_name: String,
+    station_name_k: String,
+    station_name_r: Option<String>,
+    station_name_zh: Option<String>,
+    station_name_ko: Option<String>,
+}
+
+impl From<StationNamesRow> for StationNames {
+    fn from(row: StationNamesRow) -> Self {
+        let names = [
+            Some(row.station_name),
+            Some(row.station_name_k),
+            row.station_name_r,
+            row.station_name_zh,
+            row.station_name_ko,
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+
+        Self {
+            station_cd: row.station_cd,
+            names,
+        }
+    }
+}
+
+// 駅名検索の候補をこの件数ずつDBに問い合わせる
+const NAME_SEARCH_BATCH_SIZE: usize = 100;
+
+// 駅ごとにpositionで探すと件数の2乗になるので、先に順位を引けるようにしておく
+fn sort_rows_by_station_ids(rows: &mut [StationRow], station_ids: &[u32]) {
+    let rank: HashMap<u32, usize> = station_ids
+        .iter()
+        .enumerate()
+        .map(|(index, id)| (*id, index))
+        .rev()
+        .collect();
+    rows.sort_by_key(|row| rank.get(&row.station_cd).copied().unwrap_or(usize::MAX));
+}
+
 pub struct MyStationRepository {
     pool: Arc<Pool<MySql>>,
+    spatial_index: OnceCell<StationSpatialIndex>,
+    name_index: OnceCell<StationNameIndex>,
 }
 
 impl MyStationRepository {
     pub fn new(pool: Arc<Pool<MySql>>) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            spatial_index: OnceCell::new(),
+            name_index: OnceCell::new(),
+        }
+    }
+
+    // 駅の座標はマイグレーション時にしか変わらないため、初回アクセス時に読み込んで使い回す
+    // NOTE: 稼働中のDBにマイグレーションをかけ直したときは、dataset_infoのバージョンが変わったのを
+    // 見てリポジトリごと作り直す。$DATASET_VERSION_CHECK_INTERVAL_SECSを0にしたらSIGHUPかReloadDatasetで
+    async fn spatial_index(&self) -> Result<&StationSpatialIndex, DomainError> {
+        self.spatial_index
+            .get_or_try_init(|| async {
+                let mut conn = self.pool.acquire().await?;
+                let points = InternalStationRepository::get_station_points(&mut conn).await?;
+                Ok(StationSpatialIndex::new(points))
+            })
+            .await
+    }
+
+    async fn name_index(&self) -> Result<&StationNameIndex, DomainError> {
+        self.name_index
+            .get_or_try_init(|| async {
+                let mut conn = self.pool.acquire().await?;
+                let names = InternalStationRepository::get_station_names(&mut conn).await?;
+                Ok(StationNameIndex::new(names))
+            })
+            .await
     }
 }
 
@@ -214,6 +335,18 @@ impl StationRepository for MyStationRepository {
             }
         }
     }
+    async fn get_by_line_id_vec(&self, line_id_vec: &[u32]) -> Result<Vec<Station>, DomainError> {
+        let mut conn = self.pool.acquire().await?;
+        InternalStationRepository::get_by_line_id_vec(line_id_vec, &mut conn).await
+    }
+    async fn get_by_line_id_as_of(
+        &self,
+        line_id: u32,
+        as_of: &AsOf,
+    ) -> Result<Vec<Station>, DomainError> {
+        let mut conn = self.pool.acquire().await?;
+        InternalStationRepository::get_by_line_id_as_of(line_id, as_of, &mut conn).await
+    }
     async fn get_by_station_group_id(
         &self,
         station_group_id: u32,
@@ -230,15 +363,67 @@ impl StationRepository for MyStationRepository {
             .await
     }
 
-    // ほぼ確実にキャッシュがヒットしないと思うのでキャッシュを使わない
+    // ほぼ確実にキャッシュがヒットしないと思うので結果はキャッシュせず、索引だけを持つ
     async fn get_by_coordinates(
         &self,
         latitude: f64,
         longitude: f64,
         limit: Option<u32>,
     ) -> Result<Vec<Station>, DomainError> {
+        let station_ids: Vec<u32> = self
+            .spatial_index()
+            .await?
+            .nearest_group_representatives(latitude, longitude, limit.unwrap_or(1) as usize)
+            .into_iter()
+            .map(|(point, _)| point.station_cd)
+            .collect();
+
         let mut conn = self.pool.acquire().await?;
-        InternalStationRepository::get_by_coordinates(latitude, longitude, limit, &mut conn).await
+        InternalStationRepository::get_by_id_vec_in_order(&station_ids, &mut conn).await
+    }
+
+    async fn get_by_radius(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        radius_in_km: f64,
+        filter: &StationFilter,
+        limit: Option<u32>,
+    ) -> Result<Vec<Station>, DomainError> {
+        let station_ids: Vec<u32> = self
+            .spatial_index()
+            .await?
+            .search_within_radius(
+                latitude,
+                longitude,
+                radius_in_km,
+                filter,
+                limit.map(|limit| limit as usize),
+            )
+            .into_iter()
+            .map(|point| point.station_cd)
+            .collect();
+
+        let mut conn = self.pool.acquire().await?;
+        InternalStationRepository::get_by_id_vec_in_order(&station_ids, &mut conn).await
+    }
+
+    async fn get_by_bounding_box(
+        &self,
+        bounding_box: &BoundingBox,
+        filter: &StationFilter,
+        limit: Option<u32>,
+    ) -> Result<Vec<Station>, DomainError> {
+        let station_ids: Vec<u32> = self
+            .spatial_index()
+            .await?
+            .search_within_bounding_box(bounding_box, filter, limit.map(|limit| limit as usize))
+            .into_iter()
+            .map(|point| point.station_cd)
+            .collect();
+
+        let mut conn = self.pool.acquire().await?;
+        InternalStationRepository::get_by_id_vec_in_order(&station_ids, &mut conn).await
     }
 
     async fn get_by_name(
@@ -247,41 +432,80 @@ impl StationRepository for MyStationRepository {
         limit: Option<u32>,
         from_station_group_id: Option<u32>,
     ) -> Result<Vec<Station>, DomainError> {
+        let candidate_ids = self.name_index().await?.search(&station_name);
+        let limit = limit.unwrap_or(1) as usize;
+
         let mut conn = self.pool.acquire().await?;
-        InternalStationRepository::get_by_name(
-            station_name,
-            limit,
-            from_station_group_id,
-            &mut conn,
-        )
-        .await
+        let mut group_ids = BTreeSet::new();
+        let mut stations = vec![];
+
+        // NOTE: 出発駅による絞り込みで候補が減るため、一致度の高い順に少しずつ問い合わせる
+        for chunk in candidate_ids.chunks(NAME_SEARCH_BATCH_SIZE) {
+            let rows =
+                InternalStationRepository::get_by_name(chunk, from_station_group_id, &mut conn)
+                    .await?;
+            for station in rows {
+                if stations.len() >= limit {
+                    return Ok(stations);
+                }
+                if group_ids.insert(station.station_g_cd) {
+                    stations.push(station);
+                }
+            }
+            if stations.len() >= limit {
+                break;
+            }
+        }
+
+        Ok(stations)
     }
 
     async fn get_by_line_group_id(&self, line_group_id: u32) -> Result<Vec<Station>, DomainError> {
         let mut conn = self.pool.acquire().await?;
         InternalStationRepository::get_by_line_group_id(line_group_id, &mut conn).await
     }
+    async fn get_by_line_group_id_vec(
+        &self,
+        line_group_id_vec: &[u32],
+    ) -> Result<Vec<Station>, DomainError> {
+        let mut conn = self.pool.acquire().await?;
+        InternalStationRepository::get_by_line_group_id_vec(line_group_id_vec, &mut conn).await
+    }
+    async fn get_by_prefecture_id(
+        &self,
+        prefecture_id: u32,
+        as_of: Option<&AsOf>,
+    ) -> Result<Vec<Station>, DomainError> {
+        let mut conn = self.pool.acquire().await?;
+        InternalStationRepository::get_by_prefecture_id(prefecture_id, as_of, &mut conn).await
+    }
+    async fn get_by_company_id(
+        &self,
+        company_id: u32,
+        as_of: Option<&AsOf>,
+    ) -> Result<Vec<Station>, DomainError> {
+        let mut conn = self.pool.acquire().await?;
+        InternalStationRepository::get_by_company_id(company_id, as_of, &mut conn).await
+    }
     async fn get_station_id_and_distance_by_coordinates(
         &self,
         latitude: f64,
         longitude: f64,
         line_id: Option<u32>,
     ) -> Result<StationIdWithDistance, DomainError> {
-        let mut conn = self.pool.acquire().await?;
-        match line_id {
-            Some(line_id) => {
-                InternalStationRepository::get_station_id_and_distance_by_coordinates_and_line_id(
-                    latitude, longitude, line_id, &mut conn,
-                )
-                .await
-            }
-            None => {
-                InternalStationRepository::get_station_id_and_distance_by_coordinates(
-                    latitude, longitude, &mut conn,
-                )
-                .await
-            }
-        }
+        let Some((point, distance)) = self
+            .spatial_index()
+            .await?
+            .nearest_station(latitude, longitude, line_id)
+        else {
+            return Err(sqlx::Error::RowNotFound.into());
+        };
+
+        Ok(StationIdWithDistance {
+            station_id: point.station_cd,
+            distance,
+            average_distance: point.average_distance,
+        })
     }
     async fn get_route_stops(
         &self,
@@ -291,6 +515,10 @@ impl StationRepository for MyStationRepository {
         let mut conn = self.pool.acquire().await?;
         InternalStationRepository::get_route_stops(from_station_id, to_station_id, &mut conn).await
     }
+    async fn get_all_stops(&self) -> Result<Vec<Station>, DomainError> {
+        let mut conn = self.pool.acquire().await?;
+        InternalStationRepository::get_all_stops(&mut conn).await
+    }
 }
 
 struct InternalStationRepository {}
@@ -490,6 +718,140 @@ impl InternalStationRepository {
         Ok(stations)
     }
 
+    async fn get_by_line_id_vec(
+        line_id_vec: &[u32],
+        conn: &mut MySqlConnection,
+    ) -> Result<Vec<Station>, DomainError> {
+        if line_id_vec.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let params = format!("?{}", ", ?".repeat(line_id_vec.len() - 1));
+        let query_str = format!(
+            "SELECT s.*,
+              l.company_cd,
+              l.line_type,
+              l.line_symbol_primary,
+              l.line_symbol_secondary,
+              l.line_symbol_extra,
+              l.line_symbol_primary_color,
+              l.line_symbol_secondary_color,
+              l.line_symbol_extra_color,
+              l.line_symbol_primary_shape,
+              l.line_symbol_secondary_shape,
+              l.line_symbol_extra_shape,
+              l.average_distance,
+              COALESCE(a.line_name, l.line_name) AS line_name,
+              COALESCE(a.line_name_k, l.line_name_k) AS line_name_k,
+              COALESCE(a.line_name_h, l.line_name_h) AS line_name_h,
+              COALESCE(a.line_name_r, l.line_name_r) AS line_name_r,
+              COALESCE(a.line_name_zh, l.line_name_zh) AS line_name_zh,
+              COALESCE(a.line_name_ko, l.line_name_ko) AS line_name_ko,
+              COALESCE(a.line_color_c, l.line_color_c) AS line_color_c,
+              0 AS has_train_types
+              FROM `stations` AS s
+              JOIN `lines` AS l ON l.line_cd = s.line_cd
+                AND l.e_status = 0
+              LEFT JOIN `line_aliases` AS la ON la.station_cd = s.station_cd
+              LEFT JOIN `aliases` AS a ON la.alias_cd = a.id
+            WHERE l.line_cd IN ( {} )
+              AND s.e_status = 0
+            ORDER BY s.line_cd, s.e_sort, s.station_cd ASC",
+            params
+        );
+
+        let mut query = sqlx::query_as::<_, StationRow>(&query_str);
+        for id in line_id_vec {
+            query = query.bind(id);
+        }
+        let rows = query.fetch_all(conn).await?;
+
+        let stations: Vec<Station> = rows.into_iter().map(|row| row.into()).collect();
+
+        Ok(stations)
+    }
+
+    async fn get_by_line_id_as_of(
+        line_id: u32,
+        as_of: &AsOf,
+        conn: &mut MySqlConnection,
+    ) -> Result<Vec<Station>, DomainError> {
+        Self::get_without_train_types_by_column("s.line_cd", line_id, Some(as_of), conn).await
+    }
+
+    async fn get_by_prefecture_id(
+        prefecture_id: u32,
+        as_of: Option<&AsOf>,
+        conn: &mut MySqlConnection,
+    ) -> Result<Vec<Station>, DomainError> {
+        Self::get_without_train_types_by_column("s.pref_cd", prefecture_id, as_of, conn).await
+    }
+
+    async fn get_by_company_id(
+        company_id: u32,
+        as_of: Option<&AsOf>,
+        conn: &mut MySqlConnection,
+    ) -> Result<Vec<Station>, DomainError> {
+        Self::get_without_train_types_by_column("l.company_cd", company_id, as_of, conn).await
+    }
+
+    // NOTE: columnは呼び出し側で固定した列名のみを渡すこと
+    async fn get_without_train_types_by_column(
+        column: &str,
+        value: u32,
+        as_of: Option<&AsOf>,
+        conn: &mut MySqlConnection,
+    ) -> Result<Vec<Station>, DomainError> {
+        // NOTE: 廃止された路線の駅も返せるよう、日付を指定したときは路線のe_statusを見ない
+        let line_condition = match as_of {
+            Some(_) => "",
+            None => "AND l.e_status = 0",
+        };
+        let query_str = format!(
+            "SELECT s.*,
+              l.company_cd,
+              l.line_type,
+              l.line_symbol_primary,
+              l.line_symbol_secondary,
+              l.line_symbol_extra,
+              l.line_symbol_primary_color,
+              l.line_symbol_secondary_color,
+              l.line_symbol_extra_color,
+              l.line_symbol_primary_shape,
+              l.line_symbol_secondary_shape,
+              l.line_symbol_extra_shape,
+              l.average_distance,
+              COALESCE(a.line_name, l.line_name) AS line_name,
+              COALESCE(a.line_name_k, l.line_name_k) AS line_name_k,
+              COALESCE(a.line_name_h, l.line_name_h) AS line_name_h,
+              COALESCE(a.line_name_r, l.line_name_r) AS line_name_r,
+              COALESCE(a.line_name_zh, l.line_name_zh) AS line_name_zh,
+              COALESCE(a.line_name_ko, l.line_name_ko) AS line_name_ko,
+              COALESCE(a.line_color_c, l.line_color_c) AS line_color_c,
+              0 AS has_train_types
+              FROM `stations` AS s
+              JOIN `lines` AS l ON l.line_cd = s.line_cd
+                {}
+              LEFT JOIN `line_aliases` AS la ON la.station_cd = s.station_cd
+              LEFT JOIN `aliases` AS a ON la.alias_cd = a.id
+            WHERE {} = ?
+              AND {}
+            ORDER BY s.e_sort, s.station_cd ASC",
+            line_condition,
+            column,
+            operating_station_condition("s", as_of)
+        );
+        let mut query = sqlx::query_as::<_, StationRow>(&query_str).bind(value);
+        if let Some(as_of) = as_of {
+            query = query.bind(as_of.as_str()).bind(as_of.as_str());
+        }
+        let rows = query.fetch_all(conn).await?;
+
+        let stations: Vec<Station> = rows.into_iter().map(|row| row.into()).collect();
+
+        Ok(stations)
+    }
+
     async fn get_by_line_id_and_station_id(
         line_id: u32,
         station_id: u32,
@@ -676,13 +1038,41 @@ impl InternalStationRepository {
         Ok(lines)
     }
 
-    async fn get_by_coordinates(
-        latitude: f64,
-        longitude: f64,
-        limit: Option<u32>,
+    async fn get_station_points(
+        conn: &mut MySqlConnection,
+    ) -> Result<Vec<StationPoint>, DomainError> {
+        let rows = sqlx::query_as::<_, StationPointRow>(
+            "SELECT
+                s.station_cd,
+                s.station_g_cd,
+                s.line_cd,
+                s.lat,
+                s.lon,
+                s.e_status,
+                l.company_cd,
+                l.line_type,
+                IFNULL(l.average_distance, 0.0) AS average_distance
+              FROM `stations` AS s
+              JOIN `lines` AS l ON l.line_cd = s.line_cd",
+        )
+        .fetch_all(conn)
+        .await?;
+
+        let points = rows.into_iter().map(|row| row.into()).collect();
+
+        Ok(points)
+    }
+
+    async fn get_by_id_vec_in_order(
+        station_ids: &[u32],
         conn: &mut MySqlConnection,
     ) -> Result<Vec<Station>, DomainError> {
-        let rows = sqlx::query_as::<_, StationRow>(
+        if station_ids.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let params = format!("?{}", ", ?".repeat(station_ids.len() - 1));
+        let query_str = format!(
             "SELECT 
                 s.*, 
                 l.*,
@@ -692,156 +1082,63 @@ impl InternalStationRepository {
                 COALESCE(a.line_name_r, l.line_name_r) AS line_name_r, 
                 COALESCE(a.line_name_zh, l.line_name_zh) AS line_name_zh, 
                 COALESCE(a.line_name_ko, l.line_name_ko) AS line_name_ko, 
-                COALESCE(a.line_color_c, l.line_color_c) AS line_color_c, 
-                (
-                  6371 * acos(
-                    cos(
-                      radians(s.lat)
-                    ) * cos(
-                      radians(?)
-                    ) * cos(
-                      radians(?) - radians(s.lon)
-                    ) + sin(
-                      radians(s.lat)
-                    ) * sin(
-                      radians(?)
-                    )
-                  )
-                ) AS distance
+                COALESCE(a.line_color_c, l.line_color_c) AS line_color_c
               FROM `stations` AS s
               JOIN `lines` AS l ON s.line_cd = l.line_cd
               LEFT JOIN `line_aliases` AS la ON la.station_cd = s.station_cd 
               LEFT JOIN `aliases` AS a ON a.id = la.alias_cd 
               WHERE
-                s.station_cd = s.station_g_cd
-                AND s.e_status = 0
-              ORDER BY 
-                distance
-              LIMIT
-                ?",
-        )
-        .bind(latitude)
-        .bind(longitude)
-        .bind(latitude)
-        .bind(limit.unwrap_or(1))
-        .fetch_all(conn)
-        .await?;
+                s.station_cd IN ( {} )",
+            params
+        );
+
+        let mut query = sqlx::query_as::<_, StationRow>(&query_str);
+        for id in station_ids {
+            query = query.bind(id);
+        }
+        let mut rows = query.fetch_all(conn).await?;
+
+        // 空間インデックスで求めた順に並べ直す
+        sort_rows_by_station_ids(&mut rows, station_ids);
 
         let stations = rows.into_iter().map(|row| row.into()).collect();
 
         Ok(stations)
     }
 
-    async fn get_station_id_and_distance_by_coordinates_and_line_id(
-        latitude: f64,
-        longitude: f64,
-        line_id: u32,
+    async fn get_station_names(
         conn: &mut MySqlConnection,
-    ) -> Result<StationIdWithDistance, DomainError> {
-        let row = sqlx::query_as::<_, DistanceWithIdRow>(
+    ) -> Result<Vec<StationNames>, DomainError> {
+        let rows = sqlx::query_as::<_, StationNamesRow>(
             "SELECT
-            s.station_cd,
-            s.station_g_cd, 
-            l.average_distance,
-            (
-              6371 * acos(
-                cos(
-                  radians(s.lat)
-                ) * cos(
-                  radians(?)
-                ) * cos(
-                  radians(?) - radians(s.lon)
-                ) + sin(
-                  radians(s.lat)
-                ) * sin(
-                  radians(?)
-                )
-              )
-            ) AS distance
-          FROM `stations` AS s
-          JOIN `lines` AS l ON l.line_cd = ?
-          WHERE
-            s.line_cd = ?
-            AND s.e_status = 0
-          ORDER BY 
-            distance
-          LIMIT 
-            1",
+                s.station_cd,
+                s.station_name,
+                s.station_name_k,
+                s.station_name_r,
+                s.station_name_zh,
+                s.station_name_ko
+              FROM `stations` AS s
+              WHERE s.e_status = 0",
         )
-        .bind(latitude)
-        .bind(longitude)
-        .bind(latitude)
-        .bind(line_id)
-        .bind(line_id)
-        .fetch_one(conn)
+        .fetch_all(conn)
         .await?;
-        let id_with_distance = StationIdWithDistance {
-            station_id: row.station_cd,
-            distance: row.distance,
-            average_distance: row.average_distance,
-        };
 
-        Ok(id_with_distance)
-    }
+        let names = rows.into_iter().map(|row| row.into()).collect();
 
-    async fn get_station_id_and_distance_by_coordinates(
-        latitude: f64,
-        longitude: f64,
-        conn: &mut MySqlConnection,
-    ) -> Result<StationIdWithDistance, DomainError> {
-        let row = sqlx::query_as::<_, DistanceWithIdRow>(
-            "SELECT
-          s.station_cd,
-          s.station_g_cd,
-          l.average_distance,
-          (
-            6371 * acos(
-              cos(
-                radians(s.lat)
-              ) * cos(
-                radians(?)
-              ) * cos(
-                radians(?) - radians(s.lon)
-              ) + sin(
-                radians(s.lat)
-              ) * sin(
-                radians(?)
-              )
-            )
-          ) AS distance
-        FROM `stations` AS s
-        JOIN `lines` AS l ON l.line_cd = s.line_cd
-        WHERE
-          s.e_status = 0
-        ORDER BY 
-          distance
-        LIMIT 
-          1",
-        )
-        .bind(latitude)
-        .bind(longitude)
-        .bind(latitude)
-        .fetch_one(conn)
-        .await?;
-        let id_with_distance = StationIdWithDistance {
-            station_id: row.station_cd,
-            distance: row.distance,
-            average_distance: row.average_distance,
-        };
-
-        Ok(id_with_distance)
+        Ok(names)
     }
 
     async fn get_by_name(
-        station_name: String,
-        limit: Option<u32>,
+        station_ids: &[u32],
         from_station_group_id: Option<u32>,
         conn: &mut MySqlConnection,
     ) -> Result<Vec<Station>, DomainError> {
-        let station_name = format!("%{}%", station_name);
+        if station_ids.is_empty() {
+            return Ok(vec![]);
+        }
 
-        let rows: Vec<StationRow> = sqlx::query_as!(
-            StationRow,
+        let params = format!("?{}", ", ?".repeat(station_ids.len() - 1));
+        let query_str = format!(
             "WITH from_stations AS (
                 SELECT s.station_cd,
                     s.line_cd
@@ -892,13 +1189,7 @@ impl InternalStationRepository {
                 LEFT JOIN `aliases` AS a ON la.alias_cd = a.id
                 JOIN `lines` AS l ON l.line_cd = s.line_cd
                 AND l.e_status = 0
-            WHERE (
-                    s.station_name LIKE ?
-                    OR s.station_name_r LIKE ?
-                    OR s.station_name_k LIKE ?
-                    OR s.station_name_zh LIKE ?
-                    OR s.station_name_ko LIKE ?
-                )
+            WHERE s.station_cd IN ( {} )
                 AND s.e_status = 0
                 AND IF(
                     from_sst.id IS NOT NULL
@@ -906,19 +1197,18 @@ impl InternalStationRepository {
                     from_sst.line_group_cd = dst_sst.line_group_cd
                     AND dst_sst.pass <> 1,
                     s.line_cd = IFNULL(fs.line_cd, s.line_cd)
-                )
-            GROUP BY s.station_g_cd
-            LIMIT ?",
-            from_station_group_id,
-            &station_name,
-            &station_name,
-            &station_name,
-            &station_name,
-            &station_name,
-            limit.unwrap_or(1)
-        )
-        .fetch_all(conn)
-        .await?;
+                )",
+            params
+        );
+
+        let mut query = sqlx::query_as::<_, StationRow>(&query_str).bind(from_station_group_id);
+        for id in station_ids {
+            query = query.bind(id);
+        }
+        let mut rows = query.fetch_all(conn).await?;
+
+        // 駅名インデックスで求めた一致度の順に並べ直す
+        sort_rows_by_station_ids(&mut rows, station_ids);
 
         let stations: Vec<Station> = rows.into_iter().map(|row| row.into()).collect();
 
@@ -985,6 +1275,73 @@ impl InternalStationRepository {
         Ok(stations)
     }
 
+    async fn get_by_line_group_id_vec(
+        line_group_id_vec: &[u32],
+        conn: &mut MySqlConnection,
+    ) -> Result<Vec<Station>, DomainError> {
+        if line_group_id_vec.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let params = format!("?{}", ", ?".repeat(line_group_id_vec.len() - 1));
+        let query_str = format!(
+            "SELECT DISTINCT s.*,
+            l.company_cd,
+            l.line_type,
+            l.line_symbol_primary,
+            l.line_symbol_secondary,
+            l.line_symbol_extra,
+            l.line_symbol_primary_color,
+            l.line_symbol_secondary_color,
+            l.line_symbol_extra_color,
+            l.line_symbol_primary_shape,
+            l.line_symbol_secondary_shape,
+            l.line_symbol_extra_shape,
+            l.average_distance,
+            sst.id AS sst_id,
+            sst.type_cd,
+            sst.line_group_cd,
+            sst.pass,
+            COALESCE(a.line_name, l.line_name) AS line_name,
+            COALESCE(a.line_name_k, l.line_name_k) AS line_name_k,
+            COALESCE(a.line_name_h, l.line_name_h) AS line_name_h,
+            COALESCE(a.line_name_r, l.line_name_r) AS line_name_r,
+            COALESCE(a.line_name_zh, l.line_name_zh) AS line_name_zh,
+            COALESCE(a.line_name_ko, l.line_name_ko) AS line_name_ko,
+            COALESCE(a.line_color_c, l.line_color_c) AS line_color_c,
+            IFNULL(s.station_cd = sst.station_cd, 0) AS has_train_types,
+            t.id AS type_id,
+            t.type_name,
+            t.type_name_k,
+            t.type_name_r,
+            t.type_name_zh,
+            t.type_name_ko,
+            t.color,
+            t.direction,
+            t.kind
+          FROM `stations` AS s
+          JOIN `lines` AS l ON l.line_cd = s.line_cd AND l.e_status = 0
+          JOIN `station_station_types` AS sst ON sst.line_group_cd IN ( {} )
+            AND sst.station_cd = s.station_cd
+          LEFT JOIN `types` AS t ON t.type_cd = sst.type_cd
+          LEFT JOIN `line_aliases` AS la ON la.station_cd = s.station_cd
+          LEFT JOIN `aliases` AS a ON la.alias_cd = a.id
+          WHERE s.e_status = 0
+          ORDER BY sst.id",
+            params
+        );
+
+        let mut query = sqlx::query_as::<_, StationRow>(&query_str);
+        for id in line_group_id_vec {
+            query = query.bind(id);
+        }
+        let rows = query.fetch_all(conn).await?;
+
+        let stations: Vec<Station> = rows.into_iter().map(|row| row.into()).collect();
+
+        Ok(stations)
+    }
+
     async fn get_route_stops(
         from_station_id: u32,
         to_station_id: u32,
@@ -1241,4 +1598,51 @@ impl InternalStationRepository {
 
         Ok(stations)
     }
+
+    async fn get_all_stops(conn: &mut MySqlConnection) -> Result<Vec<Station>, DomainError> {
+        let rows = sqlx::query_as::<_, StationRow>(
+            "SELECT
+                s.*,
+                l.company_cd,
+                l.line_type,
+                l.line_symbol_primary,
+                l.line_symbol_secondary,
+                l.line_symbol_extra,
+                l.line_symbol_primary_color,
+                l.line_symbol_secondary_color,
+                l.line_symbol_extra_color,
+                l.line_symbol_primary_shape,
+                l.line_symbol_secondary_shape,
+                l.line_symbol_extra_shape,
+                IFNULL(l.average_distance, 0.0) AS average_distance,
+                l.line_name,
+                l.line_name_k,
+                l.line_name_h,
+                l.line_name_r,
+                l.line_name_zh,
+                l.line_name_ko,
+                l.line_color_c,
+                sst.id AS sst_id,
+                sst.type_cd,
+                sst.line_group_cd,
+                sst.pass
+              FROM `stations` AS s
+              JOIN `lines` AS l ON l.line_cd = s.line_cd AND l.e_status = 0
+              LEFT JOIN `station_station_types` AS sst ON sst.station_cd = s.station_cd
+                AND sst.pass <> 1
+              WHERE
+                s.e_status = 0
+              ORDER BY
+                s.line_cd,
+                s.e_sort,
+                s.station_cd,
+                sst.id",
+        )
+        .fetch_all(conn)
+        .await?;
+
+        let stations = rows.into_iter().map(|row| row.into()).collect();
+
+        Ok(stations)
+    }
 }
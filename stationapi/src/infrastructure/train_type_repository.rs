@@ -1,10 +1,14 @@
 use crate::domain::{
-    entity::train_type::TrainType, error::DomainError,
+    entity::{misc::TrainTypeFilter, train_type::TrainType},
+    error::DomainError,
     repository::train_type_repository::TrainTypeRepository,
 };
 use async_trait::async_trait;
 use sqlx::{MySql, MySqlConnection, Pool};
-use std::sync::Arc;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+};
 
 #[derive(sqlx::FromRow, Clone)]
 pub struct TrainTypeRow {
@@ -60,6 +64,13 @@ impl From<TrainTypeRow> for TrainType {
     }
 }
 
+#[derive(sqlx::FromRow, Clone)]
+pub struct TrainTypeWithLineRow {
+    #[sqlx(flatten)]
+    train_type: TrainTypeRow,
+    line_cd: u32,
+}
+
 pub struct MyTrainTypeRepository {
     pool: Arc<Pool<MySql>>,
 }
@@ -85,6 +96,14 @@ impl TrainTypeRepository for MyTrainTypeRepository {
         InternalTrainTypeRepository::get_by_station_id(station_id, &mut conn).await
     }
 
+    async fn get_all_by_station_id_vec(
+        &self,
+        station_id_vec: &[u32],
+    ) -> Result<Vec<TrainType>, DomainError> {
+        let mut conn = self.pool.acquire().await?;
+        InternalTrainTypeRepository::get_all_by_station_id_vec(station_id_vec, &mut conn).await
+    }
+
     async fn find_by_line_group_id_and_line_id(
         &self,
         line_group_id: u32,
@@ -99,6 +118,18 @@ impl TrainTypeRepository for MyTrainTypeRepository {
         .await
     }
 
+    async fn get_by_line_group_id_and_line_id_vec(
+        &self,
+        line_group_id_and_line_id_vec: &[(u32, u32)],
+    ) -> Result<BTreeMap<(u32, u32), TrainType>, DomainError> {
+        let mut conn = self.pool.acquire().await?;
+        InternalTrainTypeRepository::get_by_line_group_id_and_line_id_vec(
+            line_group_id_and_line_id_vec,
+            &mut conn,
+        )
+        .await
+    }
+
     async fn get_by_station_id_vec(
         &self,
         station_id_vec: &[u32],
@@ -130,6 +161,16 @@ impl TrainTypeRepository for MyTrainTypeRepository {
         let mut conn = self.pool.acquire().await?;
         InternalTrainTypeRepository::get_by_line_group_id_vec(line_group_id_vec, &mut conn).await
     }
+
+    async fn find_by_type_cd(&self, type_cd: u32) -> Result<Option<TrainType>, DomainError> {
+        let mut conn = self.pool.acquire().await?;
+        InternalTrainTypeRepository::find_by_type_cd(type_cd, &mut conn).await
+    }
+
+    async fn get_by_filter(&self, filter: &TrainTypeFilter) -> Result<Vec<TrainType>, DomainError> {
+        let mut conn = self.pool.acquire().await?;
+        InternalTrainTypeRepository::get_by_filter(filter, &mut conn).await
+    }
 }
 
 pub struct InternalTrainTypeRepository {}
@@ -191,6 +232,42 @@ impl InternalTrainTypeRepository {
 
         Ok(train_types)
     }
+    async fn get_all_by_station_id_vec(
+        station_id_vec: &[u32],
+        conn: &mut MySqlConnection,
+    ) -> Result<Vec<TrainType>, DomainError> {
+        if station_id_vec.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let params = format!("?{}", ", ?".repeat(station_id_vec.len() - 1));
+        let query_str = format!(
+            "SELECT 
+            t.type_name,
+            t.type_name_k,
+            t.type_name_r,
+            t.type_name_zh,
+            t.type_name_ko,
+            t.color,
+            t.direction,
+            t.kind,
+            sst.*
+            FROM  `types` AS t
+            JOIN `stations` AS s ON s.station_cd IN ( {} ) AND s.e_status = 0
+            JOIN `station_station_types` AS sst ON sst.station_cd = s.station_cd AND sst.type_cd = t.type_cd AND sst.pass <> 1
+            ORDER BY sst.id",
+            params
+        );
+
+        let mut query = sqlx::query_as::<_, TrainTypeRow>(&query_str);
+        for id in station_id_vec {
+            query = query.bind(id);
+        }
+        let rows = query.fetch_all(conn).await?;
+        let train_types: Vec<TrainType> = rows.into_iter().map(|row| row.into()).collect();
+
+        Ok(train_types)
+    }
     async fn get_by_line_group_id_and_line_id(
         line_group_id: u32,
         line_id: u32,
@@ -229,6 +306,57 @@ impl InternalTrainTypeRepository {
         Ok(Some(train_type))
     }
 
+    async fn get_by_line_group_id_and_line_id_vec(
+        line_group_id_and_line_id_vec: &[(u32, u32)],
+        conn: &mut MySqlConnection,
+    ) -> Result<BTreeMap<(u32, u32), TrainType>, DomainError> {
+        if line_group_id_and_line_id_vec.is_empty() {
+            return Ok(BTreeMap::new());
+        }
+
+        let keys: BTreeSet<(u32, u32)> = line_group_id_and_line_id_vec.iter().copied().collect();
+        let line_group_ids: BTreeSet<u32> = keys
+            .iter()
+            .map(|(line_group_cd, _)| *line_group_cd)
+            .collect();
+        let line_ids: BTreeSet<u32> = keys.iter().map(|(_, line_cd)| *line_cd).collect();
+
+        // NOTE: 組のIN句はインデックスが効かないので系統と路線それぞれで絞り、組はRust側で選ぶ
+        let line_group_params = format!("?{}", ", ?".repeat(line_group_ids.len() - 1));
+        let line_params = format!("?{}", ", ?".repeat(line_ids.len() - 1));
+        let query_str = format!(
+            "SELECT 
+            t.*, 
+            sst.*,
+            s.line_cd
+            FROM `types` as t
+            JOIN `station_station_types` AS sst ON sst.line_group_cd IN ( {} ) AND t.type_cd = sst.type_cd
+            JOIN `stations` AS s ON s.station_cd = sst.station_cd AND s.line_cd IN ( {} ) AND s.e_status = 0
+            ORDER BY sst.id",
+            line_group_params, line_params
+        );
+
+        let mut query = sqlx::query_as::<_, TrainTypeWithLineRow>(&query_str);
+        for id in line_group_ids.iter() {
+            query = query.bind(id);
+        }
+        for id in line_ids.iter() {
+            query = query.bind(id);
+        }
+        let rows = query.fetch_all(conn).await?;
+
+        // get_by_line_group_id_and_line_idと同じく、組ごとにsst.idが最も小さい行を使う
+        let mut train_types = BTreeMap::new();
+        for row in rows {
+            let key = (row.train_type.line_group_cd, row.line_cd);
+            if keys.contains(&key) && !train_types.contains_key(&key) {
+                train_types.insert(key, row.train_type.into());
+            }
+        }
+
+        Ok(train_types)
+    }
+
     async fn get_by_station_id_vec(
         station_id_vec: &[u32],
         line_group_id: Option<u32>,
@@ -343,4 +471,137 @@ impl InternalTrainTypeRepository {
 
         Ok(train_types)
     }
+
+    async fn find_by_type_cd(
+        type_cd: u32,
+        conn: &mut MySqlConnection,
+    ) -> Result<Option<TrainType>, DomainError> {
+        // NOTE: 駅・系統に紐づかないのでstation_cd, line_group_cd, passは0で返す
+        let row = sqlx::query_as::<_, TrainTypeRow>(
+            "SELECT
+            t.id,
+            CAST(0 AS UNSIGNED) AS station_cd,
+            t.type_cd,
+            CAST(0 AS UNSIGNED) AS line_group_cd,
+            CAST(0 AS UNSIGNED) AS pass,
+            t.type_name,
+            t.type_name_k,
+            t.type_name_r,
+            t.type_name_zh,
+            t.type_name_ko,
+            t.color,
+            t.direction,
+            t.kind
+            FROM types AS t
+            WHERE t.type_cd = ?",
+        )
+        .bind(type_cd)
+        .fetch_optional(conn)
+        .await?;
+
+        Ok(row.map(|row| row.into()))
+    }
+
+    async fn get_by_filter(
+        filter: &TrainTypeFilter,
+        conn: &mut MySqlConnection,
+    ) -> Result<Vec<TrainType>, DomainError> {
+        let name = filter.name.as_deref().map(like_contains_pattern);
+
+        // 系統ごとに最初に停車する駅の行を代表にする
+        let rows = sqlx::query_as::<_, TrainTypeRow>(
+            "SELECT
+            t.type_name,
+            t.type_name_k,
+            t.type_name_r,
+            t.type_name_zh,
+            t.type_name_ko,
+            t.color,
+            t.direction,
+            t.kind,
+            sst.*
+            FROM `station_station_types` AS sst
+            JOIN types AS t ON t.type_cd = sst.type_cd
+            WHERE sst.id IN (
+                SELECT MIN(f.id)
+                FROM `station_station_types` AS f
+                JOIN `stations` AS fs ON fs.station_cd = f.station_cd AND fs.e_status = 0
+                JOIN `lines` AS fl ON fl.line_cd = fs.line_cd AND fl.e_status = 0
+                WHERE f.pass <> 1 AND (? IS NULL OR f.type_cd = ?)
+                GROUP BY f.type_cd, f.line_group_cd
+            )
+            AND (? IS NULL OR t.kind = ?)
+            AND (
+                ? IS NULL
+                OR t.type_name LIKE ? ESCAPE '!'
+                OR t.type_name_k LIKE ? ESCAPE '!'
+                OR t.type_name_r LIKE ? ESCAPE '!'
+                OR t.type_name_zh LIKE ? ESCAPE '!'
+                OR t.type_name_ko LIKE ? ESCAPE '!'
+            )
+            AND (? IS NULL OR EXISTS (
+                SELECT 1
+                FROM `station_station_types` AS e
+                JOIN `stations` AS es ON es.station_cd = e.station_cd AND es.e_status = 0
+                JOIN `lines` AS el ON el.line_cd = es.line_cd AND el.e_status = 0
+                WHERE e.type_cd = sst.type_cd
+                AND e.line_group_cd = sst.line_group_cd
+                AND e.pass <> 1
+                AND el.line_cd = ?
+            ))
+            AND (? IS NULL OR EXISTS (
+                SELECT 1
+                FROM `station_station_types` AS e
+                JOIN `stations` AS es ON es.station_cd = e.station_cd AND es.e_status = 0
+                JOIN `lines` AS el ON el.line_cd = es.line_cd AND el.e_status = 0
+                WHERE e.type_cd = sst.type_cd
+                AND e.line_group_cd = sst.line_group_cd
+                AND e.pass <> 1
+                AND el.company_cd = ?
+            ))
+            ORDER BY sst.type_cd, sst.line_group_cd",
+        )
+        .bind(filter.type_cd)
+        .bind(filter.type_cd)
+        .bind(filter.kind)
+        .bind(filter.kind)
+        .bind(&name)
+        .bind(&name)
+        .bind(&name)
+        .bind(&name)
+        .bind(&name)
+        .bind(&name)
+        .bind(filter.line_cd)
+        .bind(filter.line_cd)
+        .bind(filter.company_cd)
+        .bind(filter.company_cd)
+        .fetch_all(conn)
+        .await?;
+
+        Ok(rows.into_iter().map(|row| row.into()).collect())
+    }
+}
+
+// NOTE: インメモリ版のcontains_ignore_caseと同じく、%や_も文字そのものとして探す
+fn like_contains_pattern(keyword: &str) -> String {
+    let mut pattern = String::from("%");
+    for c in keyword.chars() {
+        if matches!(c, '!' | '%' | '_') {
+            pattern.push('!');
+        }
+        pattern.push(c);
+    }
+    pattern.push('%');
+    pattern
+}
+
+#[cfg(test)]
+mod tests {
+    use super::like_contains_pattern;
+
+    #[test]
+    fn escape_like_wildcards() {
+        assert_eq!(like_contains_pattern("快速"), "%快速%");
+        assert_eq!(like_contains_pattern("100%_!"), "%100!%!_!!%");
+    }
 }
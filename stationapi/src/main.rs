@@ -1,22 +1,50 @@
 use sqlx::MySqlPool;
 use stationapi::{
+    domain::entity::dataset_info::DatasetInfo,
     infrastructure::{
-        company_repository::MyCompanyRepository, line_repository::MyLineRepository,
-        station_repository::MyStationRepository, train_type_repository::MyTrainTypeRepository,
+        dataset_loader::{MyDatasetLoader, MyQueryInteractor},
+        memory::{
+            dataset::Dataset,
+            dataset_loader::{InMemoryDatasetLoader, InMemoryQueryInteractor},
+        },
+    },
+    presentation::{
+        controller::{graphql, grpc::MyApi, rest},
+        dataset_version::DatasetVersionLayer,
     },
-    presentation::controller::grpc::MyApi,
     station_api::station_api_server::StationApiServer,
-    use_case::interactor::query::QueryInteractor,
+    use_case::{
+        cache::{CacheConfig, QueryCache, DEFAULT_CACHE_CAPACITY, DEFAULT_CACHE_TTL},
+        interactor::{
+            cached_query::CachedQueryInteractor, reload::ReloadInteractor,
+            reloadable_query::ReloadableQueryInteractor,
+        },
+        traits::{
+            query::QueryUseCase,
+            reload::{DatasetLoader, ReloadUseCase},
+        },
+    },
 };
 use std::sync::Arc;
 use std::{
     env::{self, VarError},
     net::{AddrParseError, SocketAddr},
+    path::Path,
+    time::Duration,
 };
 use tonic::codec::CompressionEncoding;
 use tonic::transport::Server;
-use tonic_health::server::HealthReporter;
-use tracing::{info, warn};
+use tonic_health::{
+    pb::health_server::{Health, HealthServer},
+    server::HealthReporter,
+};
+use tracing::{error, info, warn};
+
+type ReloadableQueryUseCase<T> = CachedQueryInteractor<ReloadableQueryInteractor<T>>;
+type MyQueryUseCase = ReloadableQueryUseCase<MyQueryInteractor>;
+type InMemoryQueryUseCase = ReloadableQueryUseCase<InMemoryQueryInteractor>;
+
+const DEFAULT_DATASET_VERSION_CHECK_INTERVAL_SECS: u64 = 60;
 
 async fn station_api_service_status(mut reporter: HealthReporter) {
     let db_url = fetch_database_url();
@@ -28,9 +56,13 @@ async fn station_api_service_status(mut reporter: HealthReporter) {
         .unwrap();
 
     if row.alive == 1 {
-        reporter.set_serving::<StationApiServer<MyApi>>().await;
+        reporter
+            .set_serving::<StationApiServer<MyApi<MyQueryUseCase>>>()
+            .await;
     } else {
-        reporter.set_not_serving::<StationApiServer<MyApi>>().await;
+        reporter
+            .set_not_serving::<StationApiServer<MyApi<MyQueryUseCase>>>()
+            .await;
     }
 }
 
@@ -47,32 +79,179 @@ async fn run() -> std::result::Result<(), anyhow::Error> {
     };
 
     let (mut health_reporter, health_service) = tonic_health::server::health_reporter();
+
+    let disable_grpc_web = fetch_disable_grpc_web_flag();
+    let addr = fetch_addr()?;
+    let rest_addr = fetch_rest_addr()?;
+    let query_cache = Arc::new(QueryCache::new(fetch_query_cache_config()));
+    if query_cache.is_enabled() {
+        tokio::spawn(report_query_cache_stats(Arc::clone(&query_cache)));
+    }
+
+    if fetch_use_in_memory_dataset_flag() {
+        let dataset_path = fetch_dataset_path();
+        let dataset = Arc::new(Dataset::load(Path::new(&dataset_path))?);
+        info!("Loaded the dataset from {}", dataset_path);
+
+        let query_interactor = InMemoryDatasetLoader::build_query_interactor(dataset);
+        let dataset_info = query_interactor.get_dataset_info().await?;
+        let my_api = build_reloadable_api(
+            InMemoryDatasetLoader,
+            query_interactor,
+            dataset_info,
+            dataset_path,
+            query_cache,
+            None,
+        );
+
+        health_reporter
+            .set_serving::<StationApiServer<MyApi<InMemoryQueryUseCase>>>()
+            .await;
+
+        return serve(my_api, health_service, addr, rest_addr, disable_grpc_web).await;
+    }
+
     health_reporter
-        .set_serving::<StationApiServer<MyApi>>()
+        .set_serving::<StationApiServer<MyApi<MyQueryUseCase>>>()
         .await;
 
     tokio::spawn(station_api_service_status(health_reporter.clone()));
 
-    let disable_grpc_web = fetch_disable_grpc_web_flag();
-    let addr = fetch_addr()?;
-
     let db_url = fetch_database_url();
     let pool = Arc::new(MySqlPool::connect(db_url.as_str()).await?);
 
-    let station_repository = MyStationRepository::new(Arc::clone(&pool));
-    let line_repository = MyLineRepository::new(Arc::clone(&pool));
-    let train_type_repository = MyTrainTypeRepository::new(Arc::clone(&pool));
-    let company_repository = MyCompanyRepository::new(Arc::clone(&pool));
+    let query_interactor = MyDatasetLoader::build_query_interactor(pool);
+    let dataset_info = query_interactor.get_dataset_info().await?;
+    let my_api = build_reloadable_api(
+        MyDatasetLoader,
+        query_interactor,
+        dataset_info,
+        db_url,
+        query_cache,
+        fetch_dataset_version_check_interval(),
+    );
+
+    serve(my_api, health_service, addr, rest_addr, disable_grpc_web).await
+}
+
+// NOTE: 起動時のデータはそのまま使い、リロードするときだけ検証する
+fn build_reloadable_api<L: DatasetLoader>(
+    loader: L,
+    query_interactor: L::QueryUseCase,
+    dataset_info: Option<DatasetInfo>,
+    source: String,
+    query_cache: Arc<QueryCache>,
+    version_check_interval: Option<Duration>,
+) -> MyApi<ReloadableQueryUseCase<L::QueryUseCase>> {
+    let reloadable = ReloadableQueryInteractor::new(query_interactor, dataset_info);
+    let reload_use_case: Arc<dyn ReloadUseCase> = Arc::new(ReloadInteractor::new(
+        loader,
+        reloadable.clone(),
+        Arc::clone(&query_cache),
+        source,
+    ));
+
+    #[cfg(unix)]
+    tokio::spawn(reload_dataset_on_sighup(Arc::clone(&reload_use_case)));
+    if let Some(interval) = version_check_interval {
+        tokio::spawn(reload_dataset_on_version_change(
+            Arc::clone(&reload_use_case),
+            interval,
+        ));
+    }
+
+    MyApi {
+        query_use_case: Arc::new(CachedQueryInteractor::new(reloadable, query_cache)),
+        reload_use_case: Some(reload_use_case),
+        admin_token: fetch_admin_token(),
+    }
+}
 
-    let query_use_case = QueryInteractor {
-        station_repository,
-        line_repository,
-        train_type_repository,
-        company_repository,
+#[cfg(unix)]
+async fn reload_dataset_on_sighup(reload_use_case: Arc<dyn ReloadUseCase>) {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut hangup = match signal(SignalKind::hangup()) {
+        Ok(hangup) => hangup,
+        Err(err) => {
+            error!("Failed to listen for SIGHUP: {}", err);
+            return;
+        }
     };
+    while hangup.recv().await.is_some() {
+        info!("Received SIGHUP. Reloading the dataset.");
+        match reload_use_case.reload_dataset(None).await {
+            Ok(summary) => info!(
+                "Reloaded the dataset: {} stations, {} lines",
+                summary.stations, summary.lines
+            ),
+            Err(err) => error!("Failed to reload the dataset: {}", err),
+        }
+    }
+}
+
+// NOTE: DBにmigrationが入ったら、SIGHUPを送らなくても索引ごと読み込み直す
+async fn reload_dataset_on_version_change(
+    reload_use_case: Arc<dyn ReloadUseCase>,
+    interval: Duration,
+) {
+    let mut interval = tokio::time::interval(interval);
+    // NOTE: 最初のtickはすぐに来るが、起動直後に読み込んだばかりなので飛ばす
+    interval.tick().await;
+    loop {
+        interval.tick().await;
+        match reload_use_case.reload_dataset_if_changed().await {
+            Ok(Some(summary)) => info!(
+                "The dataset version changed. Reloaded the dataset: {} stations, {} lines",
+                summary.stations, summary.lines
+            ),
+            Ok(None) => {}
+            Err(err) => error!("Failed to reload the changed dataset: {}", err),
+        }
+    }
+}
 
-    let my_api = MyApi { query_use_case };
+async fn report_query_cache_stats(query_cache: Arc<QueryCache>) {
+    let mut interval = tokio::time::interval(Duration::from_secs(60));
+    loop {
+        interval.tick().await;
+        let stats = query_cache.stats();
+        info!(
+            "Query cache: {} entries, {} hits, {} misses ({:.1}% hit ratio), {} evictions, {} invalidations",
+            stats.entries,
+            stats.hits,
+            stats.misses,
+            stats.hit_ratio() * 100.0,
+            stats.evictions,
+            stats.invalidations
+        );
+    }
+}
+
+async fn serve<T: QueryUseCase>(
+    my_api: MyApi<T>,
+    health_service: HealthServer<impl Health>,
+    addr: SocketAddr,
+    rest_addr: Option<SocketAddr>,
+    disable_grpc_web: bool,
+) -> std::result::Result<(), anyhow::Error> {
+    if let Some(rest_addr) = rest_addr {
+        let listener = tokio::net::TcpListener::bind(rest_addr).await?;
+        let router = rest::router(Arc::clone(&my_api.query_use_case))
+            .merge(graphql::router(Arc::clone(&my_api.query_use_case)))
+            .layer(DatasetVersionLayer::new(Arc::clone(&my_api.query_use_case)));
+        info!(
+            "REST gateway and GraphQL endpoint listening on {}",
+            rest_addr
+        );
+        tokio::spawn(async move {
+            if let Err(err) = axum::serve(listener, router).await {
+                error!("REST gateway stopped: {}", err);
+            }
+        });
+    }
 
+    let dataset_version_layer = DatasetVersionLayer::new(Arc::clone(&my_api.query_use_case));
     let svc = StationApiServer::new(my_api)
         .accept_compressed(CompressionEncoding::Zstd)
         .send_compressed(CompressionEncoding::Zstd);
@@ -81,6 +260,7 @@ async fn run() -> std::result::Result<(), anyhow::Error> {
 
     if disable_grpc_web {
         Server::builder()
+            .layer(dataset_version_layer)
             .add_service(health_service)
             .add_service(svc)
             .serve(addr)
@@ -88,6 +268,7 @@ async fn run() -> std::result::Result<(), anyhow::Error> {
     } else {
         Server::builder()
             .accept_http1(true)
+            .layer(dataset_version_layer)
             .add_service(tonic_web::enable(health_service))
             .add_service(tonic_web::enable(svc))
             .serve(addr)
@@ -121,6 +302,20 @@ fn fetch_addr() -> Result<SocketAddr, AddrParseError> {
     }
 }
 
+// NOTE: $REST_PORTを指定したときだけREST/JSONゲートウェイと/graphqlを立ち上げる
+fn fetch_rest_addr() -> Result<Option<SocketAddr>, AddrParseError> {
+    let port: u16 = match env::var("REST_PORT") {
+        Ok(s) => s.parse().expect("Failed to parse $REST_PORT"),
+        Err(env::VarError::NotPresent) => return Ok(None),
+        Err(VarError::NotUnicode(_)) => panic!("$REST_PORT should be written in Unicode."),
+    };
+    match env::var("HOST") {
+        Ok(s) => format!("{}:{}", s, port).parse().map(Some),
+        Err(env::VarError::NotPresent) => format!("[::1]:{}", port).parse().map(Some),
+        Err(VarError::NotUnicode(_)) => panic!("$HOST should be written in Unicode."),
+    }
+}
+
 fn fetch_database_url() -> String {
     match env::var("DATABASE_URL") {
         Ok(s) => s,
@@ -138,3 +333,65 @@ fn fetch_disable_grpc_web_flag() -> bool {
         Err(VarError::NotUnicode(_)) => panic!("$DISABLE_GRPC_WEB should be written in Unicode."),
     }
 }
+
+fn fetch_use_in_memory_dataset_flag() -> bool {
+    match env::var("USE_IN_MEMORY_DATASET") {
+        Ok(s) => s.parse().expect("Failed to parse $USE_IN_MEMORY_DATASET"),
+        Err(env::VarError::NotPresent) => false,
+        Err(VarError::NotUnicode(_)) => {
+            panic!("$USE_IN_MEMORY_DATASET should be written in Unicode.")
+        }
+    }
+}
+
+fn fetch_dataset_path() -> String {
+    match env::var("DATASET_PATH") {
+        Ok(s) => s,
+        Err(env::VarError::NotPresent) => {
+            warn!("$DATASET_PATH is not set. Falling back to ./data.");
+            "data".to_string()
+        }
+        Err(VarError::NotUnicode(_)) => panic!("$DATASET_PATH should be written in Unicode."),
+    }
+}
+
+// NOTE: $QUERY_CACHE_CAPACITYに0を指定するとキャッシュしない
+fn fetch_query_cache_config() -> CacheConfig {
+    let capacity = match env::var("QUERY_CACHE_CAPACITY") {
+        Ok(s) => s.parse().expect("Failed to parse $QUERY_CACHE_CAPACITY"),
+        Err(env::VarError::NotPresent) => DEFAULT_CACHE_CAPACITY,
+        Err(VarError::NotUnicode(_)) => {
+            panic!("$QUERY_CACHE_CAPACITY should be written in Unicode.")
+        }
+    };
+    let ttl = match env::var("QUERY_CACHE_TTL_SECS") {
+        Ok(s) => Duration::from_secs(s.parse().expect("Failed to parse $QUERY_CACHE_TTL_SECS")),
+        Err(env::VarError::NotPresent) => DEFAULT_CACHE_TTL,
+        Err(VarError::NotUnicode(_)) => {
+            panic!("$QUERY_CACHE_TTL_SECS should be written in Unicode.")
+        }
+    };
+    CacheConfig { capacity, ttl }
+}
+
+// NOTE: $DATASET_VERSION_CHECK_INTERVAL_SECSに0を指定するとバージョンの変更を見に行かない
+fn fetch_dataset_version_check_interval() -> Option<Duration> {
+    let secs: u64 = match env::var("DATASET_VERSION_CHECK_INTERVAL_SECS") {
+        Ok(s) => s
+            .parse()
+            .expect("Failed to parse $DATASET_VERSION_CHECK_INTERVAL_SECS"),
+        Err(env::VarError::NotPresent) => DEFAULT_DATASET_VERSION_CHECK_INTERVAL_SECS,
+        Err(VarError::NotUnicode(_)) => {
+            panic!("$DATASET_VERSION_CHECK_INTERVAL_SECS should be written in Unicode.")
+        }
+    };
+    (secs > 0).then(|| Duration::from_secs(secs))
+}
+
+fn fetch_admin_token() -> Option<String> {
+    match env::var("ADMIN_TOKEN") {
+        Ok(s) if !s.is_empty() => Some(s),
+        Ok(_) | Err(env::VarError::NotPresent) => None,
+        Err(VarError::NotUnicode(_)) => panic!("$ADMIN_TOKEN should be written in Unicode."),
+    }
+}
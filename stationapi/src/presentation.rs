@@ -1,2 +1,3 @@
 pub mod controller;
+pub mod dataset_version;
 pub mod error;
@@ -1 +1,3 @@
+pub mod graphql;
 pub mod grpc;
+pub mod rest;
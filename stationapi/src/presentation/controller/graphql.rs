@@ -0,0 +1,872 @@
+use std::{collections::HashMap, sync::Arc};
+
+use async_graphql::{
+    dataloader::{DataLoader, Loader},
+    http::GraphiQLSource,
+    Context, EmptyMutation, EmptySubscription, Enum, ErrorExtensions, Object, Schema, SimpleObject,
+};
+use axum::{extract::State, response::Html, routing::get, Json, Router};
+
+use crate::{
+    domain::entity::{
+        company::Company,
+        dataset_info::DatasetInfo,
+        line::Line,
+        line_symbol::LineSymbol,
+        misc::{CompanyFilter, TrainTypeFilter},
+        prefecture::Prefecture,
+        station::Station,
+        station_number::StationNumber,
+        train_type::TrainType,
+    },
+    presentation::{
+        controller::grpc::{parse_as_of, parse_service_day},
+        error::PresentationalError,
+    },
+    station_api::ServiceDayType,
+    use_case::{error::UseCaseError, traits::query::QueryUseCase},
+};
+
+// NOTE: Station.lines[].stations[].lines[]...のような入れ子を際限なく辿らせない
+const MAX_QUERY_DEPTH: usize = 10;
+// NOTE: 駅と路線の属性をひととおり取っても収まり、一覧を4重に入れ子にすると超える程度
+const MAX_QUERY_COMPLEXITY: usize = 5000;
+// 一覧を返す属性は件数が読めないので、子の複雑度をこの倍数で見積もる
+const LIST_COMPLEXITY: usize = 10;
+
+type SharedQueryUseCase = Arc<dyn QueryUseCase>;
+type GraphQLResult<T> = async_graphql::Result<T>;
+
+pub type StationSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+#[derive(Clone)]
+struct GraphQLState {
+    schema: StationSchema,
+    query_use_case: SharedQueryUseCase,
+}
+
+// POST /graphql でクエリを実行し、GET /graphql でGraphiQLを返す
+pub fn router<T: QueryUseCase>(query_use_case: Arc<T>) -> Router {
+    let query_use_case: SharedQueryUseCase = query_use_case;
+    let schema = build_schema(Arc::clone(&query_use_case));
+
+    Router::new()
+        .route("/graphql", get(graphiql).post(execute))
+        .with_state(GraphQLState {
+            schema,
+            query_use_case,
+        })
+}
+
+fn build_schema(query_use_case: SharedQueryUseCase) -> StationSchema {
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .data(query_use_case)
+        .limit_depth(MAX_QUERY_DEPTH)
+        .limit_complexity(MAX_QUERY_COMPLEXITY)
+        .finish()
+}
+
+async fn graphiql() -> Html<String> {
+    Html(GraphiQLSource::build().endpoint("/graphql").finish())
+}
+
+async fn execute(
+    State(state): State<GraphQLState>,
+    Json(request): Json<async_graphql::Request>,
+) -> Json<async_graphql::Response> {
+    let request = with_loaders(request, &state.query_use_case);
+    Json(state.schema.execute(request).await)
+}
+
+// DataLoaderはリクエストごとに作り、同じリクエスト内の駅や路線の問い合わせだけをまとめる
+fn with_loaders(
+    request: async_graphql::Request,
+    query_use_case: &SharedQueryUseCase,
+) -> async_graphql::Request {
+    request
+        .data(DataLoader::new(
+            LinesByStationGroupLoader(Arc::clone(query_use_case)),
+            tokio::spawn,
+        ))
+        .data(DataLoader::new(
+            LinesByLineGroupLoader(Arc::clone(query_use_case)),
+            tokio::spawn,
+        ))
+        .data(DataLoader::new(
+            CompanyLoader(Arc::clone(query_use_case)),
+            tokio::spawn,
+        ))
+        .data(DataLoader::new(
+            TrainTypesByStationLoader(Arc::clone(query_use_case)),
+            tokio::spawn,
+        ))
+        .data(DataLoader::new(
+            StationsByLineLoader(Arc::clone(query_use_case)),
+            tokio::spawn,
+        ))
+        .data(DataLoader::new(
+            StationsByLineGroupLoader(Arc::clone(query_use_case)),
+            tokio::spawn,
+        ))
+}
+
+impl ErrorExtensions for PresentationalError {
+    fn extend(&self) -> async_graphql::Error {
+        let code = match self {
+            PresentationalError::NotFound(_) => "NOT_FOUND",
+            PresentationalError::InvalidArgument(_) => "INVALID_ARGUMENT",
+            PresentationalError::PermissionDenied(_) => "PERMISSION_DENIED",
+            PresentationalError::OtherError(_) | PresentationalError::Unexpected(_) => "INTERNAL",
+        };
+        async_graphql::Error::new(self.to_string()).extend_with(|_, e| e.set("code", code))
+    }
+}
+
+fn graphql_error(err: UseCaseError) -> async_graphql::Error {
+    PresentationalError::from(err).extend()
+}
+
+fn query_use_case<'a>(ctx: &Context<'a>) -> &'a SharedQueryUseCase {
+    ctx.data_unchecked::<SharedQueryUseCase>()
+}
+
+pub struct LinesByStationGroupLoader(SharedQueryUseCase);
+
+impl Loader<u32> for LinesByStationGroupLoader {
+    type Value = Vec<Line>;
+    type Error = PresentationalError;
+
+    async fn load(&self, keys: &[u32]) -> Result<HashMap<u32, Self::Value>, Self::Error> {
+        let lines = self.0.get_lines_by_station_group_id_vec(keys).await?;
+
+        let mut lines_by_group_id: HashMap<u32, Vec<Line>> = HashMap::new();
+        for line in lines {
+            if let Some(station_group_id) = line.station_g_cd {
+                lines_by_group_id
+                    .entry(station_group_id)
+                    .or_default()
+                    .push(line);
+            }
+        }
+        Ok(lines_by_group_id)
+    }
+}
+
+pub struct LinesByLineGroupLoader(SharedQueryUseCase);
+
+impl Loader<u32> for LinesByLineGroupLoader {
+    type Value = Vec<Line>;
+    type Error = PresentationalError;
+
+    async fn load(&self, keys: &[u32]) -> Result<HashMap<u32, Self::Value>, Self::Error> {
+        let lines = self.0.get_lines_by_line_group_id_vec(keys).await?;
+
+        let mut lines_by_group_id: HashMap<u32, Vec<Line>> = HashMap::new();
+        for line in lines {
+            if let Some(line_group_id) = line.line_group_cd {
+                lines_by_group_id
+                    .entry(line_group_id)
+                    .or_default()
+                    .push(line);
+            }
+        }
+        Ok(lines_by_group_id)
+    }
+}
+
+pub struct CompanyLoader(SharedQueryUseCase);
+
+impl Loader<u32> for CompanyLoader {
+    type Value = Company;
+    type Error = PresentationalError;
+
+    async fn load(&self, keys: &[u32]) -> Result<HashMap<u32, Self::Value>, Self::Error> {
+        let companies = self.0.find_company_by_id_vec(keys).await?;
+
+        Ok(companies
+            .into_iter()
+            .map(|company| (company.company_cd, company))
+            .collect())
+    }
+}
+
+pub struct TrainTypesByStationLoader(SharedQueryUseCase);
+
+impl Loader<u32> for TrainTypesByStationLoader {
+    type Value = Vec<TrainType>;
+    type Error = PresentationalError;
+
+    async fn load(&self, keys: &[u32]) -> Result<HashMap<u32, Self::Value>, Self::Error> {
+        let train_types = self.0.get_all_train_types_by_station_id_vec(keys).await?;
+
+        let mut train_types_by_station_id: HashMap<u32, Vec<TrainType>> = HashMap::new();
+        for train_type in train_types {
+            train_types_by_station_id
+                .entry(train_type.station_cd)
+                .or_default()
+                .push(train_type);
+        }
+        Ok(train_types_by_station_id)
+    }
+}
+
+pub struct StationsByLineLoader(SharedQueryUseCase);
+
+impl Loader<u32> for StationsByLineLoader {
+    type Value = Vec<Station>;
+    type Error = PresentationalError;
+
+    async fn load(&self, keys: &[u32]) -> Result<HashMap<u32, Self::Value>, Self::Error> {
+        let stations = self
+            .0
+            .get_stations_by_line_id_vec_without_attributes(keys)
+            .await?;
+
+        let mut stations_by_line_id: HashMap<u32, Vec<Station>> = HashMap::new();
+        for station in stations {
+            stations_by_line_id
+                .entry(station.line_cd)
+                .or_default()
+                .push(station);
+        }
+        Ok(stations_by_line_id)
+    }
+}
+
+pub struct StationsByLineGroupLoader(SharedQueryUseCase);
+
+impl Loader<u32> for StationsByLineGroupLoader {
+    type Value = Vec<Station>;
+    type Error = PresentationalError;
+
+    async fn load(&self, keys: &[u32]) -> Result<HashMap<u32, Self::Value>, Self::Error> {
+        let stations = self
+            .0
+            .get_stations_by_line_group_id_vec_without_attributes(keys)
+            .await?;
+
+        let mut stations_by_line_group_id: HashMap<u32, Vec<Station>> = HashMap::new();
+        for station in stations {
+            if let Some(line_group_id) = station.line_group_cd {
+                stations_by_line_group_id
+                    .entry(line_group_id)
+                    .or_default()
+                    .push(station);
+            }
+        }
+        Ok(stations_by_line_group_id)
+    }
+}
+
+#[derive(Enum, Clone, Copy, PartialEq, Eq)]
+#[graphql(name = "ServiceDay")]
+pub enum ServiceDayKind {
+    Weekday,
+    Holiday,
+}
+
+impl From<ServiceDayKind> for ServiceDayType {
+    fn from(kind: ServiceDayKind) -> Self {
+        match kind {
+            ServiceDayKind::Weekday => ServiceDayType::WeekdayService,
+            ServiceDayKind::Holiday => ServiceDayType::HolidayService,
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+#[graphql(name = "StationNumber")]
+pub struct StationNumberNode {
+    line_symbol: String,
+    line_symbol_color: String,
+    line_symbol_shape: String,
+    station_number: String,
+}
+
+impl From<StationNumber> for StationNumberNode {
+    fn from(station_number: StationNumber) -> Self {
+        Self {
+            line_symbol: station_number.line_symbol,
+            line_symbol_color: station_number.line_symbol_color,
+            line_symbol_shape: station_number.line_symbol_shape,
+            station_number: station_number.station_number,
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+#[graphql(name = "DatasetInfo")]
+pub struct DatasetInfoNode {
+    version: String,
+    content_hash: String,
+    created_at: u64,
+}
+
+impl From<DatasetInfo> for DatasetInfoNode {
+    fn from(info: DatasetInfo) -> Self {
+        Self {
+            version: info.version,
+            content_hash: info.content_hash,
+            created_at: info.created_at,
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+#[graphql(name = "LineSymbol")]
+pub struct LineSymbolNode {
+    symbol: String,
+    color: String,
+    shape: String,
+}
+
+impl From<LineSymbol> for LineSymbolNode {
+    fn from(line_symbol: LineSymbol) -> Self {
+        Self {
+            symbol: line_symbol.symbol,
+            color: line_symbol.color,
+            shape: line_symbol.shape,
+        }
+    }
+}
+
+pub struct StationNode(Station);
+
+#[Object(name = "Station")]
+impl StationNode {
+    async fn id(&self) -> u32 {
+        self.0.station_cd
+    }
+    async fn group_id(&self) -> u32 {
+        self.0.station_g_cd
+    }
+    async fn name(&self) -> &str {
+        &self.0.station_name
+    }
+    async fn name_katakana(&self) -> &str {
+        &self.0.station_name_k
+    }
+    async fn name_roman(&self) -> Option<&str> {
+        self.0.station_name_r.as_deref()
+    }
+    async fn name_chinese(&self) -> Option<&str> {
+        self.0.station_name_zh.as_deref()
+    }
+    async fn name_korean(&self) -> Option<&str> {
+        self.0.station_name_ko.as_deref()
+    }
+    async fn three_letter_code(&self) -> Option<&str> {
+        self.0.three_letter_code.as_deref()
+    }
+    async fn station_numbers(&self, ctx: &Context<'_>) -> Vec<StationNumberNode> {
+        query_use_case(ctx)
+            .get_station_numbers(&self.0)
+            .into_iter()
+            .map(StationNumberNode::from)
+            .collect()
+    }
+    async fn prefecture_id(&self) -> u32 {
+        self.0.pref_cd
+    }
+    async fn postal_code(&self) -> &str {
+        &self.0.post
+    }
+    async fn address(&self) -> &str {
+        &self.0.address
+    }
+    async fn latitude(&self) -> f64 {
+        self.0.lat
+    }
+    async fn longitude(&self) -> f64 {
+        self.0.lon
+    }
+    async fn opened_at(&self) -> &str {
+        &self.0.open_ymd
+    }
+    async fn closed_at(&self) -> &str {
+        &self.0.close_ymd
+    }
+    async fn status(&self) -> u32 {
+        self.0.e_status
+    }
+    async fn stop_condition(&self) -> &str {
+        self.0.stop_condition.as_str_name()
+    }
+    async fn distance(&self) -> Option<f64> {
+        self.0.distance
+    }
+    // 駅が属する路線。駅のレコードにJOIN済みなので問い合わせない
+    async fn line(&self, ctx: &Context<'_>) -> LineNode {
+        LineNode(query_use_case(ctx).extract_line_from_station(&self.0))
+    }
+    // 同じ駅グループに乗り入れるすべての路線
+    #[graphql(complexity = "LIST_COMPLEXITY * child_complexity")]
+    async fn lines(&self, ctx: &Context<'_>) -> GraphQLResult<Vec<LineNode>> {
+        let lines = ctx
+            .data_unchecked::<DataLoader<LinesByStationGroupLoader>>()
+            .load_one(self.0.station_g_cd)
+            .await
+            .map_err(|err| err.extend())?
+            .unwrap_or_default();
+
+        Ok(lines.into_iter().map(LineNode).collect())
+    }
+    // 系統ごとの駅一覧で取得したときの、その系統の種別
+    async fn train_type(&self) -> Option<TrainTypeNode> {
+        let station = &self.0;
+        Some(TrainTypeNode(TrainType::new(
+            station.sst_id?,
+            station.station_cd,
+            station.type_cd?,
+            station.line_group_cd?,
+            station.pass.unwrap_or(0),
+            station.type_name.clone()?,
+            station.type_name_k.clone()?,
+            station.type_name_r.clone(),
+            station.type_name_zh.clone(),
+            station.type_name_ko.clone(),
+            station.color.clone()?,
+            station.direction.unwrap_or(0),
+            station.kind.unwrap_or(0),
+        )))
+    }
+    // この駅に停車するすべての種別
+    #[graphql(complexity = "LIST_COMPLEXITY * child_complexity")]
+    async fn train_types(&self, ctx: &Context<'_>) -> GraphQLResult<Vec<TrainTypeNode>> {
+        let train_types = ctx
+            .data_unchecked::<DataLoader<TrainTypesByStationLoader>>()
+            .load_one(self.0.station_cd)
+            .await
+            .map_err(|err| err.extend())?
+            .unwrap_or_default();
+
+        Ok(train_types.into_iter().map(TrainTypeNode).collect())
+    }
+}
+
+pub struct LineNode(Line);
+
+#[Object(name = "Line")]
+impl LineNode {
+    async fn id(&self) -> u32 {
+        self.0.line_cd
+    }
+    async fn name(&self) -> &str {
+        &self.0.line_name
+    }
+    async fn name_katakana(&self) -> &str {
+        &self.0.line_name_k
+    }
+    async fn name_full(&self) -> &str {
+        &self.0.line_name_h
+    }
+    async fn name_roman(&self) -> Option<&str> {
+        self.0.line_name_r.as_deref()
+    }
+    async fn name_chinese(&self) -> Option<&str> {
+        self.0.line_name_zh.as_deref()
+    }
+    async fn name_korean(&self) -> Option<&str> {
+        self.0.line_name_ko.as_deref()
+    }
+    async fn color(&self) -> Option<&str> {
+        self.0.line_color_c.as_deref()
+    }
+    async fn line_type(&self) -> Option<u32> {
+        self.0.line_type
+    }
+    async fn line_symbols(&self, ctx: &Context<'_>) -> Vec<LineSymbolNode> {
+        query_use_case(ctx)
+            .get_line_symbols(&self.0)
+            .into_iter()
+            .map(LineSymbolNode::from)
+            .collect()
+    }
+    async fn status(&self) -> u32 {
+        self.0.e_status
+    }
+    async fn line_group_id(&self) -> Option<u32> {
+        self.0.line_group_cd
+    }
+    async fn company(&self, ctx: &Context<'_>) -> GraphQLResult<Option<CompanyNode>> {
+        let company = ctx
+            .data_unchecked::<DataLoader<CompanyLoader>>()
+            .load_one(self.0.company_cd)
+            .await
+            .map_err(|err| err.extend())?;
+
+        Ok(company.map(CompanyNode))
+    }
+    #[graphql(complexity = "LIST_COMPLEXITY * child_complexity")]
+    async fn stations(&self, ctx: &Context<'_>) -> GraphQLResult<Vec<StationNode>> {
+        let stations = ctx
+            .data_unchecked::<DataLoader<StationsByLineLoader>>()
+            .load_one(self.0.line_cd)
+            .await
+            .map_err(|err| err.extend())?
+            .unwrap_or_default();
+
+        Ok(stations.into_iter().map(StationNode).collect())
+    }
+}
+
+pub struct TrainTypeNode(TrainType);
+
+#[Object(name = "TrainType")]
+impl TrainTypeNode {
+    async fn id(&self) -> u32 {
+        self.0.id
+    }
+    async fn type_id(&self) -> u32 {
+        self.0.type_cd
+    }
+    async fn line_group_id(&self) -> u32 {
+        self.0.line_group_cd
+    }
+    async fn name(&self) -> &str {
+        &self.0.type_name
+    }
+    async fn name_katakana(&self) -> &str {
+        &self.0.type_name_k
+    }
+    async fn name_roman(&self) -> Option<&str> {
+        self.0.type_name_r.as_deref()
+    }
+    async fn name_chinese(&self) -> Option<&str> {
+        self.0.type_name_zh.as_deref()
+    }
+    async fn name_korean(&self) -> Option<&str> {
+        self.0.type_name_ko.as_deref()
+    }
+    async fn color(&self) -> &str {
+        &self.0.color
+    }
+    async fn direction(&self) -> u32 {
+        self.0.direction
+    }
+    async fn kind(&self) -> u32 {
+        self.0.kind
+    }
+    // 系統が走る路線。種別コードで取得したときは全系統の路線をまとめて持っている
+    #[graphql(complexity = "LIST_COMPLEXITY * child_complexity")]
+    async fn lines(&self, ctx: &Context<'_>) -> GraphQLResult<Vec<LineNode>> {
+        if !self.0.lines.is_empty() {
+            return Ok(self.0.lines.iter().cloned().map(LineNode).collect());
+        }
+
+        let lines = ctx
+            .data_unchecked::<DataLoader<LinesByLineGroupLoader>>()
+            .load_one(self.0.line_group_cd)
+            .await
+            .map_err(|err| err.extend())?
+            .unwrap_or_default();
+
+        Ok(lines.into_iter().map(LineNode).collect())
+    }
+    #[graphql(complexity = "LIST_COMPLEXITY * child_complexity")]
+    async fn stations(&self, ctx: &Context<'_>) -> GraphQLResult<Vec<StationNode>> {
+        let stations = ctx
+            .data_unchecked::<DataLoader<StationsByLineGroupLoader>>()
+            .load_one(self.0.line_group_cd)
+            .await
+            .map_err(|err| err.extend())?
+            .unwrap_or_default();
+
+        Ok(stations.into_iter().map(StationNode).collect())
+    }
+}
+
+pub struct CompanyNode(Company);
+
+#[Object(name = "Company")]
+impl CompanyNode {
+    async fn id(&self) -> u32 {
+        self.0.company_cd
+    }
+    async fn railroad_id(&self) -> u32 {
+        self.0.rr_cd
+    }
+    async fn name_short(&self) -> &str {
+        &self.0.company_name
+    }
+    async fn name_katakana(&self) -> &str {
+        &self.0.company_name_k
+    }
+    async fn name_full(&self) -> &str {
+        &self.0.company_name_h
+    }
+    async fn name_english_short(&self) -> &str {
+        &self.0.company_name_en
+    }
+    async fn name_english_full(&self) -> &str {
+        &self.0.company_name_full_en
+    }
+    async fn url(&self) -> Option<&str> {
+        self.0.company_url.as_deref()
+    }
+    async fn company_type(&self) -> u32 {
+        self.0.company_type
+    }
+    async fn status(&self) -> u32 {
+        self.0.e_status
+    }
+    #[graphql(complexity = "LIST_COMPLEXITY * child_complexity")]
+    async fn lines(&self, ctx: &Context<'_>) -> GraphQLResult<Vec<LineNode>> {
+        let lines = query_use_case(ctx)
+            .get_lines_by_company_id(self.0.company_cd, None)
+            .await
+            .map_err(graphql_error)?;
+
+        Ok(lines.into_iter().map(LineNode).collect())
+    }
+}
+
+pub struct PrefectureNode(Prefecture);
+
+#[Object(name = "Prefecture")]
+impl PrefectureNode {
+    async fn id(&self) -> u32 {
+        self.0.pref_cd
+    }
+    async fn name(&self) -> &str {
+        &self.0.pref_name
+    }
+    async fn name_katakana(&self) -> &str {
+        &self.0.pref_name_k
+    }
+    async fn name_roman(&self) -> &str {
+        &self.0.pref_name_r
+    }
+    async fn name_chinese(&self) -> &str {
+        &self.0.pref_name_zh
+    }
+    async fn name_korean(&self) -> &str {
+        &self.0.pref_name_ko
+    }
+}
+
+pub struct QueryRoot;
+
+// NOTE: 入れ子の属性はクライアントが要求したときだけ解決するので、ここでは駅や路線の素のレコードだけを返す
+#[Object]
+impl QueryRoot {
+    async fn station(&self, ctx: &Context<'_>, id: u32) -> GraphQLResult<Option<StationNode>> {
+        let stations = query_use_case(ctx)
+            .get_stations_by_id_vec_without_attributes(&[id])
+            .await
+            .map_err(graphql_error)?;
+
+        Ok(stations.into_iter().next().map(StationNode))
+    }
+    #[graphql(complexity = "LIST_COMPLEXITY * child_complexity")]
+    async fn stations(&self, ctx: &Context<'_>, ids: Vec<u32>) -> GraphQLResult<Vec<StationNode>> {
+        let stations = query_use_case(ctx)
+            .get_stations_by_id_vec_without_attributes(&ids)
+            .await
+            .map_err(graphql_error)?;
+
+        Ok(stations.into_iter().map(StationNode).collect())
+    }
+    #[graphql(complexity = "LIST_COMPLEXITY * child_complexity")]
+    async fn stations_by_group_id(
+        &self,
+        ctx: &Context<'_>,
+        group_id: u32,
+    ) -> GraphQLResult<Vec<StationNode>> {
+        let stations = query_use_case(ctx)
+            .get_stations_by_group_id_vec(&[group_id])
+            .await
+            .map_err(graphql_error)?;
+
+        Ok(stations.into_iter().map(StationNode).collect())
+    }
+    #[graphql(complexity = "LIST_COMPLEXITY * child_complexity")]
+    async fn stations_nearby(
+        &self,
+        ctx: &Context<'_>,
+        latitude: f64,
+        longitude: f64,
+        limit: Option<u32>,
+    ) -> GraphQLResult<Vec<StationNode>> {
+        let stations = query_use_case(ctx)
+            .get_stations_by_coordinates_without_attributes(latitude, longitude, limit)
+            .await
+            .map_err(graphql_error)?;
+
+        Ok(stations.into_iter().map(StationNode).collect())
+    }
+    #[graphql(complexity = "LIST_COMPLEXITY * child_complexity")]
+    async fn line_group_stations(
+        &self,
+        ctx: &Context<'_>,
+        line_group_id: u32,
+        service_day: Option<ServiceDayKind>,
+        service_date: Option<String>,
+    ) -> GraphQLResult<Vec<StationNode>> {
+        let service_day = parse_service_day(service_day.map(Into::into), service_date.as_deref())
+            .map_err(|err| err.extend())?;
+        let stations = query_use_case(ctx)
+            .get_stations_by_line_group_id_without_attributes(line_group_id, service_day)
+            .await
+            .map_err(graphql_error)?;
+
+        Ok(stations.into_iter().map(StationNode).collect())
+    }
+    async fn line(
+        &self,
+        ctx: &Context<'_>,
+        id: u32,
+        as_of: Option<String>,
+    ) -> GraphQLResult<Option<LineNode>> {
+        let as_of = parse_as_of(as_of.as_deref()).map_err(|err| err.extend())?;
+        let line = query_use_case(ctx)
+            .find_line_by_id(id, as_of.as_ref())
+            .await
+            .map_err(graphql_error)?;
+
+        Ok(line.map(LineNode))
+    }
+    #[graphql(complexity = "LIST_COMPLEXITY * child_complexity")]
+    async fn lines_by_name(
+        &self,
+        ctx: &Context<'_>,
+        name: String,
+        limit: Option<u32>,
+    ) -> GraphQLResult<Vec<LineNode>> {
+        let lines = query_use_case(ctx)
+            .get_lines_by_name(name, limit)
+            .await
+            .map_err(graphql_error)?;
+
+        Ok(lines.into_iter().map(LineNode).collect())
+    }
+    async fn company(&self, ctx: &Context<'_>, id: u32) -> GraphQLResult<Option<CompanyNode>> {
+        let company = query_use_case(ctx)
+            .find_company_by_id(id)
+            .await
+            .map_err(graphql_error)?;
+
+        Ok(company.map(CompanyNode))
+    }
+    #[graphql(complexity = "LIST_COMPLEXITY * child_complexity")]
+    async fn companies(
+        &self,
+        ctx: &Context<'_>,
+        company_type: Option<u32>,
+        railroad_id: Option<u32>,
+        status: Option<u32>,
+    ) -> GraphQLResult<Vec<CompanyNode>> {
+        let filter = CompanyFilter::new(company_type, railroad_id, status);
+        let companies = query_use_case(ctx)
+            .get_companies(&filter)
+            .await
+            .map_err(graphql_error)?;
+
+        Ok(companies.into_iter().map(CompanyNode).collect())
+    }
+    #[graphql(complexity = "LIST_COMPLEXITY * child_complexity")]
+    async fn prefectures(&self, ctx: &Context<'_>) -> GraphQLResult<Vec<PrefectureNode>> {
+        let prefectures = query_use_case(ctx)
+            .get_prefectures()
+            .await
+            .map_err(graphql_error)?;
+
+        Ok(prefectures.into_iter().map(PrefectureNode).collect())
+    }
+    async fn train_type(&self, ctx: &Context<'_>, id: u32) -> GraphQLResult<Option<TrainTypeNode>> {
+        let train_type = query_use_case(ctx)
+            .find_train_type_by_id(id)
+            .await
+            .map_err(graphql_error)?;
+
+        Ok(train_type.map(TrainTypeNode))
+    }
+    #[graphql(complexity = "LIST_COMPLEXITY * child_complexity")]
+    async fn train_types(
+        &self,
+        ctx: &Context<'_>,
+        kind: Option<u32>,
+        name: Option<String>,
+        line_id: Option<u32>,
+        company_id: Option<u32>,
+    ) -> GraphQLResult<Vec<TrainTypeNode>> {
+        let filter = TrainTypeFilter::new(kind, name, line_id, company_id);
+        let train_types = query_use_case(ctx)
+            .get_train_types(&filter)
+            .await
+            .map_err(graphql_error)?;
+
+        Ok(train_types.into_iter().map(TrainTypeNode).collect())
+    }
+    async fn dataset_info(&self, ctx: &Context<'_>) -> GraphQLResult<Option<DatasetInfoNode>> {
+        let info = query_use_case(ctx)
+            .get_dataset_info()
+            .await
+            .map_err(graphql_error)?;
+
+        Ok(info.map(DatasetInfoNode::from))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{path::Path, sync::Arc};
+
+    use crate::infrastructure::memory::{dataset::Dataset, dataset_loader::InMemoryDatasetLoader};
+
+    use super::{build_schema, with_loaders, SharedQueryUseCase};
+
+    async fn execute(query: &str) -> async_graphql::Response {
+        let data_path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../data");
+        let dataset = Dataset::load(&data_path).expect("Failed to load the dataset");
+        let query_use_case: SharedQueryUseCase = Arc::new(
+            InMemoryDatasetLoader::build_query_interactor(Arc::new(dataset)),
+        );
+        let schema = build_schema(Arc::clone(&query_use_case));
+
+        schema
+            .execute(with_loaders(query.into(), &query_use_case))
+            .await
+    }
+
+    #[tokio::test]
+    async fn resolve_nested_fields() {
+        let response = execute(
+            "{ station(id: 1130519) { name lines { id stations { id } } trainTypes { name stations { id } } } }",
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+        let data = response.data.into_json().unwrap();
+        let station = &data["station"];
+        assert_eq!(station["name"], "新松戸");
+
+        let lines = station["lines"].as_array().unwrap();
+        assert!(lines.iter().any(|line| line["id"] == 11305));
+        assert!(lines
+            .iter()
+            .all(|line| !line["stations"].as_array().unwrap().is_empty()));
+
+        let train_types = station["trainTypes"].as_array().unwrap();
+        assert!(!train_types.is_empty());
+        // 武蔵野線の系統は新松戸にも停車する
+        assert!(train_types.iter().all(|train_type| {
+            train_type["stations"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .any(|station| station["id"] == 1130519)
+        }));
+    }
+
+    #[tokio::test]
+    async fn reject_complex_query() {
+        // 深さの上限には収まっていても、一覧の入れ子で件数が膨らむクエリは実行しない
+        let response = execute(
+            "{ station(id: 1130519) { lines { stations { lines { stations { id } } } } } }",
+        )
+        .await;
+        assert_eq!(response.errors.len(), 1);
+        assert!(response.errors[0].message.contains("complex"));
+    }
+}
@@ -1,40 +1,176 @@
+use std::sync::Arc;
+
 use crate::{
-    infrastructure::{
-        company_repository::MyCompanyRepository, line_repository::MyLineRepository,
-        station_repository::MyStationRepository, train_type_repository::MyTrainTypeRepository,
+    domain::entity::{
+        location_tracker::{DistanceThresholds, LocationTracker},
+        misc::{
+            AsOf, BoundingBox, CompanyFilter, StationFieldMask, StationFilter, TrainTypeFilter,
+        },
+        service_day::ServiceDay,
+        station::Station,
     },
     presentation::error::PresentationalError,
     station_api::{
-        station_api_server::StationApi, CoordinatesRequest, DistanceResponse,
-        DistanceResponseState, GetLineByIdRequest, GetLinesByNameRequest, GetRouteRequest,
+        station_api_server::StationApi, AutocompleteResponse, CoordinatesRequest,
+        DatasetDiffResponse, DatasetInfoResponse, DistanceResponse, GeoJsonResponse,
+        GetAutocompleteSuggestionsRequest, GetCompaniesRequest, GetCompanyByIdRequest,
+        GetDatasetDiffRequest, GetDatasetInfoRequest, GetJourneysRequest, GetLineByIdRequest,
+        GetLineGeoJsonRequest, GetLinesByCompanyIdRequest, GetLinesByNameRequest,
+        GetLinesByPrefectureIdRequest, GetPrefecturesRequest, GetRouteRequest,
         GetStationByCoordinatesRequest, GetStationByGroupIdRequest, GetStationByIdListRequest,
-        GetStationByIdRequest, GetStationByLineIdRequest, GetStationsByLineGroupIdRequest,
-        GetStationsByNameRequest, GetTrainTypesByStationIdRequest, MultipleLineResponse,
-        MultipleStationResponse, MultipleTrainTypeResponse, RouteResponse, SingleLineResponse,
-        SingleStationResponse,
+        GetStationByIdRequest, GetStationByLineIdRequest, GetStationsByBoundingBoxRequest,
+        GetStationsByCompanyIdRequest, GetStationsByLineGroupIdRequest, GetStationsByNameRequest,
+        GetStationsByPrefectureIdRequest, GetStationsByRadiusRequest,
+        GetStationsByTrainTypeRequest, GetStoppingPatternDiffRequest, GetTrainTypeByIdRequest,
+        GetTrainTypesByStationIdRequest, GetTrainTypesRequest, GetTravelDirectionRequest,
+        JourneyResponse, MultipleCompanyResponse, MultipleLineResponse, MultiplePrefectureResponse,
+        MultipleStationResponse, MultipleTrainTypeResponse, ReloadDatasetRequest,
+        ReloadDatasetResponse, RouteResponse, ServiceDayType, SingleCompanyResponse,
+        SingleLineResponse, SingleStationResponse, SingleTrainTypeResponse,
+        StoppingPatternDiffResponse, TrackLocationRequest, TrackLocationResponse,
+        TravelDirectionResponse,
+    },
+    use_case::{
+        pagination::{paginate, paginate_in_order, Page, PageRequest},
+        traits::{query::QueryUseCase, reload::ReloadUseCase},
     },
-    use_case::{interactor::query::QueryInteractor, traits::query::QueryUseCase},
 };
+use subtle::ConstantTimeEq;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
 use tonic::Response;
 
-pub struct MyApi {
-    pub query_use_case: QueryInteractor<
-        MyStationRepository,
-        MyLineRepository,
-        MyTrainTypeRepository,
-        MyCompanyRepository,
-    >,
+const TRACK_LOCATION_CHANNEL_CAPACITY: usize = 16;
+
+// "YYYY-MM-DD"。空文字は未指定として扱う
+pub fn parse_as_of(as_of: Option<&str>) -> Result<Option<AsOf>, PresentationalError> {
+    match as_of.filter(|as_of| !as_of.is_empty()) {
+        Some(as_of) => AsOf::parse(as_of).map(Some).ok_or_else(|| {
+            PresentationalError::InvalidArgument(format!(
+                r#"Invalid as_of "{}", expected YYYY-MM-DD"#,
+                as_of
+            ))
+        }),
+        None => Ok(None),
+    }
+}
+
+// service_dayとservice_date("YYYY-MM-DD")はどちらか一方だけ指定できる
+pub fn parse_service_day(
+    service_day: Option<ServiceDayType>,
+    service_date: Option<&str>,
+) -> Result<Option<ServiceDay>, PresentationalError> {
+    match (service_day, service_date.filter(|date| !date.is_empty())) {
+        (Some(_), Some(_)) => Err(PresentationalError::InvalidArgument(
+            "Specify either service_day or service_date".to_string(),
+        )),
+        (Some(ServiceDayType::WeekdayService), None) => Ok(Some(ServiceDay::Weekday)),
+        (Some(ServiceDayType::HolidayService), None) => Ok(Some(ServiceDay::Holiday)),
+        (None, Some(date)) => ServiceDay::from_ymd(date).map(Some).ok_or_else(|| {
+            PresentationalError::InvalidArgument(format!(
+                r#"Invalid service_date "{}", expected YYYY-MM-DD"#,
+                date
+            ))
+        }),
+        (None, None) => Ok(None),
+    }
+}
+
+// FieldMaskのpaths。field_maskを送らなければすべての属性を含める
+pub fn parse_field_mask(paths: Option<&[String]>) -> Result<StationFieldMask, PresentationalError> {
+    match paths {
+        Some(paths) => StationFieldMask::from_paths(paths).map_err(|path| {
+            PresentationalError::InvalidArgument(format!(
+                r#"Invalid field_mask path "{}", expected station_numbers, lines, company or train_type"#,
+                path
+            ))
+        }),
+        None => Ok(StationFieldMask::ALL),
+    }
+}
+
+// NOTE: 日付を指定したときは列車種別を付けないのでstation_idは使わない
+pub async fn get_stations_page_by_line_id<T: QueryUseCase>(
+    query_use_case: &T,
+    line_id: u32,
+    station_id: Option<u32>,
+    as_of: Option<&AsOf>,
+    field_mask: &StationFieldMask,
+    page_request: &PageRequest,
+) -> Result<Page<Station>, PresentationalError> {
+    let page = match (as_of, station_id) {
+        (Some(as_of), _) => {
+            let stations = query_use_case
+                .get_stations_by_line_id_as_of(line_id, as_of, field_mask)
+                .await?;
+            // NOTE: 駅の増減でページがずれないよう、並び順そのもの(e_sort, station_cd)をカーソルにする
+            paginate(stations, page_request, |station| {
+                vec![station.e_sort, station.station_cd]
+            })?
+        }
+        (None, Some(station_id)) => {
+            // NOTE: 列車種別の停車順(sst.id順)で返ってくるので、e_sortではなく並び順の位置をカーソルにする
+            let stations = query_use_case
+                .get_stations_by_line_id(line_id, Some(station_id), field_mask)
+                .await?;
+            paginate_in_order(stations, page_request)?
+        }
+        (None, None) => {
+            let stations = query_use_case
+                .get_stations_by_line_id(line_id, None, field_mask)
+                .await?;
+            paginate(stations, page_request, |station| {
+                vec![station.e_sort, station.station_cd]
+            })?
+        }
+    };
+    Ok(page)
+}
+
+pub struct MyApi<T: QueryUseCase> {
+    pub query_use_case: Arc<T>,
+    pub reload_use_case: Option<Arc<dyn ReloadUseCase>>,
+    // NOTE: 未設定なら管理用RPCは使えない
+    pub admin_token: Option<String>,
+}
+
+impl<T: QueryUseCase> MyApi<T> {
+    fn authorize_admin<R>(&self, request: &tonic::Request<R>) -> Result<(), PresentationalError> {
+        let Some(admin_token) = self.admin_token.as_deref() else {
+            return Err(PresentationalError::PermissionDenied(
+                "Admin RPCs are disabled".to_string(),
+            ));
+        };
+        match request.metadata().get("x-admin-token") {
+            // NOTE: 何文字目まで合っているかを応答時間から推測されないよう、定数時間で比べる
+            Some(token) if bool::from(token.as_bytes().ct_eq(admin_token.as_bytes())) => Ok(()),
+            _ => Err(PresentationalError::PermissionDenied(
+                "Invalid x-admin-token".to_string(),
+            )),
+        }
+    }
 }
 
 #[tonic::async_trait]
-impl StationApi for MyApi {
+impl<T: QueryUseCase> StationApi for MyApi<T> {
     async fn get_station_by_id(
         &self,
         request: tonic::Request<GetStationByIdRequest>,
     ) -> Result<tonic::Response<SingleStationResponse>, tonic::Status> {
-        let station_id = request.get_ref().id;
+        let request_ref = request.get_ref();
+        let station_id = request_ref.id;
+        let field_mask = parse_field_mask(
+            request_ref
+                .field_mask
+                .as_ref()
+                .map(|mask| mask.paths.as_slice()),
+        )?;
 
-        let station = match self.query_use_case.find_station_by_id(station_id).await {
+        let station = match self
+            .query_use_case
+            .find_station_by_id(station_id, &field_mask)
+            .await
+        {
             Ok(Some(station)) => station,
             Ok(None) => {
                 return Err(PresentationalError::NotFound(format!(
@@ -57,11 +193,18 @@ impl StationApi for MyApi {
         &self,
         request: tonic::Request<GetStationByIdListRequest>,
     ) -> Result<tonic::Response<MultipleStationResponse>, tonic::Status> {
-        let station_ids = &request.get_ref().ids;
+        let request_ref = request.get_ref();
+        let station_ids = &request_ref.ids;
+        let field_mask = parse_field_mask(
+            request_ref
+                .field_mask
+                .as_ref()
+                .map(|mask| mask.paths.as_slice()),
+        )?;
 
         let stations = match self
             .query_use_case
-            .get_stations_by_id_vec(station_ids)
+            .get_stations_by_id_vec(station_ids, &field_mask)
             .await
         {
             Ok(stations) => stations,
@@ -72,6 +215,7 @@ impl StationApi for MyApi {
 
         Ok(Response::new(MultipleStationResponse {
             stations: stations.into_iter().map(|station| station.into()).collect(),
+            next_page_token: None,
         }))
     }
 
@@ -79,13 +223,25 @@ impl StationApi for MyApi {
         &self,
         request: tonic::Request<GetStationByGroupIdRequest>,
     ) -> Result<tonic::Response<MultipleStationResponse>, tonic::Status> {
-        let group_id = request.get_ref().group_id;
+        let request_ref = request.get_ref();
+        let group_id = request_ref.group_id;
+        let field_mask = parse_field_mask(
+            request_ref
+                .field_mask
+                .as_ref()
+                .map(|mask| mask.paths.as_slice()),
+        )?;
+        let page_request = PageRequest::new(request_ref.page_size, request_ref.page_token.clone());
 
-        match self.query_use_case.get_stations_by_group_id(group_id).await {
+        match self
+            .query_use_case
+            .get_stations_by_group_id(group_id, &field_mask)
+            .await
+        {
             Ok(stations) => {
-                return Ok(Response::new(MultipleStationResponse {
-                    stations: stations.into_iter().map(|station| station.into()).collect(),
-                }));
+                let page = paginate_in_order(stations, &page_request)
+                    .map_err(PresentationalError::from)?;
+                Ok(Response::new(page.into()))
             }
             Err(err) => return Err(PresentationalError::from(err).into()),
         }
@@ -97,57 +253,169 @@ impl StationApi for MyApi {
         let request_ref: &GetStationByCoordinatesRequest = request.get_ref();
         let latitude = request_ref.latitude;
         let longitude = request_ref.longitude;
-        let limit = request_ref.limit;
+        let field_mask = parse_field_mask(
+            request_ref
+                .field_mask
+                .as_ref()
+                .map(|mask| mask.paths.as_slice()),
+        )?;
+        let page_request = PageRequest::new(request_ref.page_size, request_ref.page_token.clone());
+        let limit = page_request
+            .fetch_limit(request_ref.limit)
+            .map_err(PresentationalError::from)?;
         let stations = match self
             .query_use_case
-            .get_stations_by_coordinates(latitude, longitude, limit)
+            .get_stations_by_coordinates(latitude, longitude, limit, &field_mask)
             .await
         {
             Ok(stations) => stations,
             Err(err) => return Err(PresentationalError::from(err).into()),
         };
 
-        Ok(tonic::Response::new(MultipleStationResponse {
-            stations: stations.into_iter().map(|station| station.into()).collect(),
-        }))
+        let page = paginate_in_order(stations, &page_request).map_err(PresentationalError::from)?;
+        Ok(Response::new(page.into()))
     }
-    async fn get_stations_by_line_id(
+    async fn get_stations_by_radius(
         &self,
-        request: tonic::Request<GetStationByLineIdRequest>,
+        request: tonic::Request<GetStationsByRadiusRequest>,
     ) -> Result<tonic::Response<MultipleStationResponse>, tonic::Status> {
-        let line_id = request.get_ref().line_id;
-        let station_id = request.get_ref().station_id;
+        let request_ref = request.get_ref();
+        let filter = StationFilter::new(
+            request_ref.line_type.map(|line_type| line_type as u32),
+            request_ref.company_id,
+            request_ref.status.map(|status| status as u32),
+        );
+        let field_mask = parse_field_mask(
+            request_ref
+                .field_mask
+                .as_ref()
+                .map(|mask| mask.paths.as_slice()),
+        )?;
+        let page_request = PageRequest::new(request_ref.page_size, request_ref.page_token.clone())
+            .or_first_page(request_ref.limit);
+        let limit = page_request
+            .fetch_limit(request_ref.limit)
+            .map_err(PresentationalError::from)?;
 
         match self
             .query_use_case
-            .get_stations_by_line_id(line_id, station_id)
+            .get_stations_by_radius(
+                request_ref.latitude,
+                request_ref.longitude,
+                request_ref.radius,
+                &filter,
+                limit,
+                &field_mask,
+            )
             .await
         {
             Ok(stations) => {
-                return Ok(Response::new(MultipleStationResponse {
-                    stations: stations.into_iter().map(|station| station.into()).collect(),
-                }));
+                let page = paginate_in_order(stations, &page_request)
+                    .map_err(PresentationalError::from)?;
+                Ok(Response::new(page.into()))
             }
             Err(err) => Err(PresentationalError::from(err).into()),
         }
     }
+    async fn get_stations_by_bounding_box(
+        &self,
+        request: tonic::Request<GetStationsByBoundingBoxRequest>,
+    ) -> Result<tonic::Response<MultipleStationResponse>, tonic::Status> {
+        let request_ref = request.get_ref();
+        let bounding_box = BoundingBox::new(
+            request_ref.min_latitude,
+            request_ref.min_longitude,
+            request_ref.max_latitude,
+            request_ref.max_longitude,
+        );
+        let filter = StationFilter::new(
+            request_ref.line_type.map(|line_type| line_type as u32),
+            request_ref.company_id,
+            request_ref.status.map(|status| status as u32),
+        );
+        let field_mask = parse_field_mask(
+            request_ref
+                .field_mask
+                .as_ref()
+                .map(|mask| mask.paths.as_slice()),
+        )?;
+        let page_request = PageRequest::new(request_ref.page_size, request_ref.page_token.clone())
+            .or_first_page(request_ref.limit);
+        let limit = page_request
+            .fetch_limit(request_ref.limit)
+            .map_err(PresentationalError::from)?;
+
+        match self
+            .query_use_case
+            .get_stations_by_bounding_box(&bounding_box, &filter, limit, &field_mask)
+            .await
+        {
+            Ok(stations) => {
+                let page = paginate_in_order(stations, &page_request)
+                    .map_err(PresentationalError::from)?;
+                Ok(Response::new(page.into()))
+            }
+            Err(err) => Err(PresentationalError::from(err).into()),
+        }
+    }
+    async fn get_stations_by_line_id(
+        &self,
+        request: tonic::Request<GetStationByLineIdRequest>,
+    ) -> Result<tonic::Response<MultipleStationResponse>, tonic::Status> {
+        let request_ref = request.get_ref();
+        let line_id = request_ref.line_id;
+        let station_id = request_ref.station_id;
+        let as_of = parse_as_of(request_ref.as_of.as_deref())?;
+        let field_mask = parse_field_mask(
+            request_ref
+                .field_mask
+                .as_ref()
+                .map(|mask| mask.paths.as_slice()),
+        )?;
+        let page_request = PageRequest::new(request_ref.page_size, request_ref.page_token.clone());
+
+        let page = get_stations_page_by_line_id(
+            self.query_use_case.as_ref(),
+            line_id,
+            station_id,
+            as_of.as_ref(),
+            &field_mask,
+            &page_request,
+        )
+        .await?;
+        Ok(Response::new(page.into()))
+    }
     async fn get_stations_by_name(
         &self,
         request: tonic::Request<GetStationsByNameRequest>,
     ) -> Result<tonic::Response<MultipleStationResponse>, tonic::Status> {
         let request_ref = request.get_ref();
         let query_station_name = request_ref.station_name.clone();
-        let query_limit = request_ref.limit;
         let from_station_group_id = request_ref.from_station_group_id;
+        let field_mask = parse_field_mask(
+            request_ref
+                .field_mask
+                .as_ref()
+                .map(|mask| mask.paths.as_slice()),
+        )?;
+        let page_request = PageRequest::new(request_ref.page_size, request_ref.page_token.clone());
+        let query_limit = page_request
+            .fetch_limit(request_ref.limit)
+            .map_err(PresentationalError::from)?;
         match self
             .query_use_case
-            .get_stations_by_name(query_station_name, query_limit, from_station_group_id)
+            .get_stations_by_name(
+                query_station_name,
+                query_limit,
+                from_station_group_id,
+                &field_mask,
+            )
             .await
         {
             Ok(stations) => {
-                return Ok(Response::new(MultipleStationResponse {
-                    stations: stations.into_iter().map(|station| station.into()).collect(),
-                }));
+                let page = paginate_in_order(stations, &page_request)
+                    .map_err(PresentationalError::from)?;
+                Ok(Response::new(page.into()))
             }
             Err(err) => Err(PresentationalError::from(err).into()),
         }
@@ -159,16 +427,27 @@ impl StationApi for MyApi {
     ) -> Result<tonic::Response<MultipleStationResponse>, tonic::Status> {
         let request_ref = request.get_ref();
         let query_line_group_id = request_ref.line_group_id;
+        let service_day = parse_service_day(
+            request_ref.service_day.map(|_| request_ref.service_day()),
+            request_ref.service_date.as_deref(),
+        )?;
+        let field_mask = parse_field_mask(
+            request_ref
+                .field_mask
+                .as_ref()
+                .map(|mask| mask.paths.as_slice()),
+        )?;
+        let page_request = PageRequest::new(request_ref.page_size, request_ref.page_token.clone());
 
         match self
             .query_use_case
-            .get_stations_by_line_group_id(query_line_group_id)
+            .get_stations_by_line_group_id(query_line_group_id, service_day, &field_mask)
             .await
         {
             Ok(stations) => {
-                return Ok(Response::new(MultipleStationResponse {
-                    stations: stations.into_iter().map(|station| station.into()).collect(),
-                }));
+                let page = paginate_in_order(stations, &page_request)
+                    .map_err(PresentationalError::from)?;
+                Ok(Response::new(page.into()))
             }
             Err(err) => Err(PresentationalError::from(err).into()),
         }
@@ -180,15 +459,18 @@ impl StationApi for MyApi {
     ) -> Result<tonic::Response<MultipleTrainTypeResponse>, tonic::Status> {
         let request_ref: &GetTrainTypesByStationIdRequest = request.get_ref();
         let query_station_id = request_ref.station_id;
+        let page_request = PageRequest::new(request_ref.page_size, request_ref.page_token.clone());
 
         match self
             .query_use_case
             .get_train_types_by_station_id(query_station_id)
             .await
         {
-            Ok(train_types) => Ok(Response::new(MultipleTrainTypeResponse {
-                train_types: train_types.into_iter().map(|tt| tt.into()).collect(),
-            })),
+            Ok(train_types) => {
+                let page = paginate_in_order(train_types, &page_request)
+                    .map_err(PresentationalError::from)?;
+                Ok(Response::new(page.into()))
+            }
             Err(err) => Err(PresentationalError::from(err).into()),
         }
     }
@@ -208,26 +490,8 @@ impl StationApi for MyApi {
             .await
         {
             Ok(station) => {
-                let avg_distance_in_km = station.average_distance / 1000.0;
-                let arrived_threshold = if (avg_distance_in_km / 4.5) > 0.5 {
-                    0.5
-                } else {
-                    avg_distance_in_km / 4.5
-                };
-                let approaching_threshold = if (avg_distance_in_km / 2.0) > 1.0 {
-                    1.0
-                } else {
-                    avg_distance_in_km / 2.0
-                };
-
-                let mut state = DistanceResponseState::Away;
-                if station.distance < arrived_threshold {
-                    state = DistanceResponseState::Arrived;
-                }
-                if station.distance < approaching_threshold && station.distance > arrived_threshold
-                {
-                    state = DistanceResponseState::Approaching
-                }
+                let state =
+                    DistanceThresholds::new(station.average_distance).state(station.distance);
 
                 Ok(Response::new(DistanceResponse {
                     station_id: station.station_id,
@@ -239,14 +503,119 @@ impl StationApi for MyApi {
         }
     }
 
+    type TrackLocationStream = ReceiverStream<Result<TrackLocationResponse, tonic::Status>>;
+
+    async fn track_location(
+        &self,
+        request: tonic::Request<tonic::Streaming<TrackLocationRequest>>,
+    ) -> Result<tonic::Response<Self::TrackLocationStream>, tonic::Status> {
+        let mut inbound = request.into_inner();
+        let query_use_case = Arc::clone(&self.query_use_case);
+        let (tx, rx) = mpsc::channel(TRACK_LOCATION_CHANNEL_CAPACITY);
+
+        // NOTE: ストリームごとに状態を持たせ、路線の指定が変わったときだけ駅一覧を取り直す
+        tokio::spawn(async move {
+            let mut tracker: Option<((u32, Option<u32>), LocationTracker)> = None;
+
+            loop {
+                let request = match inbound.message().await {
+                    Ok(Some(request)) => request,
+                    Ok(None) => break,
+                    Err(status) => {
+                        let _ = tx.send(Err(status)).await;
+                        break;
+                    }
+                };
+
+                let key = (request.line_id, request.line_group_id);
+                let tracker = match tracker.take() {
+                    Some((current_key, current)) if current_key == key => {
+                        tracker.insert((current_key, current))
+                    }
+                    _ => match query_use_case
+                        .create_location_tracker(request.line_id, request.line_group_id)
+                        .await
+                    {
+                        Ok(created) => tracker.insert((key, created)),
+                        Err(err) => {
+                            let _ = tx.send(Err(PresentationalError::from(err).into())).await;
+                            break;
+                        }
+                    },
+                };
+
+                if let Some(update) = tracker.1.update(request.latitude, request.longitude) {
+                    if tx.send(Ok(update.into())).await.is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+
+    async fn get_travel_direction(
+        &self,
+        request: tonic::Request<GetTravelDirectionRequest>,
+    ) -> Result<tonic::Response<TravelDirectionResponse>, tonic::Status> {
+        let request_ref = request.get_ref();
+        let coordinates: Vec<(f64, f64)> = request_ref
+            .coordinates
+            .iter()
+            .map(|coordinates| (coordinates.latitude, coordinates.longitude))
+            .collect();
+
+        match self
+            .query_use_case
+            .get_travel_direction(&coordinates, request_ref.line_id, request_ref.line_group_id)
+            .await
+        {
+            Ok(estimate) => Ok(Response::new(
+                estimate.map(|estimate| estimate.into()).unwrap_or_default(),
+            )),
+            Err(err) => Err(PresentationalError::from(err).into()),
+        }
+    }
+
+    async fn get_autocomplete_suggestions(
+        &self,
+        request: tonic::Request<GetAutocompleteSuggestionsRequest>,
+    ) -> Result<tonic::Response<AutocompleteResponse>, tonic::Status> {
+        let request_ref = request.get_ref();
+
+        match self
+            .query_use_case
+            .get_autocomplete_suggestions(&request_ref.query, request_ref.limit)
+            .await
+        {
+            Ok(suggestions) => Ok(Response::new(AutocompleteResponse {
+                suggestions: suggestions
+                    .into_iter()
+                    .map(|suggestion| suggestion.into())
+                    .collect(),
+            })),
+            Err(err) => Err(PresentationalError::from(err).into()),
+        }
+    }
+
     async fn get_routes(
         &self,
         request: tonic::Request<GetRouteRequest>,
     ) -> Result<tonic::Response<RouteResponse>, tonic::Status> {
-        let from_id = request.get_ref().from_station_group_id;
-        let to_id = request.get_ref().to_station_group_id;
+        let request_ref = request.get_ref();
+        let from_id = request_ref.from_station_group_id;
+        let to_id = request_ref.to_station_group_id;
+        let service_day = parse_service_day(
+            request_ref.service_day.map(|_| request_ref.service_day()),
+            request_ref.service_date.as_deref(),
+        )?;
 
-        match self.query_use_case.get_routes(from_id, to_id).await {
+        match self
+            .query_use_case
+            .get_routes(from_id, to_id, service_day)
+            .await
+        {
             Ok(routes) => {
                 return Ok(Response::new(RouteResponse { routes }));
             }
@@ -254,13 +623,41 @@ impl StationApi for MyApi {
         }
     }
 
+    async fn get_journeys(
+        &self,
+        request: tonic::Request<GetJourneysRequest>,
+    ) -> Result<tonic::Response<JourneyResponse>, tonic::Status> {
+        let request_ref = request.get_ref();
+        let from_id = request_ref.from_station_group_id;
+        let to_id = request_ref.to_station_group_id;
+        let criterion = request_ref.criterion();
+        let limit = request_ref.limit;
+
+        match self
+            .query_use_case
+            .get_journeys(from_id, to_id, criterion, limit)
+            .await
+        {
+            Ok(journeys) => Ok(Response::new(JourneyResponse {
+                journeys: journeys.into_iter().map(|journey| journey.into()).collect(),
+            })),
+            Err(err) => Err(PresentationalError::from(err).into()),
+        }
+    }
+
     async fn get_line_by_id(
         &self,
         request: tonic::Request<GetLineByIdRequest>,
     ) -> Result<tonic::Response<SingleLineResponse>, tonic::Status> {
-        let line_id = request.get_ref().line_id;
+        let request_ref = request.get_ref();
+        let line_id = request_ref.line_id;
+        let as_of = parse_as_of(request_ref.as_of.as_deref())?;
 
-        let line = match self.query_use_case.find_line_by_id(line_id).await {
+        let line = match self
+            .query_use_case
+            .find_line_by_id(line_id, as_of.as_ref())
+            .await
+        {
             Ok(Some(line)) => line,
             Ok(None) => {
                 return Err(PresentationalError::NotFound(format!(
@@ -283,8 +680,12 @@ impl StationApi for MyApi {
         &self,
         request: tonic::Request<GetLinesByNameRequest>,
     ) -> Result<tonic::Response<MultipleLineResponse>, tonic::Status> {
-        let line_name = request.get_ref().line_name.clone();
-        let limit = request.get_ref().limit;
+        let request_ref = request.get_ref();
+        let line_name = request_ref.line_name.clone();
+        let page_request = PageRequest::new(request_ref.page_size, request_ref.page_token.clone());
+        let limit = page_request
+            .fetch_limit(request_ref.limit)
+            .map_err(PresentationalError::from)?;
 
         match self
             .query_use_case
@@ -292,11 +693,528 @@ impl StationApi for MyApi {
             .await
         {
             Ok(lines) => {
-                return Ok(Response::new(MultipleLineResponse {
-                    lines: lines.into_iter().map(|line| line.into()).collect(),
-                }));
+                let page =
+                    paginate_in_order(lines, &page_request).map_err(PresentationalError::from)?;
+                Ok(Response::new(page.into()))
+            }
+            Err(err) => Err(PresentationalError::from(err).into()),
+        }
+    }
+
+    async fn get_prefectures(
+        &self,
+        _request: tonic::Request<GetPrefecturesRequest>,
+    ) -> Result<tonic::Response<MultiplePrefectureResponse>, tonic::Status> {
+        match self.query_use_case.get_prefectures().await {
+            Ok(prefectures) => Ok(Response::new(MultiplePrefectureResponse {
+                prefectures: prefectures
+                    .into_iter()
+                    .map(|prefecture| prefecture.into())
+                    .collect(),
+            })),
+            Err(err) => Err(PresentationalError::from(err).into()),
+        }
+    }
+
+    async fn get_stations_by_prefecture_id(
+        &self,
+        request: tonic::Request<GetStationsByPrefectureIdRequest>,
+    ) -> Result<tonic::Response<MultipleStationResponse>, tonic::Status> {
+        let request_ref = request.get_ref();
+        let as_of = parse_as_of(request_ref.as_of.as_deref())?;
+        let field_mask = parse_field_mask(
+            request_ref
+                .field_mask
+                .as_ref()
+                .map(|mask| mask.paths.as_slice()),
+        )?;
+        let page_request = PageRequest::new(request_ref.page_size, request_ref.page_token.clone());
+
+        match self
+            .query_use_case
+            .get_stations_by_prefecture_id(request_ref.prefecture_id, as_of.as_ref(), &field_mask)
+            .await
+        {
+            Ok(stations) => {
+                let page = paginate(stations, &page_request, |station| {
+                    vec![station.e_sort, station.station_cd]
+                })
+                .map_err(PresentationalError::from)?;
+                Ok(Response::new(page.into()))
+            }
+            Err(err) => Err(PresentationalError::from(err).into()),
+        }
+    }
+
+    async fn get_lines_by_prefecture_id(
+        &self,
+        request: tonic::Request<GetLinesByPrefectureIdRequest>,
+    ) -> Result<tonic::Response<MultipleLineResponse>, tonic::Status> {
+        let request_ref = request.get_ref();
+        let as_of = parse_as_of(request_ref.as_of.as_deref())?;
+        let page_request = PageRequest::new(request_ref.page_size, request_ref.page_token.clone());
+
+        match self
+            .query_use_case
+            .get_lines_by_prefecture_id(request_ref.prefecture_id, as_of.as_ref())
+            .await
+        {
+            Ok(lines) => {
+                let page = paginate(lines, &page_request, |line| vec![line.e_sort, line.line_cd])
+                    .map_err(PresentationalError::from)?;
+                Ok(Response::new(page.into()))
+            }
+            Err(err) => Err(PresentationalError::from(err).into()),
+        }
+    }
+
+    async fn get_companies(
+        &self,
+        request: tonic::Request<GetCompaniesRequest>,
+    ) -> Result<tonic::Response<MultipleCompanyResponse>, tonic::Status> {
+        let request_ref = request.get_ref();
+        let filter = CompanyFilter::new(
+            request_ref
+                .company_type
+                .map(|company_type| company_type as u32),
+            request_ref.railroad_id,
+            request_ref.status.map(|status| status as u32),
+        );
+        let page_request = PageRequest::new(request_ref.page_size, request_ref.page_token.clone());
+
+        match self.query_use_case.get_companies(&filter).await {
+            Ok(companies) => {
+                let page = paginate(companies, &page_request, |company| {
+                    vec![company.e_sort, company.company_cd]
+                })
+                .map_err(PresentationalError::from)?;
+                Ok(Response::new(page.into()))
+            }
+            Err(err) => Err(PresentationalError::from(err).into()),
+        }
+    }
+
+    async fn get_company_by_id(
+        &self,
+        request: tonic::Request<GetCompanyByIdRequest>,
+    ) -> Result<tonic::Response<SingleCompanyResponse>, tonic::Status> {
+        let company_id = request.get_ref().company_id;
+
+        match self.query_use_case.find_company_by_id(company_id).await {
+            Ok(Some(company)) => Ok(Response::new(SingleCompanyResponse {
+                company: Some(company.into()),
+            })),
+            Ok(None) => Err(PresentationalError::NotFound(format!(
+                "Company with id {} not found",
+                company_id
+            ))
+            .into()),
+            Err(err) => Err(PresentationalError::from(err).into()),
+        }
+    }
+
+    async fn get_lines_by_company_id(
+        &self,
+        request: tonic::Request<GetLinesByCompanyIdRequest>,
+    ) -> Result<tonic::Response<MultipleLineResponse>, tonic::Status> {
+        let request_ref = request.get_ref();
+        let as_of = parse_as_of(request_ref.as_of.as_deref())?;
+        let page_request = PageRequest::new(request_ref.page_size, request_ref.page_token.clone());
+
+        match self
+            .query_use_case
+            .get_lines_by_company_id(request_ref.company_id, as_of.as_ref())
+            .await
+        {
+            Ok(lines) => {
+                let page = paginate(lines, &page_request, |line| vec![line.e_sort, line.line_cd])
+                    .map_err(PresentationalError::from)?;
+                Ok(Response::new(page.into()))
             }
             Err(err) => Err(PresentationalError::from(err).into()),
         }
     }
+
+    async fn get_stations_by_company_id(
+        &self,
+        request: tonic::Request<GetStationsByCompanyIdRequest>,
+    ) -> Result<tonic::Response<MultipleStationResponse>, tonic::Status> {
+        let request_ref = request.get_ref();
+        let as_of = parse_as_of(request_ref.as_of.as_deref())?;
+        let field_mask = parse_field_mask(
+            request_ref
+                .field_mask
+                .as_ref()
+                .map(|mask| mask.paths.as_slice()),
+        )?;
+        let page_request = PageRequest::new(request_ref.page_size, request_ref.page_token.clone());
+
+        match self
+            .query_use_case
+            .get_stations_by_company_id(request_ref.company_id, as_of.as_ref(), &field_mask)
+            .await
+        {
+            Ok(stations) => {
+                let page = paginate(stations, &page_request, |station| {
+                    vec![station.e_sort, station.station_cd]
+                })
+                .map_err(PresentationalError::from)?;
+                Ok(Response::new(page.into()))
+            }
+            Err(err) => Err(PresentationalError::from(err).into()),
+        }
+    }
+
+    async fn get_train_types(
+        &self,
+        request: tonic::Request<GetTrainTypesRequest>,
+    ) -> Result<tonic::Response<MultipleTrainTypeResponse>, tonic::Status> {
+        let request_ref = request.get_ref();
+        let filter = TrainTypeFilter::new(
+            request_ref.kind.map(|kind| kind as u32),
+            request_ref.name.clone(),
+            request_ref.line_id,
+            request_ref.company_id,
+        );
+        let page_request = PageRequest::new(request_ref.page_size, request_ref.page_token.clone());
+
+        match self.query_use_case.get_train_types(&filter).await {
+            Ok(train_types) => {
+                let page = paginate(train_types, &page_request, |train_type| {
+                    vec![train_type.type_cd, train_type.line_group_cd]
+                })
+                .map_err(PresentationalError::from)?;
+                Ok(Response::new(page.into()))
+            }
+            Err(err) => Err(PresentationalError::from(err).into()),
+        }
+    }
+
+    async fn get_train_type_by_id(
+        &self,
+        request: tonic::Request<GetTrainTypeByIdRequest>,
+    ) -> Result<tonic::Response<SingleTrainTypeResponse>, tonic::Status> {
+        let type_id = request.get_ref().type_id;
+
+        match self.query_use_case.find_train_type_by_id(type_id).await {
+            Ok(Some(train_type)) => Ok(Response::new(SingleTrainTypeResponse {
+                train_type: Some(train_type.into()),
+            })),
+            Ok(None) => Err(PresentationalError::NotFound(format!(
+                "Train type with id {} not found",
+                type_id
+            ))
+            .into()),
+            Err(err) => Err(PresentationalError::from(err).into()),
+        }
+    }
+
+    async fn get_stations_by_train_type(
+        &self,
+        request: tonic::Request<GetStationsByTrainTypeRequest>,
+    ) -> Result<tonic::Response<MultipleStationResponse>, tonic::Status> {
+        let request_ref = request.get_ref();
+        let field_mask = parse_field_mask(
+            request_ref
+                .field_mask
+                .as_ref()
+                .map(|mask| mask.paths.as_slice()),
+        )?;
+        let page_request = PageRequest::new(request_ref.page_size, request_ref.page_token.clone());
+
+        match self
+            .query_use_case
+            .get_stations_by_train_type(request_ref.type_id, request_ref.line_group_id, &field_mask)
+            .await
+        {
+            Ok(stations) => {
+                let page = paginate_in_order(stations, &page_request)
+                    .map_err(PresentationalError::from)?;
+                Ok(Response::new(page.into()))
+            }
+            Err(err) => Err(PresentationalError::from(err).into()),
+        }
+    }
+
+    async fn get_stopping_pattern_diff(
+        &self,
+        request: tonic::Request<GetStoppingPatternDiffRequest>,
+    ) -> Result<tonic::Response<StoppingPatternDiffResponse>, tonic::Status> {
+        let request_ref = request.get_ref();
+
+        match self
+            .query_use_case
+            .get_stopping_pattern_diff(
+                request_ref.first_line_group_id,
+                request_ref.second_line_group_id,
+            )
+            .await
+        {
+            Ok(pattern) => Ok(Response::new(pattern.into())),
+            Err(err) => Err(PresentationalError::from(err).into()),
+        }
+    }
+
+    async fn get_line_geo_json(
+        &self,
+        request: tonic::Request<GetLineGeoJsonRequest>,
+    ) -> Result<tonic::Response<GeoJsonResponse>, tonic::Status> {
+        let line_id = request.get_ref().line_id;
+
+        let feature_collection = match self.query_use_case.get_line_geojson(line_id).await {
+            Ok(feature_collection) => feature_collection,
+            Err(err) => return Err(PresentationalError::from(err).into()),
+        };
+
+        match serde_json::to_string(&feature_collection) {
+            Ok(geojson) => Ok(Response::new(GeoJsonResponse { geojson })),
+            Err(err) => Err(PresentationalError::OtherError(anyhow::anyhow!(err).into()).into()),
+        }
+    }
+
+    async fn reload_dataset(
+        &self,
+        request: tonic::Request<ReloadDatasetRequest>,
+    ) -> Result<tonic::Response<ReloadDatasetResponse>, tonic::Status> {
+        self.authorize_admin(&request)?;
+        let Some(reload_use_case) = self.reload_use_case.as_ref() else {
+            return Err(PresentationalError::PermissionDenied(
+                "Dataset reloading is not available".to_string(),
+            )
+            .into());
+        };
+
+        match reload_use_case
+            .reload_dataset(request.into_inner().source)
+            .await
+        {
+            Ok(summary) => Ok(Response::new(summary.into())),
+            Err(err) => Err(PresentationalError::from(err).into()),
+        }
+    }
+
+    async fn get_dataset_info(
+        &self,
+        _request: tonic::Request<GetDatasetInfoRequest>,
+    ) -> Result<tonic::Response<DatasetInfoResponse>, tonic::Status> {
+        match self.query_use_case.get_dataset_info().await {
+            Ok(Some(info)) => Ok(Response::new(info.into())),
+            Ok(None) => Err(PresentationalError::NotFound(
+                "The dataset version has not been recorded".to_string(),
+            )
+            .into()),
+            Err(err) => Err(PresentationalError::from(err).into()),
+        }
+    }
+
+    async fn get_dataset_diff(
+        &self,
+        request: tonic::Request<GetDatasetDiffRequest>,
+    ) -> Result<tonic::Response<DatasetDiffResponse>, tonic::Status> {
+        self.authorize_admin(&request)?;
+        let Some(reload_use_case) = self.reload_use_case.as_ref() else {
+            return Err(PresentationalError::PermissionDenied(
+                "Dataset diffs are not available".to_string(),
+            )
+            .into());
+        };
+        let request = request.into_inner();
+
+        let changelog = match reload_use_case
+            .diff_dataset(request.base_source, request.target_source)
+            .await
+        {
+            Ok(changelog) => changelog,
+            Err(err) => return Err(PresentationalError::from(err).into()),
+        };
+
+        match serde_json::to_string(&changelog) {
+            Ok(changelog) => Ok(Response::new(DatasetDiffResponse { changelog })),
+            Err(err) => Err(PresentationalError::OtherError(anyhow::anyhow!(err).into()).into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{path::Path, sync::Arc};
+
+    use tonic::{Code, Request};
+
+    use crate::{
+        domain::entity::misc::StationFieldMask,
+        infrastructure::memory::{dataset::Dataset, dataset_loader::InMemoryDatasetLoader},
+        station_api::{
+            station_api_server::StationApi, GetStationsByBoundingBoxRequest,
+            GetStationsByRadiusRequest,
+        },
+        use_case::{pagination::PageRequest, traits::query::QueryUseCase},
+    };
+
+    use super::{get_stations_page_by_line_id, MyApi};
+
+    #[tokio::test]
+    async fn page_through_line_group_stations() {
+        let data_path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../data");
+        let dataset = Dataset::load(&data_path).expect("Failed to load the dataset");
+        let query_use_case = InMemoryDatasetLoader::build_query_interactor(Arc::new(dataset));
+        let field_mask = StationFieldMask::ALL;
+
+        // 武蔵野線から京葉線に直通するので停車順はe_sort順にならない
+        let (line_id, station_id) = (11305, 1130519);
+        let stations = query_use_case
+            .get_stations_by_line_id(line_id, Some(station_id), &field_mask)
+            .await
+            .unwrap();
+        let keys: Vec<(u32, u32)> = stations
+            .iter()
+            .map(|station| (station.e_sort, station.station_cd))
+            .collect();
+        assert!(keys.windows(2).any(|pair| pair[0] > pair[1]));
+
+        let mut paged = vec![];
+        let mut page_token = None;
+        loop {
+            let page = get_stations_page_by_line_id(
+                &query_use_case,
+                line_id,
+                Some(station_id),
+                None,
+                &field_mask,
+                &PageRequest::new(Some(5), page_token),
+            )
+            .await
+            .unwrap();
+            assert!(page.items.len() <= 5);
+            paged.extend(page.items.into_iter().map(|station| station.station_cd));
+            page_token = page.next_page_token;
+            if page_token.is_none() {
+                break;
+            }
+        }
+        let station_ids: Vec<u32> = stations.iter().map(|station| station.station_cd).collect();
+        assert_eq!(paged, station_ids);
+    }
+
+    #[tokio::test]
+    async fn validate_spatial_search_requests() {
+        let data_path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../data");
+        let dataset = Dataset::load(&data_path).expect("Failed to load the dataset");
+        let api = MyApi {
+            query_use_case: Arc::new(InMemoryDatasetLoader::build_query_interactor(Arc::new(
+                dataset,
+            ))),
+            reload_use_case: None,
+            admin_token: None,
+        };
+        let radius_request = |latitude: f64, radius: f64| GetStationsByRadiusRequest {
+            latitude,
+            longitude: 139.767125,
+            radius,
+            ..Default::default()
+        };
+
+        for (latitude, radius) in [
+            (35.681236, -1.0),
+            (35.681236, f64::NAN),
+            (35.681236, f64::INFINITY),
+            (91.0, 1.0),
+            (f64::NAN, 1.0),
+        ] {
+            let status = api
+                .get_stations_by_radius(Request::new(radius_request(latitude, radius)))
+                .await
+                .unwrap_err();
+            assert_eq!(status.code(), Code::InvalidArgument);
+        }
+
+        let status = api
+            .get_stations_by_bounding_box(Request::new(GetStationsByBoundingBoxRequest {
+                min_latitude: 35.8,
+                min_longitude: 139.6,
+                max_latitude: 35.6,
+                max_longitude: 139.8,
+                ..Default::default()
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), Code::InvalidArgument);
+
+        // 件数を指定しなくても全国の駅は返さず、続きのページのトークンを返す
+        let response = api
+            .get_stations_by_bounding_box(Request::new(GetStationsByBoundingBoxRequest {
+                min_latitude: 20.0,
+                min_longitude: 120.0,
+                max_latitude: 46.0,
+                max_longitude: 154.0,
+                ..Default::default()
+            }))
+            .await
+            .unwrap();
+        assert_eq!(response.get_ref().stations.len(), 100);
+        assert!(response.get_ref().next_page_token.is_some());
+
+        // 上限を超えて該当するときは切り詰めずにエラーにする
+        let status = api
+            .get_stations_by_radius(Request::new(GetStationsByRadiusRequest {
+                limit: Some(u32::MAX),
+                ..radius_request(35.681236, 3000.0)
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), Code::InvalidArgument);
+
+        let response = api
+            .get_stations_by_radius(Request::new(GetStationsByRadiusRequest {
+                limit: Some(1000),
+                ..radius_request(35.681236, 3000.0)
+            }))
+            .await
+            .unwrap();
+        assert_eq!(response.get_ref().stations.len(), 1000);
+        assert!(response.get_ref().next_page_token.is_none());
+    }
+
+    #[test]
+    fn authorize_admin_token() {
+        let dataset = Dataset::new(
+            vec![],
+            vec![],
+            vec![],
+            vec![],
+            vec![],
+            vec![],
+            vec![],
+            vec![],
+        );
+        let api = MyApi {
+            query_use_case: Arc::new(InMemoryDatasetLoader::build_query_interactor(Arc::new(
+                dataset,
+            ))),
+            reload_use_case: None,
+            admin_token: Some("secret".to_string()),
+        };
+        let request_with_token = |token: Option<&str>| {
+            let mut request = Request::new(());
+            if let Some(token) = token {
+                request
+                    .metadata_mut()
+                    .insert("x-admin-token", token.parse().unwrap());
+            }
+            request
+        };
+
+        assert!(api
+            .authorize_admin(&request_with_token(Some("secret")))
+            .is_ok());
+        for token in [
+            None,
+            Some(""),
+            Some("secre"),
+            Some("secret!"),
+            Some("SECRET"),
+        ] {
+            assert!(api.authorize_admin(&request_with_token(token)).is_err());
+        }
+    }
 }
@@ -0,0 +1,768 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, Query, State},
+    routing::get,
+    Json, Router,
+};
+use serde::Deserialize;
+
+use crate::{
+    domain::entity::{
+        geojson::FeatureCollection,
+        location_tracker::DistanceThresholds,
+        misc::{BoundingBox, CompanyFilter, StationFieldMask, StationFilter, TrainTypeFilter},
+    },
+    presentation::{
+        controller::grpc::{
+            get_stations_page_by_line_id, parse_as_of, parse_field_mask, parse_service_day,
+        },
+        error::PresentationalError,
+    },
+    station_api::{
+        AutocompleteResponse, DatasetInfoResponse, DistanceResponse, JourneyResponse,
+        JourneySearchCriterion, MultipleCompanyResponse, MultipleLineResponse,
+        MultiplePrefectureResponse, MultipleStationResponse, MultipleTrainTypeResponse,
+        RouteResponse, ServiceDayType, SingleCompanyResponse, SingleLineResponse,
+        SingleStationResponse, SingleTrainTypeResponse, StoppingPatternDiffResponse,
+    },
+    use_case::{
+        pagination::{paginate, paginate_in_order, PageRequest},
+        traits::query::QueryUseCase,
+    },
+};
+
+type RestResult<T> = Result<Json<T>, PresentationalError>;
+
+// gRPCと同じQueryUseCaseをクエリ文字列で呼び出すJSONのルーター
+// NOTE: TrackLocationやGetTravelDirectionのように座標を送り続けるものはgRPCだけで提供する
+pub fn router<T: QueryUseCase>(query_use_case: Arc<T>) -> Router {
+    Router::new()
+        .route("/stations", get(get_stations_by_id_list::<T>))
+        .route("/stations/nearby", get(get_stations_by_coordinates::<T>))
+        .route("/stations/closest", get(get_closest_station::<T>))
+        .route("/stations/radius", get(get_stations_by_radius::<T>))
+        .route(
+            "/stations/bounding_box",
+            get(get_stations_by_bounding_box::<T>),
+        )
+        .route("/stations/search", get(get_stations_by_name::<T>))
+        .route("/stations/:id", get(get_station_by_id::<T>))
+        .route(
+            "/stations/:id/train_types",
+            get(get_train_types_by_station_id::<T>),
+        )
+        .route(
+            "/station_groups/:id/stations",
+            get(get_stations_by_group_id::<T>),
+        )
+        .route("/lines/search", get(get_lines_by_name::<T>))
+        .route("/lines/:id", get(get_line_by_id::<T>))
+        .route("/lines/:id/stations", get(get_stations_by_line_id::<T>))
+        .route("/lines/:id/geojson", get(get_line_geojson::<T>))
+        .route(
+            "/line_groups/:id/stations",
+            get(get_stations_by_line_group_id::<T>),
+        )
+        .route(
+            "/stopping_pattern_diff",
+            get(get_stopping_pattern_diff::<T>),
+        )
+        .route("/routes", get(get_routes::<T>))
+        .route("/journeys", get(get_journeys::<T>))
+        .route("/autocomplete", get(get_autocomplete_suggestions::<T>))
+        .route("/prefectures", get(get_prefectures::<T>))
+        .route(
+            "/prefectures/:id/stations",
+            get(get_stations_by_prefecture_id::<T>),
+        )
+        .route(
+            "/prefectures/:id/lines",
+            get(get_lines_by_prefecture_id::<T>),
+        )
+        .route("/companies", get(get_companies::<T>))
+        .route("/companies/:id", get(get_company_by_id::<T>))
+        .route("/companies/:id/lines", get(get_lines_by_company_id::<T>))
+        .route(
+            "/companies/:id/stations",
+            get(get_stations_by_company_id::<T>),
+        )
+        .route("/train_types", get(get_train_types::<T>))
+        .route("/train_types/:id", get(get_train_type_by_id::<T>))
+        .route(
+            "/train_types/:id/stations",
+            get(get_stations_by_train_type::<T>),
+        )
+        .route("/dataset", get(get_dataset_info::<T>))
+        .with_state(query_use_case)
+}
+
+// NOTE: serde_urlencodedは#[serde(flatten)]で数値を読めないので、ページングのパラメータは各構造体に持たせる
+#[derive(Deserialize)]
+pub struct PageParams {
+    page_size: Option<u32>,
+    page_token: Option<String>,
+}
+
+// fields: "lines,train_type" のようにFieldMaskのpathsをカンマ区切りで渡す
+#[derive(Deserialize)]
+pub struct FieldsParams {
+    fields: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct StationPageParams {
+    fields: Option<String>,
+    page_size: Option<u32>,
+    page_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct IdListParams {
+    ids: String,
+    fields: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct CoordinatesParams {
+    latitude: f64,
+    longitude: f64,
+    limit: Option<u32>,
+    line_id: Option<u32>,
+    fields: Option<String>,
+    page_size: Option<u32>,
+    page_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct RadiusParams {
+    latitude: f64,
+    longitude: f64,
+    radius: f64,
+    line_type: Option<u32>,
+    company_id: Option<u32>,
+    status: Option<u32>,
+    limit: Option<u32>,
+    fields: Option<String>,
+    page_size: Option<u32>,
+    page_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct BoundingBoxParams {
+    min_latitude: f64,
+    min_longitude: f64,
+    max_latitude: f64,
+    max_longitude: f64,
+    line_type: Option<u32>,
+    company_id: Option<u32>,
+    status: Option<u32>,
+    limit: Option<u32>,
+    fields: Option<String>,
+    page_size: Option<u32>,
+    page_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct NameParams {
+    name: String,
+    limit: Option<u32>,
+    from_station_group_id: Option<u32>,
+    fields: Option<String>,
+    page_size: Option<u32>,
+    page_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct AsOfParams {
+    as_of: Option<String>,
+    page_size: Option<u32>,
+    page_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct StationAsOfParams {
+    as_of: Option<String>,
+    fields: Option<String>,
+    page_size: Option<u32>,
+    page_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct LineStationsParams {
+    station_id: Option<u32>,
+    as_of: Option<String>,
+    fields: Option<String>,
+    page_size: Option<u32>,
+    page_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct ServiceDayParams {
+    service_day: Option<String>,
+    service_date: Option<String>,
+    fields: Option<String>,
+    page_size: Option<u32>,
+    page_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct StoppingPatternDiffParams {
+    first_line_group_id: u32,
+    second_line_group_id: u32,
+}
+
+#[derive(Deserialize)]
+pub struct RouteParams {
+    from_station_group_id: u32,
+    to_station_group_id: u32,
+    service_day: Option<String>,
+    service_date: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct JourneyParams {
+    from_station_group_id: u32,
+    to_station_group_id: u32,
+    criterion: Option<String>,
+    limit: Option<u32>,
+}
+
+#[derive(Deserialize)]
+pub struct AutocompleteParams {
+    query: String,
+    limit: Option<u32>,
+}
+
+#[derive(Deserialize)]
+pub struct CompanyParams {
+    company_type: Option<u32>,
+    railroad_id: Option<u32>,
+    status: Option<u32>,
+    page_size: Option<u32>,
+    page_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct TrainTypeParams {
+    kind: Option<u32>,
+    name: Option<String>,
+    line_id: Option<u32>,
+    company_id: Option<u32>,
+    page_size: Option<u32>,
+    page_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct TrainTypeStationsParams {
+    line_group_id: Option<u32>,
+    fields: Option<String>,
+    page_size: Option<u32>,
+    page_token: Option<String>,
+}
+
+// "1,2,3"
+fn parse_ids(ids: &str) -> Result<Vec<u32>, PresentationalError> {
+    ids.split(',')
+        .filter(|id| !id.is_empty())
+        .map(|id| {
+            id.trim().parse::<u32>().map_err(|_| {
+                PresentationalError::InvalidArgument(format!(r#"Invalid id "{}""#, id))
+            })
+        })
+        .collect()
+}
+
+fn parse_fields(fields: Option<&str>) -> Result<StationFieldMask, PresentationalError> {
+    let paths: Option<Vec<String>> = fields.map(|fields| {
+        fields
+            .split(',')
+            .filter(|path| !path.is_empty())
+            .map(|path| path.to_string())
+            .collect()
+    });
+    parse_field_mask(paths.as_deref())
+}
+
+// "weekday" または "holiday"
+fn parse_service_day_type(
+    service_day: Option<&str>,
+) -> Result<Option<ServiceDayType>, PresentationalError> {
+    match service_day.filter(|service_day| !service_day.is_empty()) {
+        Some("weekday") => Ok(Some(ServiceDayType::WeekdayService)),
+        Some("holiday") => Ok(Some(ServiceDayType::HolidayService)),
+        Some(service_day) => Err(PresentationalError::InvalidArgument(format!(
+            r#"Invalid service_day "{}", expected weekday or holiday"#,
+            service_day
+        ))),
+        None => Ok(None),
+    }
+}
+
+// "fewest_stops" または "fewest_transfers"
+fn parse_criterion(criterion: Option<&str>) -> Result<JourneySearchCriterion, PresentationalError> {
+    match criterion.filter(|criterion| !criterion.is_empty()) {
+        None | Some("fewest_stops") => Ok(JourneySearchCriterion::FewestStops),
+        Some("fewest_transfers") => Ok(JourneySearchCriterion::FewestTransfers),
+        Some(criterion) => Err(PresentationalError::InvalidArgument(format!(
+            r#"Invalid criterion "{}", expected fewest_stops or fewest_transfers"#,
+            criterion
+        ))),
+    }
+}
+
+async fn get_station_by_id<T: QueryUseCase>(
+    State(query_use_case): State<Arc<T>>,
+    Path(station_id): Path<u32>,
+    Query(params): Query<FieldsParams>,
+) -> RestResult<SingleStationResponse> {
+    let field_mask = parse_fields(params.fields.as_deref())?;
+    match query_use_case
+        .find_station_by_id(station_id, &field_mask)
+        .await?
+    {
+        Some(station) => Ok(Json(SingleStationResponse {
+            station: Some(station.into()),
+        })),
+        None => Err(PresentationalError::NotFound(format!(
+            "Station with id {} not found",
+            station_id
+        ))),
+    }
+}
+
+async fn get_stations_by_id_list<T: QueryUseCase>(
+    State(query_use_case): State<Arc<T>>,
+    Query(params): Query<IdListParams>,
+) -> RestResult<MultipleStationResponse> {
+    let station_ids = parse_ids(&params.ids)?;
+    let field_mask = parse_fields(params.fields.as_deref())?;
+    let stations = query_use_case
+        .get_stations_by_id_vec(&station_ids, &field_mask)
+        .await?;
+
+    Ok(Json(MultipleStationResponse {
+        stations: stations.into_iter().map(|station| station.into()).collect(),
+        next_page_token: None,
+    }))
+}
+
+async fn get_stations_by_group_id<T: QueryUseCase>(
+    State(query_use_case): State<Arc<T>>,
+    Path(group_id): Path<u32>,
+    Query(params): Query<StationPageParams>,
+) -> RestResult<MultipleStationResponse> {
+    let field_mask = parse_fields(params.fields.as_deref())?;
+    let page_request = PageRequest::new(params.page_size, params.page_token);
+    let stations = query_use_case
+        .get_stations_by_group_id(group_id, &field_mask)
+        .await?;
+    let page = paginate_in_order(stations, &page_request)?;
+    Ok(Json(page.into()))
+}
+
+async fn get_stations_by_coordinates<T: QueryUseCase>(
+    State(query_use_case): State<Arc<T>>,
+    Query(params): Query<CoordinatesParams>,
+) -> RestResult<MultipleStationResponse> {
+    let field_mask = parse_fields(params.fields.as_deref())?;
+    let page_request = PageRequest::new(params.page_size, params.page_token);
+    let limit = page_request.fetch_limit(params.limit)?;
+    let stations = query_use_case
+        .get_stations_by_coordinates(params.latitude, params.longitude, limit, &field_mask)
+        .await?;
+    let page = paginate_in_order(stations, &page_request)?;
+    Ok(Json(page.into()))
+}
+
+async fn get_closest_station<T: QueryUseCase>(
+    State(query_use_case): State<Arc<T>>,
+    Query(params): Query<CoordinatesParams>,
+) -> RestResult<DistanceResponse> {
+    let station = query_use_case
+        .get_station_id_and_distance_by_coordinates(
+            params.latitude,
+            params.longitude,
+            params.line_id,
+        )
+        .await?;
+    let state = DistanceThresholds::new(station.average_distance).state(station.distance);
+
+    Ok(Json(DistanceResponse {
+        station_id: station.station_id,
+        distance: station.distance,
+        state: state.into(),
+    }))
+}
+
+async fn get_stations_by_radius<T: QueryUseCase>(
+    State(query_use_case): State<Arc<T>>,
+    Query(params): Query<RadiusParams>,
+) -> RestResult<MultipleStationResponse> {
+    let filter = StationFilter::new(params.line_type, params.company_id, params.status);
+    let field_mask = parse_fields(params.fields.as_deref())?;
+    let page_request =
+        PageRequest::new(params.page_size, params.page_token).or_first_page(params.limit);
+    let limit = page_request.fetch_limit(params.limit)?;
+    let stations = query_use_case
+        .get_stations_by_radius(
+            params.latitude,
+            params.longitude,
+            params.radius,
+            &filter,
+            limit,
+            &field_mask,
+        )
+        .await?;
+    let page = paginate_in_order(stations, &page_request)?;
+    Ok(Json(page.into()))
+}
+
+async fn get_stations_by_bounding_box<T: QueryUseCase>(
+    State(query_use_case): State<Arc<T>>,
+    Query(params): Query<BoundingBoxParams>,
+) -> RestResult<MultipleStationResponse> {
+    let bounding_box = BoundingBox::new(
+        params.min_latitude,
+        params.min_longitude,
+        params.max_latitude,
+        params.max_longitude,
+    );
+    let filter = StationFilter::new(params.line_type, params.company_id, params.status);
+    let field_mask = parse_fields(params.fields.as_deref())?;
+    let page_request =
+        PageRequest::new(params.page_size, params.page_token).or_first_page(params.limit);
+    let limit = page_request.fetch_limit(params.limit)?;
+    let stations = query_use_case
+        .get_stations_by_bounding_box(&bounding_box, &filter, limit, &field_mask)
+        .await?;
+    let page = paginate_in_order(stations, &page_request)?;
+    Ok(Json(page.into()))
+}
+
+async fn get_stations_by_name<T: QueryUseCase>(
+    State(query_use_case): State<Arc<T>>,
+    Query(params): Query<NameParams>,
+) -> RestResult<MultipleStationResponse> {
+    let field_mask = parse_fields(params.fields.as_deref())?;
+    let page_request = PageRequest::new(params.page_size, params.page_token);
+    let limit = page_request.fetch_limit(params.limit)?;
+    let stations = query_use_case
+        .get_stations_by_name(
+            params.name,
+            limit,
+            params.from_station_group_id,
+            &field_mask,
+        )
+        .await?;
+    let page = paginate_in_order(stations, &page_request)?;
+    Ok(Json(page.into()))
+}
+
+async fn get_train_types_by_station_id<T: QueryUseCase>(
+    State(query_use_case): State<Arc<T>>,
+    Path(station_id): Path<u32>,
+    Query(params): Query<PageParams>,
+) -> RestResult<MultipleTrainTypeResponse> {
+    let page_request = PageRequest::new(params.page_size, params.page_token);
+    let train_types = query_use_case
+        .get_train_types_by_station_id(station_id)
+        .await?;
+    let page = paginate_in_order(train_types, &page_request)?;
+    Ok(Json(page.into()))
+}
+
+async fn get_lines_by_name<T: QueryUseCase>(
+    State(query_use_case): State<Arc<T>>,
+    Query(params): Query<NameParams>,
+) -> RestResult<MultipleLineResponse> {
+    let page_request = PageRequest::new(params.page_size, params.page_token);
+    let limit = page_request.fetch_limit(params.limit)?;
+    let lines = query_use_case.get_lines_by_name(params.name, limit).await?;
+    let page = paginate_in_order(lines, &page_request)?;
+    Ok(Json(page.into()))
+}
+
+async fn get_line_by_id<T: QueryUseCase>(
+    State(query_use_case): State<Arc<T>>,
+    Path(line_id): Path<u32>,
+    Query(params): Query<AsOfParams>,
+) -> RestResult<SingleLineResponse> {
+    let as_of = parse_as_of(params.as_of.as_deref())?;
+
+    match query_use_case
+        .find_line_by_id(line_id, as_of.as_ref())
+        .await?
+    {
+        Some(line) => Ok(Json(SingleLineResponse {
+            line: Some(line.into()),
+        })),
+        None => Err(PresentationalError::NotFound(format!(
+            "Line with id {} not found",
+            line_id
+        ))),
+    }
+}
+
+async fn get_stations_by_line_id<T: QueryUseCase>(
+    State(query_use_case): State<Arc<T>>,
+    Path(line_id): Path<u32>,
+    Query(params): Query<LineStationsParams>,
+) -> RestResult<MultipleStationResponse> {
+    let as_of = parse_as_of(params.as_of.as_deref())?;
+    let field_mask = parse_fields(params.fields.as_deref())?;
+    let page_request = PageRequest::new(params.page_size, params.page_token);
+
+    let page = get_stations_page_by_line_id(
+        query_use_case.as_ref(),
+        line_id,
+        params.station_id,
+        as_of.as_ref(),
+        &field_mask,
+        &page_request,
+    )
+    .await?;
+    Ok(Json(page.into()))
+}
+
+async fn get_line_geojson<T: QueryUseCase>(
+    State(query_use_case): State<Arc<T>>,
+    Path(line_id): Path<u32>,
+) -> RestResult<FeatureCollection> {
+    let feature_collection = query_use_case.get_line_geojson(line_id).await?;
+    Ok(Json(feature_collection))
+}
+
+async fn get_stations_by_line_group_id<T: QueryUseCase>(
+    State(query_use_case): State<Arc<T>>,
+    Path(line_group_id): Path<u32>,
+    Query(params): Query<ServiceDayParams>,
+) -> RestResult<MultipleStationResponse> {
+    let service_day = parse_service_day(
+        parse_service_day_type(params.service_day.as_deref())?,
+        params.service_date.as_deref(),
+    )?;
+    let field_mask = parse_fields(params.fields.as_deref())?;
+    let page_request = PageRequest::new(params.page_size, params.page_token);
+    let stations = query_use_case
+        .get_stations_by_line_group_id(line_group_id, service_day, &field_mask)
+        .await?;
+    let page = paginate_in_order(stations, &page_request)?;
+    Ok(Json(page.into()))
+}
+
+async fn get_stopping_pattern_diff<T: QueryUseCase>(
+    State(query_use_case): State<Arc<T>>,
+    Query(params): Query<StoppingPatternDiffParams>,
+) -> RestResult<StoppingPatternDiffResponse> {
+    let pattern = query_use_case
+        .get_stopping_pattern_diff(params.first_line_group_id, params.second_line_group_id)
+        .await?;
+    Ok(Json(pattern.into()))
+}
+
+async fn get_routes<T: QueryUseCase>(
+    State(query_use_case): State<Arc<T>>,
+    Query(params): Query<RouteParams>,
+) -> RestResult<RouteResponse> {
+    let service_day = parse_service_day(
+        parse_service_day_type(params.service_day.as_deref())?,
+        params.service_date.as_deref(),
+    )?;
+    let routes = query_use_case
+        .get_routes(
+            params.from_station_group_id,
+            params.to_station_group_id,
+            service_day,
+        )
+        .await?;
+    Ok(Json(RouteResponse { routes }))
+}
+
+async fn get_journeys<T: QueryUseCase>(
+    State(query_use_case): State<Arc<T>>,
+    Query(params): Query<JourneyParams>,
+) -> RestResult<JourneyResponse> {
+    let criterion = parse_criterion(params.criterion.as_deref())?;
+    let journeys = query_use_case
+        .get_journeys(
+            params.from_station_group_id,
+            params.to_station_group_id,
+            criterion,
+            params.limit,
+        )
+        .await?;
+    Ok(Json(JourneyResponse {
+        journeys: journeys.into_iter().map(|journey| journey.into()).collect(),
+    }))
+}
+
+async fn get_autocomplete_suggestions<T: QueryUseCase>(
+    State(query_use_case): State<Arc<T>>,
+    Query(params): Query<AutocompleteParams>,
+) -> RestResult<AutocompleteResponse> {
+    let suggestions = query_use_case
+        .get_autocomplete_suggestions(&params.query, params.limit)
+        .await?;
+    Ok(Json(AutocompleteResponse {
+        suggestions: suggestions
+            .into_iter()
+            .map(|suggestion| suggestion.into())
+            .collect(),
+    }))
+}
+
+async fn get_prefectures<T: QueryUseCase>(
+    State(query_use_case): State<Arc<T>>,
+) -> RestResult<MultiplePrefectureResponse> {
+    let prefectures = query_use_case.get_prefectures().await?;
+    Ok(Json(MultiplePrefectureResponse {
+        prefectures: prefectures
+            .into_iter()
+            .map(|prefecture| prefecture.into())
+            .collect(),
+    }))
+}
+
+async fn get_stations_by_prefecture_id<T: QueryUseCase>(
+    State(query_use_case): State<Arc<T>>,
+    Path(prefecture_id): Path<u32>,
+    Query(params): Query<StationAsOfParams>,
+) -> RestResult<MultipleStationResponse> {
+    let as_of = parse_as_of(params.as_of.as_deref())?;
+    let field_mask = parse_fields(params.fields.as_deref())?;
+    let page_request = PageRequest::new(params.page_size, params.page_token);
+    let stations = query_use_case
+        .get_stations_by_prefecture_id(prefecture_id, as_of.as_ref(), &field_mask)
+        .await?;
+    let page = paginate(stations, &page_request, |station| {
+        vec![station.e_sort, station.station_cd]
+    })?;
+    Ok(Json(page.into()))
+}
+
+async fn get_lines_by_prefecture_id<T: QueryUseCase>(
+    State(query_use_case): State<Arc<T>>,
+    Path(prefecture_id): Path<u32>,
+    Query(params): Query<AsOfParams>,
+) -> RestResult<MultipleLineResponse> {
+    let as_of = parse_as_of(params.as_of.as_deref())?;
+    let page_request = PageRequest::new(params.page_size, params.page_token);
+    let lines = query_use_case
+        .get_lines_by_prefecture_id(prefecture_id, as_of.as_ref())
+        .await?;
+    let page = paginate(lines, &page_request, |line| vec![line.e_sort, line.line_cd])?;
+    Ok(Json(page.into()))
+}
+
+async fn get_companies<T: QueryUseCase>(
+    State(query_use_case): State<Arc<T>>,
+    Query(params): Query<CompanyParams>,
+) -> RestResult<MultipleCompanyResponse> {
+    let filter = CompanyFilter::new(params.company_type, params.railroad_id, params.status);
+    let page_request = PageRequest::new(params.page_size, params.page_token);
+    let companies = query_use_case.get_companies(&filter).await?;
+    let page = paginate(companies, &page_request, |company| {
+        vec![company.e_sort, company.company_cd]
+    })?;
+    Ok(Json(page.into()))
+}
+
+async fn get_company_by_id<T: QueryUseCase>(
+    State(query_use_case): State<Arc<T>>,
+    Path(company_id): Path<u32>,
+) -> RestResult<SingleCompanyResponse> {
+    match query_use_case.find_company_by_id(company_id).await? {
+        Some(company) => Ok(Json(SingleCompanyResponse {
+            company: Some(company.into()),
+        })),
+        None => Err(PresentationalError::NotFound(format!(
+            "Company with id {} not found",
+            company_id
+        ))),
+    }
+}
+
+async fn get_lines_by_company_id<T: QueryUseCase>(
+    State(query_use_case): State<Arc<T>>,
+    Path(company_id): Path<u32>,
+    Query(params): Query<AsOfParams>,
+) -> RestResult<MultipleLineResponse> {
+    let as_of = parse_as_of(params.as_of.as_deref())?;
+    let page_request = PageRequest::new(params.page_size, params.page_token);
+    let lines = query_use_case
+        .get_lines_by_company_id(company_id, as_of.as_ref())
+        .await?;
+    let page = paginate(lines, &page_request, |line| vec![line.e_sort, line.line_cd])?;
+    Ok(Json(page.into()))
+}
+
+async fn get_stations_by_company_id<T: QueryUseCase>(
+    State(query_use_case): State<Arc<T>>,
+    Path(company_id): Path<u32>,
+    Query(params): Query<StationAsOfParams>,
+) -> RestResult<MultipleStationResponse> {
+    let as_of = parse_as_of(params.as_of.as_deref())?;
+    let field_mask = parse_fields(params.fields.as_deref())?;
+    let page_request = PageRequest::new(params.page_size, params.page_token);
+    let stations = query_use_case
+        .get_stations_by_company_id(company_id, as_of.as_ref(), &field_mask)
+        .await?;
+    let page = paginate(stations, &page_request, |station| {
+        vec![station.e_sort, station.station_cd]
+    })?;
+    Ok(Json(page.into()))
+}
+
+async fn get_train_types<T: QueryUseCase>(
+    State(query_use_case): State<Arc<T>>,
+    Query(params): Query<TrainTypeParams>,
+) -> RestResult<MultipleTrainTypeResponse> {
+    let filter = TrainTypeFilter::new(params.kind, params.name, params.line_id, params.company_id);
+    let page_request = PageRequest::new(params.page_size, params.page_token);
+    let train_types = query_use_case.get_train_types(&filter).await?;
+    let page = paginate(train_types, &page_request, |train_type| {
+        vec![train_type.type_cd, train_type.line_group_cd]
+    })?;
+    Ok(Json(page.into()))
+}
+
+async fn get_train_type_by_id<T: QueryUseCase>(
+    State(query_use_case): State<Arc<T>>,
+    Path(type_id): Path<u32>,
+) -> RestResult<SingleTrainTypeResponse> {
+    match query_use_case.find_train_type_by_id(type_id).await? {
+        Some(train_type) => Ok(Json(SingleTrainTypeResponse {
+            train_type: Some(train_type.into()),
+        })),
+        None => Err(PresentationalError::NotFound(format!(
+            "Train type with id {} not found",
+            type_id
+        ))),
+    }
+}
+
+async fn get_stations_by_train_type<T: QueryUseCase>(
+    State(query_use_case): State<Arc<T>>,
+    Path(type_id): Path<u32>,
+    Query(params): Query<TrainTypeStationsParams>,
+) -> RestResult<MultipleStationResponse> {
+    let field_mask = parse_fields(params.fields.as_deref())?;
+    let page_request = PageRequest::new(params.page_size, params.page_token);
+    let stations = query_use_case
+        .get_stations_by_train_type(type_id, params.line_group_id, &field_mask)
+        .await?;
+    let page = paginate_in_order(stations, &page_request)?;
+    Ok(Json(page.into()))
+}
+
+async fn get_dataset_info<T: QueryUseCase>(
+    State(query_use_case): State<Arc<T>>,
+) -> RestResult<DatasetInfoResponse> {
+    match query_use_case.get_dataset_info().await? {
+        Some(info) => Ok(Json(info.into())),
+        None => Err(PresentationalError::NotFound(
+            "The dataset version has not been recorded".to_string(),
+        )),
+    }
+}
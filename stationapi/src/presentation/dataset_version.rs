@@ -0,0 +1,106 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use axum::http::{header::ACCESS_CONTROL_EXPOSE_HEADERS, HeaderValue, Request, Response};
+use tower::{Layer, Service};
+
+use crate::use_case::traits::query::QueryUseCase;
+
+// アプリはこの値が変わったら手元のキャッシュを捨てる
+pub const DATASET_VERSION_HEADER: &str = "x-dataset-version";
+
+// すべてのレスポンスにデータセットのバージョンを付ける
+pub struct DatasetVersionLayer<T: QueryUseCase> {
+    query_use_case: Arc<T>,
+}
+
+impl<T: QueryUseCase> DatasetVersionLayer<T> {
+    pub fn new(query_use_case: Arc<T>) -> Self {
+        Self { query_use_case }
+    }
+}
+
+impl<T: QueryUseCase> Clone for DatasetVersionLayer<T> {
+    fn clone(&self) -> Self {
+        Self {
+            query_use_case: Arc::clone(&self.query_use_case),
+        }
+    }
+}
+
+impl<S, T: QueryUseCase> Layer<S> for DatasetVersionLayer<T> {
+    type Service = DatasetVersionService<S, T>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        DatasetVersionService {
+            inner,
+            query_use_case: Arc::clone(&self.query_use_case),
+        }
+    }
+}
+
+pub struct DatasetVersionService<S, T: QueryUseCase> {
+    inner: S,
+    query_use_case: Arc<T>,
+}
+
+impl<S: Clone, T: QueryUseCase> Clone for DatasetVersionService<S, T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            query_use_case: Arc::clone(&self.query_use_case),
+        }
+    }
+}
+
+impl<S, T, ReqBody, ResBody> Service<Request<ReqBody>> for DatasetVersionService<S, T>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    T: QueryUseCase,
+    ReqBody: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<ReqBody>) -> Self::Future {
+        // NOTE: poll_readyを済ませたほうのサービスで処理する
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let query_use_case = Arc::clone(&self.query_use_case);
+
+        Box::pin(async move {
+            // NOTE: 読み込み時に取っておいた値なのでDBには問い合わせない。なくてもレスポンスは返す
+            let version = match query_use_case.get_dataset_info().await {
+                Ok(Some(info)) => HeaderValue::from_str(&info.version).ok(),
+                Ok(None) | Err(_) => None,
+            };
+
+            let mut response = inner.call(request).await?;
+            if let Some(version) = version {
+                let headers = response.headers_mut();
+                headers.insert(DATASET_VERSION_HEADER, version);
+                // NOTE: gRPC-WebのCORSでブラウザからも読めるようにする
+                if let Some(exposed) = headers
+                    .get(ACCESS_CONTROL_EXPOSE_HEADERS)
+                    .and_then(|value| value.to_str().ok())
+                    .and_then(|value| {
+                        HeaderValue::from_str(&format!("{},{}", value, DATASET_VERSION_HEADER)).ok()
+                    })
+                {
+                    headers.insert(ACCESS_CONTROL_EXPOSE_HEADERS, exposed);
+                }
+            }
+            Ok(response)
+        })
+    }
+}
@@ -1,5 +1,11 @@
 use std::sync::Arc;
 
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde_json::json;
 use thiserror::Error;
 
 use crate::use_case::error::UseCaseError;
@@ -8,6 +14,10 @@ use crate::use_case::error::UseCaseError;
 pub enum PresentationalError {
     #[error("{0}")]
     NotFound(String),
+    #[error("{0}")]
+    InvalidArgument(String),
+    #[error("{0}")]
+    PermissionDenied(String),
     #[error(transparent)]
     OtherError(Arc<anyhow::Error>),
     #[error("{0}")]
@@ -18,6 +28,7 @@ impl From<UseCaseError> for PresentationalError {
     fn from(err: UseCaseError) -> Self {
         match err {
             UseCaseError::NotFound { .. } => PresentationalError::NotFound(err.to_string()),
+            UseCaseError::InvalidArgument(message) => PresentationalError::InvalidArgument(message),
             UseCaseError::Other(_) => {
                 PresentationalError::OtherError(Arc::new(anyhow::Error::new(err)))
             }
@@ -30,8 +41,29 @@ impl From<PresentationalError> for tonic::Status {
     fn from(err: PresentationalError) -> Self {
         match err {
             PresentationalError::NotFound(message) => tonic::Status::not_found(message),
+            PresentationalError::InvalidArgument(message) => {
+                tonic::Status::invalid_argument(message)
+            }
+            PresentationalError::PermissionDenied(message) => {
+                tonic::Status::permission_denied(message)
+            }
             PresentationalError::OtherError(err) => tonic::Status::internal(err.to_string()),
             PresentationalError::Unexpected(message) => tonic::Status::internal(message),
         }
     }
 }
+
+impl IntoResponse for PresentationalError {
+    fn into_response(self) -> Response {
+        let status = match self {
+            PresentationalError::NotFound(_) => StatusCode::NOT_FOUND,
+            PresentationalError::InvalidArgument(_) => StatusCode::BAD_REQUEST,
+            PresentationalError::PermissionDenied(_) => StatusCode::FORBIDDEN,
+            PresentationalError::OtherError(_) | PresentationalError::Unexpected(_) => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+        };
+
+        (status, Json(json!({ "error": self.to_string() }))).into_response()
+    }
+}
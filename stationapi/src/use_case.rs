@@ -1,4 +1,6 @@
+pub mod cache;
 pub mod dto;
 pub mod error;
 pub mod interactor;
+pub mod pagination;
 pub mod traits;
@@ -0,0 +1,318 @@
+use std::{
+    any::Any,
+    collections::{BTreeMap, HashMap},
+    future::Future,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+// 10,000件、5分
+pub const DEFAULT_CACHE_CAPACITY: usize = 10_000;
+pub const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(300);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CacheConfig {
+    // 0ならキャッシュしない
+    // NOTE: 件数の上限で、値の大きさは見ない。路線の全駅のような大きな値ばかり入ると
+    // 件数のわりにメモリを使うので、メモリが足りなければこれを小さくする
+    pub capacity: usize,
+    pub ttl: Duration,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            capacity: DEFAULT_CACHE_CAPACITY,
+            ttl: DEFAULT_CACHE_TTL,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+    pub invalidations: u64,
+    pub entries: usize,
+}
+
+impl CacheStats {
+    pub fn hit_ratio(&self) -> f64 {
+        match self.hits + self.misses {
+            0 => 0.0,
+            total => self.hits as f64 / total as f64,
+        }
+    }
+}
+
+struct CacheEntry {
+    value: Arc<dyn Any + Send + Sync>,
+    expires_at: Instant,
+    last_used: u64,
+}
+
+#[derive(Default)]
+struct CacheEntries {
+    entries: HashMap<String, CacheEntry>,
+    // last_used -> key。先頭が最も長く使われていないもの
+    recency: BTreeMap<u64, String>,
+    tick: u64,
+    // invalidateのたびに進める。古い世代で計算した値は入れない
+    generation: u64,
+}
+
+impl CacheEntries {
+    fn touch(&mut self, key: &str) {
+        self.tick += 1;
+        let tick = self.tick;
+        if let Some(entry) = self.entries.get_mut(key) {
+            self.recency.remove(&entry.last_used);
+            entry.last_used = tick;
+            self.recency.insert(tick, key.to_string());
+        }
+    }
+
+    fn remove(&mut self, key: &str) {
+        if let Some(entry) = self.entries.remove(key) {
+            self.recency.remove(&entry.last_used);
+        }
+    }
+}
+
+// 上限件数とTTLを持つLRUキャッシュ。値の型はキーごとに異なってよい
+pub struct QueryCache {
+    config: CacheConfig,
+    entries: Mutex<CacheEntries>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
+    invalidations: AtomicU64,
+}
+
+impl QueryCache {
+    pub fn new(config: CacheConfig) -> Self {
+        Self {
+            config,
+            entries: Mutex::new(CacheEntries::default()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            evictions: AtomicU64::new(0),
+            invalidations: AtomicU64::new(0),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.config.capacity > 0
+    }
+
+    pub fn get<V: Clone + Send + Sync + 'static>(&self, key: &str) -> Option<V> {
+        let mut entries = self.entries.lock().unwrap();
+        let value = match entries.entries.get(key) {
+            Some(entry) if entry.expires_at > Instant::now() => {
+                entry.value.downcast_ref::<V>().cloned()
+            }
+            Some(_) => {
+                entries.remove(key);
+                None
+            }
+            None => None,
+        };
+        match value {
+            Some(value) => {
+                entries.touch(key);
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                Some(value)
+            }
+            None => {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+
+    fn generation(&self) -> u64 {
+        self.entries.lock().unwrap().generation
+    }
+
+    fn insert_if_current<V: Send + Sync + 'static>(&self, key: String, value: V, generation: u64) {
+        let mut entries = self.entries.lock().unwrap();
+        // NOTE: 計算中にinvalidateされた値は古いデータから作られているかもしれない
+        if entries.generation != generation {
+            return;
+        }
+
+        entries.remove(&key);
+        while entries.entries.len() >= self.config.capacity {
+            let Some((_, oldest)) = entries.recency.pop_first() else {
+                break;
+            };
+            entries.entries.remove(&oldest);
+            self.evictions.fetch_add(1, Ordering::Relaxed);
+        }
+
+        entries.tick += 1;
+        let tick = entries.tick;
+        entries.recency.insert(tick, key.clone());
+        entries.entries.insert(
+            key,
+            CacheEntry {
+                value: Arc::new(value),
+                expires_at: Instant::now() + self.config.ttl,
+                last_used: tick,
+            },
+        );
+    }
+
+    pub fn insert<V: Send + Sync + 'static>(&self, key: String, value: V) {
+        if !self.is_enabled() {
+            return;
+        }
+        let generation = self.generation();
+        self.insert_if_current(key, value, generation);
+    }
+
+    // キャッシュにあればそれを返し、なければfetchした結果を入れる。エラーは入れない
+    pub async fn get_or_try_insert_with<V, E, F>(&self, key: String, fetch: F) -> Result<V, E>
+    where
+        V: Clone + Send + Sync + 'static,
+        F: Future<Output = Result<V, E>>,
+    {
+        if !self.is_enabled() {
+            return fetch.await;
+        }
+        if let Some(value) = self.get::<V>(&key) {
+            return Ok(value);
+        }
+
+        let generation = self.generation();
+        let value = fetch.await?;
+        self.insert_if_current(key, value.clone(), generation);
+
+        Ok(value)
+    }
+
+    // データを入れ替えたあとに呼ぶ
+    pub fn invalidate(&self) {
+        let mut entries = self.entries.lock().unwrap();
+        entries.entries.clear();
+        entries.recency.clear();
+        entries.generation += 1;
+        self.invalidations.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            evictions: self.evictions.load(Ordering::Relaxed),
+            invalidations: self.invalidations.load(Ordering::Relaxed),
+            entries: self.entries.lock().unwrap().entries.len(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::sync::oneshot;
+
+    use super::{CacheConfig, QueryCache};
+
+    fn new_cache(capacity: usize, ttl: Duration) -> QueryCache {
+        QueryCache::new(CacheConfig { capacity, ttl })
+    }
+
+    #[tokio::test]
+    async fn get_or_try_insert_with() {
+        let cache = new_cache(10, Duration::from_secs(60));
+        let value: Result<Vec<u32>, ()> = cache
+            .get_or_try_insert_with("a".to_string(), async { Ok(vec![1, 2]) })
+            .await;
+        assert_eq!(value, Ok(vec![1, 2]));
+        // 2回目はfetchしない
+        let value: Result<Vec<u32>, ()> = cache
+            .get_or_try_insert_with("a".to_string(), async { unreachable!() })
+            .await;
+        assert_eq!(value, Ok(vec![1, 2]));
+        // エラーは入れない
+        let value: Result<u32, &str> = cache
+            .get_or_try_insert_with("b".to_string(), async { Err("failed") })
+            .await;
+        assert_eq!(value, Err("failed"));
+        assert_eq!(cache.get::<u32>("b"), None);
+
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses, stats.entries), (1, 3, 1));
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let cache = new_cache(2, Duration::from_secs(60));
+        cache.insert("a".to_string(), 1u32);
+        cache.insert("b".to_string(), 2u32);
+        assert_eq!(cache.get::<u32>("a"), Some(1));
+        cache.insert("c".to_string(), 3u32);
+
+        assert_eq!(cache.get::<u32>("b"), None);
+        assert_eq!(cache.get::<u32>("a"), Some(1));
+        assert_eq!(cache.get::<u32>("c"), Some(3));
+        assert_eq!(cache.stats().evictions, 1);
+    }
+
+    #[test]
+    fn expires_and_invalidates() {
+        let cache = new_cache(10, Duration::ZERO);
+        cache.insert("a".to_string(), 1u32);
+        assert_eq!(cache.get::<u32>("a"), None);
+        assert_eq!(cache.stats().entries, 0);
+
+        let cache = QueryCache::new(CacheConfig::default());
+        cache.insert("a".to_string(), 1u32);
+        cache.invalidate();
+        assert_eq!(cache.get::<u32>("a"), None);
+        assert_eq!(cache.stats().invalidations, 1);
+
+        // 無効にしたキャッシュには入れない
+        let cache = new_cache(0, Duration::from_secs(60));
+        cache.insert("a".to_string(), 1u32);
+        assert_eq!(cache.get::<u32>("a"), None);
+    }
+
+    #[tokio::test]
+    async fn drops_values_computed_before_invalidate() {
+        let cache = new_cache(10, Duration::from_secs(60));
+        let (started_tx, started_rx) = oneshot::channel();
+        let (release_tx, release_rx) = oneshot::channel();
+
+        // 古いデータで計算している途中にinvalidateされる
+        let (value, _) = tokio::join!(
+            cache.get_or_try_insert_with("a".to_string(), async {
+                started_tx.send(()).unwrap();
+                release_rx.await.unwrap();
+                Ok::<u32, ()>(1)
+            }),
+            async {
+                started_rx.await.unwrap();
+                cache.invalidate();
+                release_tx.send(()).unwrap();
+            },
+        );
+
+        // 呼び出し元には返すが、キャッシュには入れない
+        assert_eq!(value, Ok(1));
+        assert_eq!(cache.get::<u32>("a"), None);
+        assert_eq!(cache.stats().entries, 0);
+
+        // invalidateのあとに計算した値は入る
+        let value: Result<u32, ()> = cache
+            .get_or_try_insert_with("a".to_string(), async { Ok(2) })
+            .await;
+        assert_eq!(value, Ok(2));
+        assert_eq!(cache.get::<u32>("a"), Some(2));
+    }
+}
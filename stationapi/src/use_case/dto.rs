@@ -1,6 +1,15 @@
 pub mod company;
+pub mod dataset_info;
+pub mod dataset_summary;
+pub mod journey;
 pub mod line;
 pub mod line_symbol;
+pub mod location_tracker;
+pub mod pagination;
+pub mod prefecture;
 pub mod station;
 pub mod station_number;
+pub mod stopping_pattern;
+pub mod suggestion;
 pub mod train_type;
+pub mod travel_direction;
@@ -0,0 +1,11 @@
+use crate::{domain::entity::dataset_info::DatasetInfo, station_api::DatasetInfoResponse};
+
+impl From<DatasetInfo> for DatasetInfoResponse {
+    fn from(info: DatasetInfo) -> Self {
+        Self {
+            version: info.version,
+            content_hash: info.content_hash,
+            created_at: info.created_at,
+        }
+    }
+}
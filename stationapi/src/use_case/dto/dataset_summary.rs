@@ -0,0 +1,12 @@
+use crate::{domain::entity::dataset_summary::DatasetSummary, station_api::ReloadDatasetResponse};
+
+impl From<DatasetSummary> for ReloadDatasetResponse {
+    fn from(summary: DatasetSummary) -> Self {
+        Self {
+            companies: summary.companies,
+            lines: summary.lines,
+            stations: summary.stations,
+            train_types: summary.train_types,
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use crate::{
+    domain::entity::journey::{Journey, JourneyLeg},
+    station_api::{Journey as GrpcJourney, JourneyLeg as GrpcJourneyLeg},
+};
+
+impl From<JourneyLeg> for GrpcJourneyLeg {
+    fn from(leg: JourneyLeg) -> Self {
+        let JourneyLeg {
+            line_cd: _,
+            line_group_cd: _,
+            station_cds: _,
+            stops,
+            train_type,
+        } = leg;
+        Self {
+            line: stops
+                .first()
+                .and_then(|stop| stop.line.clone())
+                .map(|line| (*line).into()),
+            train_type: train_type.map(|tt| (*tt).into()),
+            stops: stops.into_iter().map(|stop| stop.into()).collect(),
+        }
+    }
+}
+
+impl From<Journey> for GrpcJourney {
+    fn from(journey: Journey) -> Self {
+        let Journey {
+            legs,
+            station_count,
+            transfer_count,
+        } = journey;
+        Self {
+            legs: legs.into_iter().map(|leg| leg.into()).collect(),
+            station_count,
+            transfer_count,
+        }
+    }
+}
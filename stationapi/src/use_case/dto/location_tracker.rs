@@ -0,0 +1,18 @@
+use crate::{domain::entity::location_tracker::LocationUpdate, station_api::TrackLocationResponse};
+
+impl From<LocationUpdate> for TrackLocationResponse {
+    fn from(update: LocationUpdate) -> Self {
+        let LocationUpdate {
+            state,
+            station,
+            next_station,
+            distance,
+        } = update;
+        Self {
+            state: state.into(),
+            station: Some(station.into()),
+            next_station: next_station.map(|station| station.into()),
+            distance,
+        }
+    }
+}
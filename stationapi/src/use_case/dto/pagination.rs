@@ -0,0 +1,52 @@
+use crate::{
+    domain::entity::{company::Company, line::Line, station::Station, train_type::TrainType},
+    station_api::{
+        MultipleCompanyResponse, MultipleLineResponse, MultipleStationResponse,
+        MultipleTrainTypeResponse,
+    },
+    use_case::pagination::Page,
+};
+
+impl From<Page<Station>> for MultipleStationResponse {
+    fn from(page: Page<Station>) -> Self {
+        Self {
+            stations: page
+                .items
+                .into_iter()
+                .map(|station| station.into())
+                .collect(),
+            next_page_token: page.next_page_token,
+        }
+    }
+}
+
+impl From<Page<Line>> for MultipleLineResponse {
+    fn from(page: Page<Line>) -> Self {
+        Self {
+            lines: page.items.into_iter().map(|line| line.into()).collect(),
+            next_page_token: page.next_page_token,
+        }
+    }
+}
+
+impl From<Page<TrainType>> for MultipleTrainTypeResponse {
+    fn from(page: Page<TrainType>) -> Self {
+        Self {
+            train_types: page.items.into_iter().map(|tt| tt.into()).collect(),
+            next_page_token: page.next_page_token,
+        }
+    }
+}
+
+impl From<Page<Company>> for MultipleCompanyResponse {
+    fn from(page: Page<Company>) -> Self {
+        Self {
+            companies: page
+                .items
+                .into_iter()
+                .map(|company| company.into())
+                .collect(),
+            next_page_token: page.next_page_token,
+        }
+    }
+}
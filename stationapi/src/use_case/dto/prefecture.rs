@@ -0,0 +1,14 @@
+use crate::{domain::entity::prefecture::Prefecture, station_api::Prefecture as GrpcPrefecture};
+
+impl From<Prefecture> for GrpcPrefecture {
+    fn from(prefecture: Prefecture) -> Self {
+        Self {
+            id: prefecture.pref_cd,
+            name: prefecture.pref_name,
+            name_katakana: prefecture.pref_name_k,
+            name_roman: prefecture.pref_name_r,
+            name_chinese: prefecture.pref_name_zh,
+            name_korean: prefecture.pref_name_ko,
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use crate::{
+    domain::entity::stopping_pattern::{StoppingPattern, StoppingPatternStation},
+    station_api::{
+        StoppingPatternDiffResponse, StoppingPatternStation as GrpcStoppingPatternStation,
+    },
+};
+
+impl From<StoppingPatternStation> for GrpcStoppingPatternStation {
+    fn from(station: StoppingPatternStation) -> Self {
+        let differs = station.differs();
+        let StoppingPatternStation {
+            station,
+            first_stop_condition,
+            second_stop_condition,
+        } = station;
+        Self {
+            station: Some(station.into()),
+            first_stop_condition: first_stop_condition.map(|condition| condition.into()),
+            second_stop_condition: second_stop_condition.map(|condition| condition.into()),
+            differs,
+        }
+    }
+}
+
+impl From<StoppingPattern> for StoppingPatternDiffResponse {
+    fn from(pattern: StoppingPattern) -> Self {
+        let StoppingPattern {
+            first_train_type,
+            second_train_type,
+            stations,
+        } = pattern;
+        Self {
+            first_train_type: first_train_type.map(|train_type| train_type.into()),
+            second_train_type: second_train_type.map(|train_type| train_type.into()),
+            stations: stations.into_iter().map(|station| station.into()).collect(),
+        }
+    }
+}
@@ -0,0 +1,26 @@
+use crate::{
+    domain::entity::suggestion::{Suggestion, SuggestionType},
+    station_api::{Suggestion as GrpcSuggestion, SuggestionType as GrpcSuggestionType},
+};
+
+impl From<SuggestionType> for GrpcSuggestionType {
+    fn from(suggestion_type: SuggestionType) -> Self {
+        match suggestion_type {
+            SuggestionType::StationGroup => GrpcSuggestionType::StationGroupSuggestion,
+            SuggestionType::Line => GrpcSuggestionType::LineSuggestion,
+        }
+    }
+}
+
+impl From<Suggestion> for GrpcSuggestion {
+    fn from(suggestion: Suggestion) -> Self {
+        Self {
+            r#type: GrpcSuggestionType::from(suggestion.suggestion_type).into(),
+            id: suggestion.id,
+            name: suggestion.name,
+            matched_name: suggestion.matched_name,
+            matched_field: suggestion.matched_field,
+            language: suggestion.language,
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use crate::{
+    domain::entity::travel_direction::TravelDirectionEstimate, station_api::TravelDirectionResponse,
+};
+
+impl From<TravelDirectionEstimate> for TravelDirectionResponse {
+    fn from(estimate: TravelDirectionEstimate) -> Self {
+        let TravelDirectionEstimate {
+            direction,
+            next_station,
+            train_type_matched,
+        } = estimate;
+        Self {
+            direction: Some(direction.train_direction().into()),
+            next_station: next_station.map(|station| station.into()),
+            train_type_matched,
+        }
+    }
+}
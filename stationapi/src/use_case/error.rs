@@ -9,6 +9,8 @@ pub enum UseCaseError {
         entity_type: &'static str,
         entity_id: String,
     },
+    #[error("{0}")]
+    InvalidArgument(String),
     #[error(transparent)]
     Other(anyhow::Error),
     #[error("{0}")]
@@ -1 +1,4 @@
+pub mod cached_query;
 pub mod query;
+pub mod reload;
+pub mod reloadable_query;
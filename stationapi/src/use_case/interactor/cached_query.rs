@@ -0,0 +1,806 @@
+use std::{fmt::Debug, sync::Arc};
+
+use async_trait::async_trait;
+
+use crate::{
+    domain::entity::{
+        company::Company,
+        dataset_info::DatasetInfo,
+        geojson::FeatureCollection,
+        journey::Journey,
+        line::Line,
+        line_symbol::LineSymbol,
+        location_tracker::LocationTracker,
+        misc::{
+            AsOf, BoundingBox, CompanyFilter, StationFieldMask, StationFilter,
+            StationIdWithDistance, TrainTypeFilter,
+        },
+        prefecture::Prefecture,
+        service_day::ServiceDay,
+        station::Station,
+        station_number::StationNumber,
+        stopping_pattern::StoppingPattern,
+        suggestion::Suggestion,
+        train_type::TrainType,
+        travel_direction::TravelDirectionEstimate,
+    },
+    station_api::{JourneySearchCriterion, Route},
+    use_case::{cache::QueryCache, error::UseCaseError, traits::query::QueryUseCase},
+};
+
+// QueryUseCaseの結果を引数ごとにキャッシュする。データを入れ替えたらcache().invalidate()を呼ぶこと
+pub struct CachedQueryInteractor<T: QueryUseCase> {
+    inner: T,
+    cache: Arc<QueryCache>,
+}
+
+impl<T: QueryUseCase> CachedQueryInteractor<T> {
+    pub fn new(inner: T, cache: Arc<QueryCache>) -> Self {
+        Self { inner, cache }
+    }
+
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    pub fn cache(&self) -> &Arc<QueryCache> {
+        &self.cache
+    }
+}
+
+// NOTE: キーはメソッド名と引数のDebug表現。座標で引くものはほぼ確実にキャッシュがヒットしないと思うので、
+// キャッシュを使わずにそのまま問い合わせる
+fn cache_key(method: &str, args: impl Debug) -> String {
+    format!("{method}:{args:?}")
+}
+
+#[async_trait]
+impl<T: QueryUseCase> QueryUseCase for CachedQueryInteractor<T> {
+    async fn find_station_by_id(
+        &self,
+        station_id: u32,
+        field_mask: &StationFieldMask,
+    ) -> Result<Option<Station>, UseCaseError> {
+        let key = cache_key("find_station_by_id", (station_id, field_mask));
+        self.cache
+            .get_or_try_insert_with(key, self.inner.find_station_by_id(station_id, field_mask))
+            .await
+    }
+
+    async fn get_stations_by_id_vec(
+        &self,
+        station_ids: &[u32],
+        field_mask: &StationFieldMask,
+    ) -> Result<Vec<Station>, UseCaseError> {
+        let key = cache_key("get_stations_by_id_vec", (station_ids, field_mask));
+        self.cache
+            .get_or_try_insert_with(
+                key,
+                self.inner.get_stations_by_id_vec(station_ids, field_mask),
+            )
+            .await
+    }
+
+    async fn get_stations_by_id_vec_without_attributes(
+        &self,
+        station_ids: &[u32],
+    ) -> Result<Vec<Station>, UseCaseError> {
+        let key = cache_key("get_stations_by_id_vec_without_attributes", station_ids);
+        self.cache
+            .get_or_try_insert_with(
+                key,
+                self.inner
+                    .get_stations_by_id_vec_without_attributes(station_ids),
+            )
+            .await
+    }
+
+    async fn get_stations_by_group_id(
+        &self,
+        station_group_id: u32,
+        field_mask: &StationFieldMask,
+    ) -> Result<Vec<Station>, UseCaseError> {
+        let key = cache_key("get_stations_by_group_id", (station_group_id, field_mask));
+        self.cache
+            .get_or_try_insert_with(
+                key,
+                self.inner
+                    .get_stations_by_group_id(station_group_id, field_mask),
+            )
+            .await
+    }
+
+    async fn get_stations_by_group_id_vec(
+        &self,
+        station_group_id_vec: &[u32],
+    ) -> Result<Vec<Station>, UseCaseError> {
+        let key = cache_key("get_stations_by_group_id_vec", station_group_id_vec);
+        self.cache
+            .get_or_try_insert_with(
+                key,
+                self.inner
+                    .get_stations_by_group_id_vec(station_group_id_vec),
+            )
+            .await
+    }
+
+    async fn get_stations_by_coordinates(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        limit: Option<u32>,
+        field_mask: &StationFieldMask,
+    ) -> Result<Vec<Station>, UseCaseError> {
+        self.inner
+            .get_stations_by_coordinates(latitude, longitude, limit, field_mask)
+            .await
+    }
+
+    async fn get_stations_by_coordinates_without_attributes(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        limit: Option<u32>,
+    ) -> Result<Vec<Station>, UseCaseError> {
+        self.inner
+            .get_stations_by_coordinates_without_attributes(latitude, longitude, limit)
+            .await
+    }
+
+    async fn get_stations_by_radius(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        radius_in_km: f64,
+        filter: &StationFilter,
+        limit: Option<u32>,
+        field_mask: &StationFieldMask,
+    ) -> Result<Vec<Station>, UseCaseError> {
+        self.inner
+            .get_stations_by_radius(latitude, longitude, radius_in_km, filter, limit, field_mask)
+            .await
+    }
+
+    async fn get_stations_by_bounding_box(
+        &self,
+        bounding_box: &BoundingBox,
+        filter: &StationFilter,
+        limit: Option<u32>,
+        field_mask: &StationFieldMask,
+    ) -> Result<Vec<Station>, UseCaseError> {
+        self.inner
+            .get_stations_by_bounding_box(bounding_box, filter, limit, field_mask)
+            .await
+    }
+
+    async fn get_stations_by_line_id(
+        &self,
+        line_id: u32,
+        station_id: Option<u32>,
+        field_mask: &StationFieldMask,
+    ) -> Result<Vec<Station>, UseCaseError> {
+        let key = cache_key("get_stations_by_line_id", (line_id, station_id, field_mask));
+        self.cache
+            .get_or_try_insert_with(
+                key,
+                self.inner
+                    .get_stations_by_line_id(line_id, station_id, field_mask),
+            )
+            .await
+    }
+
+    async fn get_stations_by_line_id_without_attributes(
+        &self,
+        line_id: u32,
+    ) -> Result<Vec<Station>, UseCaseError> {
+        let key = cache_key("get_stations_by_line_id_without_attributes", line_id);
+        self.cache
+            .get_or_try_insert_with(
+                key,
+                self.inner
+                    .get_stations_by_line_id_without_attributes(line_id),
+            )
+            .await
+    }
+
+    async fn get_stations_by_line_id_vec_without_attributes(
+        &self,
+        line_id_vec: &[u32],
+    ) -> Result<Vec<Station>, UseCaseError> {
+        let key = cache_key(
+            "get_stations_by_line_id_vec_without_attributes",
+            line_id_vec,
+        );
+        self.cache
+            .get_or_try_insert_with(
+                key,
+                self.inner
+                    .get_stations_by_line_id_vec_without_attributes(line_id_vec),
+            )
+            .await
+    }
+
+    async fn get_stations_by_line_id_as_of(
+        &self,
+        line_id: u32,
+        as_of: &AsOf,
+        field_mask: &StationFieldMask,
+    ) -> Result<Vec<Station>, UseCaseError> {
+        let key = cache_key(
+            "get_stations_by_line_id_as_of",
+            (line_id, as_of, field_mask),
+        );
+        self.cache
+            .get_or_try_insert_with(
+                key,
+                self.inner
+                    .get_stations_by_line_id_as_of(line_id, as_of, field_mask),
+            )
+            .await
+    }
+
+    async fn get_stations_by_name(
+        &self,
+        station_name: String,
+        get_stations_by_name: Option<u32>,
+        from_station_group_id: Option<u32>,
+        field_mask: &StationFieldMask,
+    ) -> Result<Vec<Station>, UseCaseError> {
+        let key = cache_key(
+            "get_stations_by_name",
+            (
+                &station_name,
+                get_stations_by_name,
+                from_station_group_id,
+                field_mask,
+            ),
+        );
+        self.cache
+            .get_or_try_insert_with(
+                key,
+                self.inner.get_stations_by_name(
+                    station_name,
+                    get_stations_by_name,
+                    from_station_group_id,
+                    field_mask,
+                ),
+            )
+            .await
+    }
+
+    async fn find_company_by_id_vec(
+        &self,
+        company_id_vec: &[u32],
+    ) -> Result<Vec<Company>, UseCaseError> {
+        let key = cache_key("find_company_by_id_vec", company_id_vec);
+        self.cache
+            .get_or_try_insert_with(key, self.inner.find_company_by_id_vec(company_id_vec))
+            .await
+    }
+
+    async fn update_station_vec_with_attributes(
+        &self,
+        stations: Vec<Station>,
+        line_group_id: Option<u32>,
+        field_mask: &StationFieldMask,
+    ) -> Result<Vec<Station>, UseCaseError> {
+        self.inner
+            .update_station_vec_with_attributes(stations, line_group_id, field_mask)
+            .await
+    }
+
+    async fn get_lines_by_station_group_id(
+        &self,
+        station_group_id: u32,
+    ) -> Result<Vec<Line>, UseCaseError> {
+        let key = cache_key("get_lines_by_station_group_id", station_group_id);
+        self.cache
+            .get_or_try_insert_with(
+                key,
+                self.inner.get_lines_by_station_group_id(station_group_id),
+            )
+            .await
+    }
+
+    async fn get_lines_by_station_group_id_vec(
+        &self,
+        station_group_id_vec: &[u32],
+    ) -> Result<Vec<Line>, UseCaseError> {
+        let key = cache_key("get_lines_by_station_group_id_vec", station_group_id_vec);
+        self.cache
+            .get_or_try_insert_with(
+                key,
+                self.inner
+                    .get_lines_by_station_group_id_vec(station_group_id_vec),
+            )
+            .await
+    }
+
+    fn get_station_numbers(&self, station: &Station) -> Vec<StationNumber> {
+        self.inner.get_station_numbers(station)
+    }
+
+    fn get_line_symbols(&self, line: &Line) -> Vec<LineSymbol> {
+        self.inner.get_line_symbols(line)
+    }
+
+    fn extract_line_from_station(&self, station: &Station) -> Line {
+        self.inner.extract_line_from_station(station)
+    }
+
+    async fn get_stations_by_line_group_id(
+        &self,
+        line_group_id: u32,
+        service_day: Option<ServiceDay>,
+        field_mask: &StationFieldMask,
+    ) -> Result<Vec<Station>, UseCaseError> {
+        let key = cache_key(
+            "get_stations_by_line_group_id",
+            (line_group_id, service_day, field_mask),
+        );
+        self.cache
+            .get_or_try_insert_with(
+                key,
+                self.inner
+                    .get_stations_by_line_group_id(line_group_id, service_day, field_mask),
+            )
+            .await
+    }
+
+    async fn get_stations_by_line_group_id_without_attributes(
+        &self,
+        line_group_id: u32,
+        service_day: Option<ServiceDay>,
+    ) -> Result<Vec<Station>, UseCaseError> {
+        let key = cache_key(
+            "get_stations_by_line_group_id_without_attributes",
+            (line_group_id, service_day),
+        );
+        self.cache
+            .get_or_try_insert_with(
+                key,
+                self.inner
+                    .get_stations_by_line_group_id_without_attributes(line_group_id, service_day),
+            )
+            .await
+    }
+
+    async fn get_stations_by_line_group_id_vec_without_attributes(
+        &self,
+        line_group_id_vec: &[u32],
+    ) -> Result<Vec<Station>, UseCaseError> {
+        let key = cache_key(
+            "get_stations_by_line_group_id_vec_without_attributes",
+            line_group_id_vec,
+        );
+        self.cache
+            .get_or_try_insert_with(
+                key,
+                self.inner
+                    .get_stations_by_line_group_id_vec_without_attributes(line_group_id_vec),
+            )
+            .await
+    }
+
+    async fn get_lines_by_line_group_id_vec(
+        &self,
+        line_group_id_vec: &[u32],
+    ) -> Result<Vec<Line>, UseCaseError> {
+        let key = cache_key("get_lines_by_line_group_id_vec", line_group_id_vec);
+        self.cache
+            .get_or_try_insert_with(
+                key,
+                self.inner.get_lines_by_line_group_id_vec(line_group_id_vec),
+            )
+            .await
+    }
+
+    async fn get_train_types_by_station_id(
+        &self,
+        station_id: u32,
+    ) -> Result<Vec<TrainType>, UseCaseError> {
+        let key = cache_key("get_train_types_by_station_id", station_id);
+        self.cache
+            .get_or_try_insert_with(key, self.inner.get_train_types_by_station_id(station_id))
+            .await
+    }
+
+    async fn get_all_train_types_by_station_id_vec(
+        &self,
+        station_id_vec: &[u32],
+    ) -> Result<Vec<TrainType>, UseCaseError> {
+        let key = cache_key("get_all_train_types_by_station_id_vec", station_id_vec);
+        self.cache
+            .get_or_try_insert_with(
+                key,
+                self.inner
+                    .get_all_train_types_by_station_id_vec(station_id_vec),
+            )
+            .await
+    }
+
+    async fn get_train_types_by_station_id_vec(
+        &self,
+        station_id_vec: &[u32],
+        line_group_id: Option<u32>,
+    ) -> Result<Vec<TrainType>, UseCaseError> {
+        let key = cache_key(
+            "get_train_types_by_station_id_vec",
+            (station_id_vec, line_group_id),
+        );
+        self.cache
+            .get_or_try_insert_with(
+                key,
+                self.inner
+                    .get_train_types_by_station_id_vec(station_id_vec, line_group_id),
+            )
+            .await
+    }
+
+    async fn get_station_id_and_distance_by_coordinates(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        line_id: Option<u32>,
+    ) -> Result<StationIdWithDistance, UseCaseError> {
+        self.inner
+            .get_station_id_and_distance_by_coordinates(latitude, longitude, line_id)
+            .await
+    }
+
+    async fn create_location_tracker(
+        &self,
+        line_id: u32,
+        line_group_id: Option<u32>,
+    ) -> Result<LocationTracker, UseCaseError> {
+        self.inner
+            .create_location_tracker(line_id, line_group_id)
+            .await
+    }
+
+    async fn get_travel_direction(
+        &self,
+        coordinates: &[(f64, f64)],
+        line_id: u32,
+        line_group_id: Option<u32>,
+    ) -> Result<Option<TravelDirectionEstimate>, UseCaseError> {
+        self.inner
+            .get_travel_direction(coordinates, line_id, line_group_id)
+            .await
+    }
+
+    async fn get_autocomplete_suggestions(
+        &self,
+        query: &str,
+        limit: Option<u32>,
+    ) -> Result<Vec<Suggestion>, UseCaseError> {
+        let key = cache_key("get_autocomplete_suggestions", (query, limit));
+        self.cache
+            .get_or_try_insert_with(key, self.inner.get_autocomplete_suggestions(query, limit))
+            .await
+    }
+
+    async fn get_routes(
+        &self,
+        from_station_id: u32,
+        to_station_id: u32,
+        service_day: Option<ServiceDay>,
+    ) -> Result<Vec<Route>, UseCaseError> {
+        let key = cache_key("get_routes", (from_station_id, to_station_id, service_day));
+        self.cache
+            .get_or_try_insert_with(
+                key,
+                self.inner
+                    .get_routes(from_station_id, to_station_id, service_day),
+            )
+            .await
+    }
+
+    async fn get_journeys(
+        &self,
+        from_station_group_id: u32,
+        to_station_group_id: u32,
+        criterion: JourneySearchCriterion,
+        limit: Option<u32>,
+    ) -> Result<Vec<Journey>, UseCaseError> {
+        let key = cache_key(
+            "get_journeys",
+            (from_station_group_id, to_station_group_id, criterion, limit),
+        );
+        self.cache
+            .get_or_try_insert_with(
+                key,
+                self.inner.get_journeys(
+                    from_station_group_id,
+                    to_station_group_id,
+                    criterion,
+                    limit,
+                ),
+            )
+            .await
+    }
+
+    async fn find_line_by_id(
+        &self,
+        line_id: u32,
+        as_of: Option<&AsOf>,
+    ) -> Result<Option<Line>, UseCaseError> {
+        let key = cache_key("find_line_by_id", (line_id, as_of));
+        self.cache
+            .get_or_try_insert_with(key, self.inner.find_line_by_id(line_id, as_of))
+            .await
+    }
+
+    async fn get_lines_by_name(
+        &self,
+        line_name: String,
+        limit: Option<u32>,
+    ) -> Result<Vec<Line>, UseCaseError> {
+        let key = cache_key("get_lines_by_name", (&line_name, limit));
+        self.cache
+            .get_or_try_insert_with(key, self.inner.get_lines_by_name(line_name, limit))
+            .await
+    }
+
+    async fn find_prefecture_by_id(
+        &self,
+        prefecture_id: u32,
+    ) -> Result<Option<Prefecture>, UseCaseError> {
+        let key = cache_key("find_prefecture_by_id", prefecture_id);
+        self.cache
+            .get_or_try_insert_with(key, self.inner.find_prefecture_by_id(prefecture_id))
+            .await
+    }
+
+    async fn get_prefectures(&self) -> Result<Vec<Prefecture>, UseCaseError> {
+        let key = cache_key("get_prefectures", ());
+        self.cache
+            .get_or_try_insert_with(key, self.inner.get_prefectures())
+            .await
+    }
+
+    async fn get_stations_by_prefecture_id(
+        &self,
+        prefecture_id: u32,
+        as_of: Option<&AsOf>,
+        field_mask: &StationFieldMask,
+    ) -> Result<Vec<Station>, UseCaseError> {
+        let key = cache_key(
+            "get_stations_by_prefecture_id",
+            (prefecture_id, as_of, field_mask),
+        );
+        self.cache
+            .get_or_try_insert_with(
+                key,
+                self.inner
+                    .get_stations_by_prefecture_id(prefecture_id, as_of, field_mask),
+            )
+            .await
+    }
+
+    async fn get_lines_by_prefecture_id(
+        &self,
+        prefecture_id: u32,
+        as_of: Option<&AsOf>,
+    ) -> Result<Vec<Line>, UseCaseError> {
+        let key = cache_key("get_lines_by_prefecture_id", (prefecture_id, as_of));
+        self.cache
+            .get_or_try_insert_with(
+                key,
+                self.inner.get_lines_by_prefecture_id(prefecture_id, as_of),
+            )
+            .await
+    }
+
+    async fn find_company_by_id(&self, company_id: u32) -> Result<Option<Company>, UseCaseError> {
+        let key = cache_key("find_company_by_id", company_id);
+        self.cache
+            .get_or_try_insert_with(key, self.inner.find_company_by_id(company_id))
+            .await
+    }
+
+    async fn get_companies(&self, filter: &CompanyFilter) -> Result<Vec<Company>, UseCaseError> {
+        let key = cache_key("get_companies", filter);
+        self.cache
+            .get_or_try_insert_with(key, self.inner.get_companies(filter))
+            .await
+    }
+
+    async fn get_lines_by_company_id(
+        &self,
+        company_id: u32,
+        as_of: Option<&AsOf>,
+    ) -> Result<Vec<Line>, UseCaseError> {
+        let key = cache_key("get_lines_by_company_id", (company_id, as_of));
+        self.cache
+            .get_or_try_insert_with(key, self.inner.get_lines_by_company_id(company_id, as_of))
+            .await
+    }
+
+    async fn get_stations_by_company_id(
+        &self,
+        company_id: u32,
+        as_of: Option<&AsOf>,
+        field_mask: &StationFieldMask,
+    ) -> Result<Vec<Station>, UseCaseError> {
+        let key = cache_key(
+            "get_stations_by_company_id",
+            (company_id, as_of, field_mask),
+        );
+        self.cache
+            .get_or_try_insert_with(
+                key,
+                self.inner
+                    .get_stations_by_company_id(company_id, as_of, field_mask),
+            )
+            .await
+    }
+
+    async fn find_train_type_by_id(&self, type_id: u32) -> Result<Option<TrainType>, UseCaseError> {
+        let key = cache_key("find_train_type_by_id", type_id);
+        self.cache
+            .get_or_try_insert_with(key, self.inner.find_train_type_by_id(type_id))
+            .await
+    }
+
+    async fn get_train_types(
+        &self,
+        filter: &TrainTypeFilter,
+    ) -> Result<Vec<TrainType>, UseCaseError> {
+        let key = cache_key("get_train_types", filter);
+        self.cache
+            .get_or_try_insert_with(key, self.inner.get_train_types(filter))
+            .await
+    }
+
+    async fn get_stations_by_train_type(
+        &self,
+        type_id: u32,
+        line_group_id: Option<u32>,
+        field_mask: &StationFieldMask,
+    ) -> Result<Vec<Station>, UseCaseError> {
+        let key = cache_key(
+            "get_stations_by_train_type",
+            (type_id, line_group_id, field_mask),
+        );
+        self.cache
+            .get_or_try_insert_with(
+                key,
+                self.inner
+                    .get_stations_by_train_type(type_id, line_group_id, field_mask),
+            )
+            .await
+    }
+
+    async fn get_stopping_pattern_diff(
+        &self,
+        first_line_group_id: u32,
+        second_line_group_id: u32,
+    ) -> Result<StoppingPattern, UseCaseError> {
+        let key = cache_key(
+            "get_stopping_pattern_diff",
+            (first_line_group_id, second_line_group_id),
+        );
+        self.cache
+            .get_or_try_insert_with(
+                key,
+                self.inner
+                    .get_stopping_pattern_diff(first_line_group_id, second_line_group_id),
+            )
+            .await
+    }
+
+    async fn get_line_geojson(&self, line_id: u32) -> Result<FeatureCollection, UseCaseError> {
+        let key = cache_key("get_line_geojson", line_id);
+        self.cache
+            .get_or_try_insert_with(key, self.inner.get_line_geojson(line_id))
+            .await
+    }
+
+    async fn get_dataset_info(&self) -> Result<Option<DatasetInfo>, UseCaseError> {
+        let key = cache_key("get_dataset_info", ());
+        self.cache
+            .get_or_try_insert_with(key, self.inner.get_dataset_info())
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashSet, path::Path, sync::Arc};
+
+    use super::{cache_key, CachedQueryInteractor};
+    use crate::{
+        domain::entity::misc::{AsOf, BoundingBox, StationFieldMask, StationFilter},
+        infrastructure::memory::{dataset::Dataset, dataset_loader::InMemoryDatasetLoader},
+        use_case::{
+            cache::{CacheConfig, QueryCache},
+            traits::query::QueryUseCase,
+        },
+    };
+
+    #[test]
+    fn cache_keys_do_not_collide() {
+        let field_mask = StationFieldMask::ALL;
+        let keys = [
+            // 文字列はDebugで引用符ごとエスケープされるので、区切りを含んでも混ざらない
+            cache_key(
+                "get_stations_by_name",
+                (&"a".to_string(), None::<u32>, Some(1u32), &field_mask),
+            ),
+            cache_key(
+                "get_stations_by_name",
+                (&"a\", None".to_string(), Some(1u32), &field_mask),
+            ),
+            cache_key(
+                "get_stations_by_name",
+                (&"a".to_string(), Some(1u32), None::<u32>, &field_mask),
+            ),
+            cache_key(
+                "get_stations_by_name",
+                (
+                    &"a".to_string(),
+                    None::<u32>,
+                    Some(1u32),
+                    &StationFieldMask::NONE,
+                ),
+            ),
+            cache_key("get_stations_by_id_vec", (&[12u32][..], &field_mask)),
+            cache_key("get_stations_by_id_vec", (&[1u32, 2][..], &field_mask)),
+            cache_key("get_line_by_id", (1u32, None::<AsOf>)),
+            cache_key("get_line_by_id", (1u32, AsOf::parse("2025-01-01"))),
+            cache_key("get_lines_by_id_vec", (&[1u32][..], None::<AsOf>)),
+        ];
+
+        let distinct: HashSet<&String> = keys.iter().collect();
+        assert_eq!(distinct.len(), keys.len(), "{:#?}", keys);
+    }
+
+    #[tokio::test]
+    async fn coordinate_lookups_bypass_the_cache() {
+        let dataset = Dataset::load(&Path::new(env!("CARGO_MANIFEST_DIR")).join("../data"))
+            .expect("Failed to load the dataset");
+        let query_use_case = CachedQueryInteractor::new(
+            InMemoryDatasetLoader::build_query_interactor(Arc::new(dataset)),
+            Arc::new(QueryCache::new(CacheConfig::default())),
+        );
+        let (latitude, longitude) = (35.681236, 139.767125);
+        let field_mask = StationFieldMask::NONE;
+        let filter = StationFilter::default();
+
+        query_use_case
+            .get_stations_by_coordinates(latitude, longitude, Some(1), &field_mask)
+            .await
+            .unwrap();
+        query_use_case
+            .get_stations_by_coordinates_without_attributes(latitude, longitude, Some(1))
+            .await
+            .unwrap();
+        query_use_case
+            .get_stations_by_radius(latitude, longitude, 1.0, &filter, None, &field_mask)
+            .await
+            .unwrap();
+        query_use_case
+            .get_stations_by_bounding_box(
+                &BoundingBox::new(35.6, 139.7, 35.7, 139.8),
+                &filter,
+                None,
+                &field_mask,
+            )
+            .await
+            .unwrap();
+        query_use_case
+            .get_station_id_and_distance_by_coordinates(latitude, longitude, None)
+            .await
+            .unwrap();
+        let stats = query_use_case.cache().stats();
+        assert_eq!((stats.hits, stats.misses, stats.entries), (0, 0, 0));
+
+        // IDで引くものはキャッシュする
+        query_use_case
+            .find_station_by_id(1130208, &field_mask)
+            .await
+            .unwrap();
+        assert_eq!(query_use_case.cache().stats().entries, 1);
+    }
+}
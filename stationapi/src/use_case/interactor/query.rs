@@ -1,47 +1,86 @@
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, BTreeSet, HashMap},
     sync::{Arc, Mutex},
 };
 
 use async_trait::async_trait;
+use tokio::sync::OnceCell;
 
 use crate::{
     domain::{
         entity::{
-            company::Company, line::Line, line_symbol::LineSymbol, misc::StationIdWithDistance,
-            station::Station, station_number::StationNumber, train_type::TrainType,
+            company::Company,
+            dataset_info::DatasetInfo,
+            geojson::FeatureCollection,
+            journey::Journey,
+            line::Line,
+            line_symbol::LineSymbol,
+            location_tracker::LocationTracker,
+            misc::{
+                is_valid_coordinates, AsOf, BoundingBox, CompanyFilter, StationFieldMask,
+                StationFilter, StationIdWithDistance, TrainTypeFilter,
+            },
+            prefecture::Prefecture,
+            service_day::ServiceDay,
+            station::Station,
+            station_graph::StationGraph,
+            station_number::StationNumber,
+            stopping_pattern::StoppingPattern,
+            suggestion::Suggestion,
+            train_type::TrainType,
+            travel_direction::{LinePath, TravelDirectionEstimate},
         },
         repository::{
-            company_repository::CompanyRepository, line_repository::LineRepository,
-            station_repository::StationRepository, train_type_repository::TrainTypeRepository,
+            autocomplete_repository::AutocompleteRepository, company_repository::CompanyRepository,
+            dataset_info_repository::DatasetInfoRepository, line_repository::LineRepository,
+            prefecture_repository::PrefectureRepository, station_repository::StationRepository,
+            train_type_repository::TrainTypeRepository,
         },
     },
-    station_api::{self, Route},
+    station_api::{self, JourneySearchCriterion, Route, StopCondition},
     use_case::{error::UseCaseError, traits::query::QueryUseCase},
 };
 
+const DEFAULT_JOURNEYS_LIMIT: u32 = 3;
+// NOTE: 1経路ごとにグラフ全体を何度も探索するので、クライアントの指定が大きくてもここで打ち切る
+const MAX_JOURNEYS_LIMIT: u32 = 10;
+const DEFAULT_SUGGESTIONS_LIMIT: u32 = 10;
+// NOTE: 範囲を広く取ると全国の駅が返るので、これより多く該当するときは黙って切り詰めずにエラーにする
+const MAX_SPATIAL_SEARCH_LIMIT: u32 = 1000;
+
 #[derive(Clone)]
-pub struct QueryInteractor<SR, LR, TR, CR> {
+pub struct QueryInteractor<SR, LR, TR, CR, AR, PR, DR> {
     pub station_repository: SR,
     pub line_repository: LR,
     pub train_type_repository: TR,
     pub company_repository: CR,
+    pub autocomplete_repository: AR,
+    pub prefecture_repository: PR,
+    pub dataset_info_repository: DR,
+    pub station_graph: Arc<OnceCell<Arc<StationGraph>>>,
 }
 
 #[async_trait]
-impl<SR, LR, TR, CR> QueryUseCase for QueryInteractor<SR, LR, TR, CR>
+impl<SR, LR, TR, CR, AR, PR, DR> QueryUseCase for QueryInteractor<SR, LR, TR, CR, AR, PR, DR>
 where
     SR: StationRepository,
     LR: LineRepository,
     TR: TrainTypeRepository,
     CR: CompanyRepository,
+    AR: AutocompleteRepository,
+    PR: PrefectureRepository,
+    DR: DatasetInfoRepository,
 {
-    async fn find_station_by_id(&self, station_id: u32) -> Result<Option<Station>, UseCaseError> {
+    async fn find_station_by_id(
+        &self,
+        station_id: u32,
+        field_mask: &StationFieldMask,
+    ) -> Result<Option<Station>, UseCaseError> {
         let Some(station) = self.station_repository.find_by_id(station_id).await? else {
             return Ok(None);
         };
         let stations = self
-            .update_station_vec_with_attributes(vec![station], None)
+            .update_station_vec_with_attributes(vec![station], None, field_mask)
             .await?;
         let station = stations.first().cloned();
 
@@ -50,17 +89,29 @@ where
     async fn get_stations_by_id_vec(
         &self,
         station_ids: &[u32],
+        field_mask: &StationFieldMask,
     ) -> Result<Vec<Station>, UseCaseError> {
-        let stations = self.station_repository.get_by_id_vec(station_ids).await?;
         let stations = self
-            .update_station_vec_with_attributes(stations, None)
+            .get_stations_by_id_vec_without_attributes(station_ids)
+            .await?;
+        let stations = self
+            .update_station_vec_with_attributes(stations, None, field_mask)
             .await?;
 
         Ok(stations)
     }
+    async fn get_stations_by_id_vec_without_attributes(
+        &self,
+        station_ids: &[u32],
+    ) -> Result<Vec<Station>, UseCaseError> {
+        let stations = self.station_repository.get_by_id_vec(station_ids).await?;
+
+        Ok(stations)
+    }
     async fn get_stations_by_group_id(
         &self,
         station_group_id: u32,
+        field_mask: &StationFieldMask,
     ) -> Result<Vec<Station>, UseCaseError> {
         let stations = self
             .station_repository
@@ -68,7 +119,7 @@ where
             .await?;
 
         let stations = self
-            .update_station_vec_with_attributes(stations, Some(station_group_id))
+            .update_station_vec_with_attributes(stations, Some(station_group_id), field_mask)
             .await?;
 
         Ok(stations)
@@ -100,14 +151,95 @@ where
         latitude: f64,
         longitude: f64,
         limit: Option<u32>,
+        field_mask: &StationFieldMask,
+    ) -> Result<Vec<Station>, UseCaseError> {
+        let stations = self
+            .get_stations_by_coordinates_without_attributes(latitude, longitude, limit)
+            .await?;
+
+        let stations = self
+            .update_station_vec_with_attributes(stations, None, field_mask)
+            .await?;
+
+        Ok(stations)
+    }
+    async fn get_stations_by_coordinates_without_attributes(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        limit: Option<u32>,
     ) -> Result<Vec<Station>, UseCaseError> {
         let stations = self
             .station_repository
             .get_by_coordinates(latitude, longitude, limit)
             .await?;
 
+        Ok(stations)
+    }
+    async fn get_stations_by_radius(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        radius_in_km: f64,
+        filter: &StationFilter,
+        limit: Option<u32>,
+        field_mask: &StationFieldMask,
+    ) -> Result<Vec<Station>, UseCaseError> {
+        if !is_valid_coordinates(latitude, longitude) {
+            return Err(UseCaseError::InvalidArgument(format!(
+                "Invalid coordinates ({}, {})",
+                latitude, longitude
+            )));
+        }
+        if !(radius_in_km.is_finite() && radius_in_km >= 0.0) {
+            return Err(UseCaseError::InvalidArgument(format!(
+                "Invalid radius {}",
+                radius_in_km
+            )));
+        }
+        let stations = self
+            .station_repository
+            .get_by_radius(
+                latitude,
+                longitude,
+                radius_in_km,
+                filter,
+                Some(spatial_search_fetch_limit(limit)),
+            )
+            .await?;
+        check_spatial_search_result(&stations, limit)?;
+
+        let stations = self
+            .update_station_vec_with_attributes(stations, None, field_mask)
+            .await?;
+
+        Ok(stations)
+    }
+    async fn get_stations_by_bounding_box(
+        &self,
+        bounding_box: &BoundingBox,
+        filter: &StationFilter,
+        limit: Option<u32>,
+        field_mask: &StationFieldMask,
+    ) -> Result<Vec<Station>, UseCaseError> {
+        if !bounding_box.is_valid() {
+            return Err(UseCaseError::InvalidArgument(format!(
+                "Invalid bounding box {:?}",
+                bounding_box
+            )));
+        }
+        let stations = self
+            .station_repository
+            .get_by_bounding_box(
+                bounding_box,
+                filter,
+                Some(spatial_search_fetch_limit(limit)),
+            )
+            .await?;
+        check_spatial_search_result(&stations, limit)?;
+
         let stations = self
-            .update_station_vec_with_attributes(stations, None)
+            .update_station_vec_with_attributes(stations, None, field_mask)
             .await?;
 
         Ok(stations)
@@ -129,6 +261,7 @@ where
         &self,
         line_id: u32,
         station_id: Option<u32>,
+        field_mask: &StationFieldMask,
     ) -> Result<Vec<Station>, UseCaseError> {
         let stations = self
             .station_repository
@@ -145,7 +278,45 @@ where
         };
 
         let stations = self
-            .update_station_vec_with_attributes(stations, line_group_id)
+            .update_station_vec_with_attributes(stations, line_group_id, field_mask)
+            .await?;
+
+        Ok(stations)
+    }
+    async fn get_stations_by_line_id_without_attributes(
+        &self,
+        line_id: u32,
+    ) -> Result<Vec<Station>, UseCaseError> {
+        let stations = self
+            .station_repository
+            .get_by_line_id(line_id, None)
+            .await?;
+
+        Ok(stations)
+    }
+    async fn get_stations_by_line_id_vec_without_attributes(
+        &self,
+        line_id_vec: &[u32],
+    ) -> Result<Vec<Station>, UseCaseError> {
+        let stations = self
+            .station_repository
+            .get_by_line_id_vec(line_id_vec)
+            .await?;
+
+        Ok(stations)
+    }
+    async fn get_stations_by_line_id_as_of(
+        &self,
+        line_id: u32,
+        as_of: &AsOf,
+        field_mask: &StationFieldMask,
+    ) -> Result<Vec<Station>, UseCaseError> {
+        let stations = self
+            .station_repository
+            .get_by_line_id_as_of(line_id, as_of)
+            .await?;
+        let stations = self
+            .update_station_vec_with_attributes(stations, None, field_mask)
             .await?;
 
         Ok(stations)
@@ -155,6 +326,7 @@ where
         station_name: String,
         limit: Option<u32>,
         from_station_group_id: Option<u32>,
+        field_mask: &StationFieldMask,
     ) -> Result<Vec<Station>, UseCaseError> {
         let stations = self
             .station_repository
@@ -162,7 +334,7 @@ where
             .await?;
 
         let stations = self
-            .update_station_vec_with_attributes(stations, None)
+            .update_station_vec_with_attributes(stations, None, field_mask)
             .await?;
 
         Ok(stations)
@@ -182,6 +354,7 @@ where
         &self,
         stations: Vec<Station>,
         line_group_id: Option<u32>,
+        field_mask: &StationFieldMask,
     ) -> Result<Vec<Station>, UseCaseError> {
         let stations = Arc::new(Mutex::new(stations));
 
@@ -192,28 +365,69 @@ where
             .map(|station| station.station_g_cd)
             .collect::<Vec<u32>>();
 
-        let stations_by_group_ids = self
-            .get_stations_by_group_id_vec(&station_group_ids)
-            .await?;
+        // NOTE: マスクで外された属性の問い合わせは丸ごと省く
+        let stations_by_group_ids = if field_mask.lines {
+            self.get_stations_by_group_id_vec(&station_group_ids)
+                .await?
+        } else {
+            vec![]
+        };
 
-        let station_ids = stations_by_group_ids
-            .iter()
-            .map(|station| station.station_cd)
-            .collect::<Vec<u32>>();
+        let station_ids = if field_mask.lines {
+            stations_by_group_ids
+                .iter()
+                .map(|station| station.station_cd)
+                .collect::<Vec<u32>>()
+        } else {
+            Arc::clone(&stations)
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|station| station.station_cd)
+                .collect::<Vec<u32>>()
+        };
 
-        let lines = &self
-            .get_lines_by_station_group_id_vec(&station_group_ids)
-            .await?;
+        let lines = &if field_mask.lines {
+            self.get_lines_by_station_group_id_vec(&station_group_ids)
+                .await?
+        } else {
+            vec![]
+        };
 
-        let company_ids = &lines
-            .iter()
-            .map(|station| station.company_cd)
-            .collect::<Vec<u32>>();
-        let companies = self.find_company_by_id_vec(company_ids).await?;
+        let companies = if field_mask.companies {
+            // NOTE: 乗り換え路線を引かないときは駅自身の路線の事業者だけでいい
+            let company_ids = if field_mask.lines {
+                lines
+                    .iter()
+                    .map(|station| station.company_cd)
+                    .collect::<Vec<u32>>()
+            } else {
+                Arc::clone(&stations)
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .filter_map(|station| station.company_cd)
+                    .collect::<Vec<u32>>()
+            };
+            self.find_company_by_id_vec(&company_ids).await?
+        } else {
+            vec![]
+        };
 
-        let train_types = self
-            .get_train_types_by_station_id_vec(&station_ids, line_group_id)
-            .await?;
+        let train_types = if field_mask.train_types {
+            self.get_train_types_by_station_id_vec(&station_ids, line_group_id)
+                .await?
+        } else {
+            vec![]
+        };
+
+        let station_numbers_of = |station: &Station| -> Vec<StationNumber> {
+            if field_mask.station_numbers {
+                self.get_station_numbers(station)
+            } else {
+                vec![]
+            }
+        };
 
         let stations = Arc::clone(&stations)
             .lock()
@@ -228,8 +442,7 @@ where
                     .cloned();
                 line.station = Some(station.clone());
 
-                let station_numbers: Vec<StationNumber> = self.get_station_numbers(station);
-                station.station_numbers = station_numbers;
+                station.station_numbers = station_numbers_of(station);
                 station.line = Some(Box::new(line.clone()));
                 if let Some(tt) = train_types
                     .iter()
@@ -257,8 +470,7 @@ where
                         .filter(|s| s.line_cd == line.line_cd)
                         .find(|s| s.station_g_cd == station.station_g_cd)
                     {
-                        let station_numbers: Vec<StationNumber> = self.get_station_numbers(station);
-                        station.station_numbers = station_numbers;
+                        station.station_numbers = station_numbers_of(station);
                         if let Some(tt) = train_types
                             .iter()
                             .find(|tt| tt.station_cd == station.station_cd)
@@ -270,8 +482,7 @@ where
                         line.station = Some(station.clone());
                     }
                 }
-                let station_numbers: Vec<StationNumber> = self.get_station_numbers(station);
-                station.station_numbers = station_numbers;
+                station.station_numbers = station_numbers_of(station);
                 station.lines = lines;
 
                 station.clone()
@@ -294,18 +505,56 @@ where
     async fn get_stations_by_line_group_id(
         &self,
         line_group_id: u32,
+        service_day: Option<ServiceDay>,
+        field_mask: &StationFieldMask,
     ) -> Result<Vec<Station>, UseCaseError> {
         let stations = self
+            .get_stations_by_line_group_id_without_attributes(line_group_id, service_day)
+            .await?;
+
+        let stations = self
+            .update_station_vec_with_attributes(stations, Some(line_group_id), field_mask)
+            .await?;
+
+        Ok(stations)
+    }
+    async fn get_stations_by_line_group_id_without_attributes(
+        &self,
+        line_group_id: u32,
+        service_day: Option<ServiceDay>,
+    ) -> Result<Vec<Station>, UseCaseError> {
+        let mut stations = self
             .station_repository
             .get_by_line_group_id(line_group_id)
             .await?;
+        if let Some(service_day) = service_day {
+            stations.retain(|s| service_day.serves(s.stop_condition));
+        }
 
+        Ok(stations)
+    }
+    async fn get_stations_by_line_group_id_vec_without_attributes(
+        &self,
+        line_group_id_vec: &[u32],
+    ) -> Result<Vec<Station>, UseCaseError> {
         let stations = self
-            .update_station_vec_with_attributes(stations, Some(line_group_id))
+            .station_repository
+            .get_by_line_group_id_vec(line_group_id_vec)
             .await?;
 
         Ok(stations)
     }
+    async fn get_lines_by_line_group_id_vec(
+        &self,
+        line_group_id_vec: &[u32],
+    ) -> Result<Vec<Line>, UseCaseError> {
+        let lines = self
+            .line_repository
+            .get_by_line_group_id_vec(line_group_id_vec)
+            .await?;
+
+        Ok(lines)
+    }
     fn get_station_numbers(&self, station: &Station) -> Vec<StationNumber> {
         let station = station.clone();
 
@@ -474,60 +723,103 @@ where
         &self,
         station_id: u32,
     ) -> Result<Vec<TrainType>, UseCaseError> {
-        let mut train_types = self
+        self.get_all_train_types_by_station_id_vec(&[station_id])
+            .await
+    }
+    async fn get_all_train_types_by_station_id_vec(
+        &self,
+        station_id_vec: &[u32],
+    ) -> Result<Vec<TrainType>, UseCaseError> {
+        let train_types = self
             .train_type_repository
-            .get_by_station_id(station_id)
+            .get_all_by_station_id_vec(station_id_vec)
             .await?;
 
         let train_type_ids = train_types
             .iter()
             .map(|tt| tt.line_group_cd)
+            .collect::<BTreeSet<u32>>()
+            .into_iter()
             .collect::<Vec<u32>>();
 
-        let mut lines = self
+        let lines = self
             .line_repository
             .get_by_line_group_id_vec(&train_type_ids)
             .await?;
 
-        let company_ids = lines.iter().map(|l| l.company_cd).collect::<Vec<u32>>();
+        // NOTE: 駅自身の路線もまとめて引き、会社は1回の問い合わせで済ませる
+        let lines_by_station_id: HashMap<u32, Line> = self
+            .line_repository
+            .get_by_station_id_vec(station_id_vec)
+            .await?
+            .into_iter()
+            .filter_map(|line| Some((line.station_cd?, line)))
+            .collect();
 
-        let companies = self.company_repository.find_by_id_vec(&company_ids).await?;
+        let company_ids = lines
+            .iter()
+            .chain(lines_by_station_id.values())
+            .map(|l| l.company_cd)
+            .collect::<BTreeSet<u32>>()
+            .into_iter()
+            .collect::<Vec<u32>>();
 
-        let line = self.line_repository.find_by_station_id(station_id).await?;
-        let Some(mut line) = line else {
-            return Ok(vec![]);
-        };
+        let companies = self.company_repository.find_by_id_vec(&company_ids).await?;
 
-        for tt in train_types.iter_mut() {
-            let mut lines: Vec<Line> = lines
-                .iter_mut()
-                .map(|l| l.clone())
-                .filter(|l| l.line_group_cd.is_some())
-                .filter(|l| l.line_group_cd.unwrap() == tt.line_group_cd)
-                .collect::<Vec<Line>>();
-
-            for line in lines.iter_mut() {
+        let lines_by_station_id: HashMap<u32, Line> = lines_by_station_id
+            .into_iter()
+            .map(|(station_id, mut line)| {
                 line.company = companies
                     .iter()
                     .find(|c| c.company_cd == line.company_cd)
                     .cloned();
-                line.line_symbols = self.get_line_symbols(line);
-                let train_type: Option<TrainType> = self
-                    .train_type_repository
-                    .find_by_line_group_id_and_line_id(tt.line_group_cd, line.line_cd)
-                    .await?;
-                line.train_type = train_type;
-            }
+                line.line_symbols = self.get_line_symbols(&line);
+                (station_id, line)
+            })
+            .collect();
 
-            line.company = companies
-                .iter()
-                .find(|c| c.company_cd == line.company_cd)
-                .cloned();
-            line.line_symbols = self.get_line_symbols(&line);
+        // NOTE: 系統と路線の組ごとの種別は1回の問い合わせでまとめて引き、木はメモリ上で組み立てる
+        let line_group_id_and_line_id_vec = train_types
+            .iter()
+            .flat_map(|tt| {
+                lines
+                    .iter()
+                    .filter(|l| l.line_group_cd == Some(tt.line_group_cd))
+                    .map(|l| (tt.line_group_cd, l.line_cd))
+            })
+            .collect::<BTreeSet<(u32, u32)>>()
+            .into_iter()
+            .collect::<Vec<(u32, u32)>>();
+        let train_types_by_line = self
+            .train_type_repository
+            .get_by_line_group_id_and_line_id_vec(&line_group_id_and_line_id_vec)
+            .await?;
 
-            tt.lines = lines;
-            tt.line = Some(Box::new(line.clone()));
-        }
+        // NOTE: 路線が見つからない駅の種別は返さない
+        let train_types = train_types
+            .into_iter()
+            .filter_map(|mut tt| {
+                let line = lines_by_station_id.get(&tt.station_cd)?;
+                tt.lines = lines
+                    .iter()
+                    .filter(|l| l.line_group_cd == Some(tt.line_group_cd))
+                    .cloned()
+                    .map(|mut l| {
+                        l.company = companies
+                            .iter()
+                            .find(|c| c.company_cd == l.company_cd)
+                            .cloned();
+                        l.line_symbols = self.get_line_symbols(&l);
+                        l.train_type = train_types_by_line
+                            .get(&(tt.line_group_cd, l.line_cd))
+                            .cloned();
+                        l
+                    })
+                    .collect();
+                tt.line = Some(Box::new(line.clone()));
+                Some(tt)
+            })
+            .collect();
 
         Ok(train_types)
     }
@@ -545,15 +837,85 @@ where
         Ok(train_types)
     }
 
+    async fn create_location_tracker(
+        &self,
+        line_id: u32,
+        line_group_id: Option<u32>,
+    ) -> Result<LocationTracker, UseCaseError> {
+        let stations = match line_group_id {
+            Some(line_group_id) => {
+                self.get_stations_by_line_group_id(line_group_id, None, &StationFieldMask::ALL)
+                    .await?
+            }
+            None => {
+                self.get_stations_by_line_id(line_id, None, &StationFieldMask::ALL)
+                    .await?
+            }
+        };
+
+        let tracker = LocationTracker::new(stations);
+        if tracker.is_empty() {
+            return Err(UseCaseError::NotFound {
+                entity_type: "Station",
+                entity_id: format!("line_id: {}, line_group_id: {:?}", line_id, line_group_id),
+            });
+        }
+
+        Ok(tracker)
+    }
+
+    async fn get_travel_direction(
+        &self,
+        coordinates: &[(f64, f64)],
+        line_id: u32,
+        line_group_id: Option<u32>,
+    ) -> Result<Option<TravelDirectionEstimate>, UseCaseError> {
+        let stations = match line_group_id {
+            Some(line_group_id) => {
+                self.get_stations_by_line_group_id(line_group_id, None, &StationFieldMask::ALL)
+                    .await?
+            }
+            None => {
+                self.get_stations_by_line_id(line_id, None, &StationFieldMask::ALL)
+                    .await?
+            }
+        };
+        if stations.is_empty() {
+            return Err(UseCaseError::NotFound {
+                entity_type: "Station",
+                entity_id: format!("line_id: {}, line_group_id: {:?}", line_id, line_group_id),
+            });
+        }
+
+        Ok(LinePath::new(stations).estimate(coordinates))
+    }
+
+    async fn get_autocomplete_suggestions(
+        &self,
+        query: &str,
+        limit: Option<u32>,
+    ) -> Result<Vec<Suggestion>, UseCaseError> {
+        let suggestions = self
+            .autocomplete_repository
+            .find_by_prefix(query, limit.unwrap_or(DEFAULT_SUGGESTIONS_LIMIT))
+            .await?;
+
+        Ok(suggestions)
+    }
+
     async fn get_routes(
         &self,
         from_station_id: u32,
         to_station_id: u32,
+        service_day: Option<ServiceDay>,
     ) -> Result<Vec<Route>, UseCaseError> {
-        let stops = self
+        let mut stops = self
             .station_repository
             .get_route_stops(from_station_id, to_station_id)
             .await?;
+        if let Some(service_day) = service_day {
+            stops.retain(|stop| service_day.serves(stop.stop_condition));
+        }
         let stops = Arc::new(stops);
 
         let line_group_id_vec = Arc::clone(&stops)
@@ -800,9 +1162,13 @@ where
                     .collect::<Vec<station_api::Station>>();
 
                 // TODO: SQLで同等の処理を行う
-                let includes_requested_station = stops
-                    .iter()
-                    .any(|stop| stop.group_id == from_station_id || stop.group_id == to_station_id);
+                let includes_station =
+                    |station_id: u32| stops.iter().any(|stop| stop.group_id == station_id);
+                // NOTE: 運行日で絞り込んだときは、出発駅と到着駅の両方に停車する系統だけを残す
+                let includes_requested_station = match service_day {
+                    Some(_) => includes_station(from_station_id) && includes_station(to_station_id),
+                    None => includes_station(from_station_id) || includes_station(to_station_id),
+                };
                 if !includes_requested_station {
                     return None;
                 }
@@ -813,8 +1179,112 @@ where
         Ok(routes)
     }
 
-    async fn find_line_by_id(&self, line_id: u32) -> Result<Option<Line>, UseCaseError> {
-        let line = self.line_repository.find_by_id(line_id).await?;
+    async fn get_journeys(
+        &self,
+        from_station_group_id: u32,
+        to_station_group_id: u32,
+        criterion: JourneySearchCriterion,
+        limit: Option<u32>,
+    ) -> Result<Vec<Journey>, UseCaseError> {
+        // NOTE: グラフは初回に作ったら使い回すので、データが変わったらリロードで作り直す
+        let station_graph = self
+            .station_graph
+            .get_or_try_init(|| async {
+                let stops = self.station_repository.get_all_stops().await?;
+                let station_graph =
+                    tokio::task::spawn_blocking(move || Arc::new(StationGraph::new(&stops)))
+                        .await
+                        .map_err(|err| UseCaseError::Other(err.into()))?;
+                Ok::<Arc<StationGraph>, UseCaseError>(station_graph)
+            })
+            .await?;
+
+        // NOTE: 経路ごとにグラフ全体を何度も探索するので、ランタイムのスレッドを塞がないようにする
+        let station_graph = Arc::clone(station_graph);
+        let limit = limit
+            .unwrap_or(DEFAULT_JOURNEYS_LIMIT)
+            .min(MAX_JOURNEYS_LIMIT) as usize;
+        let mut journeys = tokio::task::spawn_blocking(move || {
+            station_graph.find_journeys(
+                from_station_group_id,
+                to_station_group_id,
+                criterion,
+                limit,
+            )
+        })
+        .await
+        .map_err(|err| UseCaseError::Other(err.into()))?;
+
+        let station_ids = journeys
+            .iter()
+            .flat_map(|journey| journey.legs.iter())
+            .flat_map(|leg| leg.station_cds.iter().copied())
+            .collect::<BTreeSet<u32>>()
+            .into_iter()
+            .collect::<Vec<u32>>();
+        let stations = self
+            .get_stations_by_id_vec(&station_ids, &StationFieldMask::ALL)
+            .await?;
+
+        let line_group_ids = journeys
+            .iter()
+            .flat_map(|journey| journey.legs.iter())
+            .filter_map(|leg| leg.line_group_cd)
+            .collect::<BTreeSet<u32>>()
+            .into_iter()
+            .collect::<Vec<u32>>();
+        let train_types = self
+            .train_type_repository
+            .get_by_line_group_id_vec(&line_group_ids)
+            .await?;
+
+        for leg in journeys
+            .iter_mut()
+            .flat_map(|journey| journey.legs.iter_mut())
+        {
+            let line_group_cd = leg.line_group_cd;
+            leg.stops = leg
+                .station_cds
+                .iter()
+                .filter_map(|station_cd| {
+                    stations
+                        .iter()
+                        .find(|station| station.station_cd == *station_cd)
+                        .cloned()
+                })
+                .map(|mut stop| {
+                    if let Some(line_group_cd) = line_group_cd {
+                        stop.train_type = train_types
+                            .iter()
+                            .find(|tt| {
+                                tt.line_group_cd == line_group_cd
+                                    && tt.station_cd == stop.station_cd
+                            })
+                            .cloned()
+                            .map(Box::new);
+                    }
+                    stop
+                })
+                .collect();
+            leg.train_type = leg.stops.first().and_then(|stop| stop.train_type.clone());
+        }
+
+        Ok(journeys)
+    }
+
+    async fn find_line_by_id(
+        &self,
+        line_id: u32,
+        as_of: Option<&AsOf>,
+    ) -> Result<Option<Line>, UseCaseError> {
+        let line = match as_of {
+            Some(as_of) => {
+                self.line_repository
+                    .find_by_id_as_of(line_id, as_of)
+                    .await?
+            }
+            None => self.line_repository.find_by_id(line_id).await?,
+        };
         Ok(line)
     }
 
@@ -826,4 +1296,286 @@ where
         let lines = self.line_repository.get_by_name(line_name, limit).await?;
         Ok(lines)
     }
+    async fn find_prefecture_by_id(
+        &self,
+        prefecture_id: u32,
+    ) -> Result<Option<Prefecture>, UseCaseError> {
+        let prefecture = self.prefecture_repository.find_by_id(prefecture_id).await?;
+        Ok(prefecture)
+    }
+    async fn get_prefectures(&self) -> Result<Vec<Prefecture>, UseCaseError> {
+        let prefectures = self.prefecture_repository.get_all().await?;
+        Ok(prefectures)
+    }
+    async fn get_stations_by_prefecture_id(
+        &self,
+        prefecture_id: u32,
+        as_of: Option<&AsOf>,
+        field_mask: &StationFieldMask,
+    ) -> Result<Vec<Station>, UseCaseError> {
+        if self.find_prefecture_by_id(prefecture_id).await?.is_none() {
+            return Err(UseCaseError::NotFound {
+                entity_type: "Prefecture",
+                entity_id: prefecture_id.to_string(),
+            });
+        }
+
+        let stations = self
+            .station_repository
+            .get_by_prefecture_id(prefecture_id, as_of)
+            .await?;
+        let stations = self
+            .update_station_vec_with_attributes(stations, None, field_mask)
+            .await?;
+
+        Ok(stations)
+    }
+    async fn get_lines_by_prefecture_id(
+        &self,
+        prefecture_id: u32,
+        as_of: Option<&AsOf>,
+    ) -> Result<Vec<Line>, UseCaseError> {
+        if self.find_prefecture_by_id(prefecture_id).await?.is_none() {
+            return Err(UseCaseError::NotFound {
+                entity_type: "Prefecture",
+                entity_id: prefecture_id.to_string(),
+            });
+        }
+
+        let lines = self
+            .line_repository
+            .get_by_prefecture_id(prefecture_id, as_of)
+            .await?;
+        Ok(lines)
+    }
+    async fn find_company_by_id(&self, company_id: u32) -> Result<Option<Company>, UseCaseError> {
+        let company = self.company_repository.find_by_id(company_id).await?;
+        Ok(company)
+    }
+    async fn get_companies(&self, filter: &CompanyFilter) -> Result<Vec<Company>, UseCaseError> {
+        let companies = self.company_repository.get_all(filter).await?;
+        Ok(companies)
+    }
+    async fn get_lines_by_company_id(
+        &self,
+        company_id: u32,
+        as_of: Option<&AsOf>,
+    ) -> Result<Vec<Line>, UseCaseError> {
+        let Some(company) = self.find_company_by_id(company_id).await? else {
+            return Err(UseCaseError::NotFound {
+                entity_type: "Company",
+                entity_id: company_id.to_string(),
+            });
+        };
+
+        let lines = self
+            .line_repository
+            .get_by_company_id(company_id, as_of)
+            .await?
+            .into_iter()
+            .map(|mut line| {
+                line.company = Some(company.clone());
+                line.line_symbols = self.get_line_symbols(&line);
+                line
+            })
+            .collect();
+
+        Ok(lines)
+    }
+    async fn get_stations_by_company_id(
+        &self,
+        company_id: u32,
+        as_of: Option<&AsOf>,
+        field_mask: &StationFieldMask,
+    ) -> Result<Vec<Station>, UseCaseError> {
+        if self.find_company_by_id(company_id).await?.is_none() {
+            return Err(UseCaseError::NotFound {
+                entity_type: "Company",
+                entity_id: company_id.to_string(),
+            });
+        }
+
+        let stations = self
+            .station_repository
+            .get_by_company_id(company_id, as_of)
+            .await?;
+        let stations = self
+            .update_station_vec_with_attributes(stations, None, field_mask)
+            .await?;
+
+        Ok(stations)
+    }
+    async fn find_train_type_by_id(&self, type_id: u32) -> Result<Option<TrainType>, UseCaseError> {
+        let Some(mut train_type) = self.train_type_repository.find_by_type_cd(type_id).await?
+        else {
+            return Ok(None);
+        };
+
+        // 全系統で走る路線をまとめる
+        let mut line_ids = BTreeSet::new();
+        train_type.lines = self
+            .get_train_types(&TrainTypeFilter::by_type_cd(type_id))
+            .await?
+            .into_iter()
+            .flat_map(|tt| tt.lines)
+            .filter(|line| line_ids.insert(line.line_cd))
+            .collect();
+
+        Ok(Some(train_type))
+    }
+    async fn get_train_types(
+        &self,
+        filter: &TrainTypeFilter,
+    ) -> Result<Vec<TrainType>, UseCaseError> {
+        let mut train_types = self.train_type_repository.get_by_filter(filter).await?;
+
+        let line_group_ids = train_types
+            .iter()
+            .map(|tt| tt.line_group_cd)
+            .collect::<BTreeSet<u32>>()
+            .into_iter()
+            .collect::<Vec<u32>>();
+        let lines = self
+            .line_repository
+            .get_by_line_group_id_vec(&line_group_ids)
+            .await?;
+        let company_ids = lines.iter().map(|l| l.company_cd).collect::<Vec<u32>>();
+        let companies = self.company_repository.find_by_id_vec(&company_ids).await?;
+
+        for tt in train_types.iter_mut() {
+            tt.lines = lines
+                .iter()
+                .filter(|l| l.line_group_cd == Some(tt.line_group_cd))
+                .cloned()
+                .map(|mut line| {
+                    line.company = companies
+                        .iter()
+                        .find(|c| c.company_cd == line.company_cd)
+                        .cloned();
+                    line.line_symbols = self.get_line_symbols(&line);
+                    line
+                })
+                .collect();
+        }
+
+        Ok(train_types)
+    }
+    async fn get_stations_by_train_type(
+        &self,
+        type_id: u32,
+        line_group_id: Option<u32>,
+        field_mask: &StationFieldMask,
+    ) -> Result<Vec<Station>, UseCaseError> {
+        let not_found = || UseCaseError::NotFound {
+            entity_type: "TrainType",
+            entity_id: format!("type_id: {}, line_group_id: {:?}", type_id, line_group_id),
+        };
+
+        let line_group_ids: Vec<u32> = self
+            .train_type_repository
+            .get_by_filter(&TrainTypeFilter::by_type_cd(type_id))
+            .await?
+            .into_iter()
+            .map(|tt| tt.line_group_cd)
+            .collect();
+
+        let line_group_id = match (line_group_id, line_group_ids.as_slice()) {
+            (Some(line_group_id), ids) if ids.contains(&line_group_id) => line_group_id,
+            (None, [line_group_id]) => *line_group_id,
+            // NOTE: 同じ種別でも系統ごとに停車駅が違うので、どれか選んでもらう
+            (None, ids) if ids.len() > 1 => {
+                return Err(UseCaseError::InvalidArgument(format!(
+                    "Train type {} runs on multiple line groups, specify one of {:?}",
+                    type_id, ids
+                )));
+            }
+            _ => return Err(not_found()),
+        };
+
+        let stations = self
+            .get_stations_by_line_group_id(line_group_id, None, field_mask)
+            .await?
+            .into_iter()
+            .filter(|s| s.type_cd == Some(type_id) && s.stop_condition != StopCondition::Not)
+            .collect();
+
+        Ok(stations)
+    }
+    async fn get_stopping_pattern_diff(
+        &self,
+        first_line_group_id: u32,
+        second_line_group_id: u32,
+    ) -> Result<StoppingPattern, UseCaseError> {
+        let mut line_group_stations = vec![];
+        for line_group_id in [first_line_group_id, second_line_group_id] {
+            let stations = self
+                .get_stations_by_line_group_id(line_group_id, None, &StationFieldMask::ALL)
+                .await?;
+            if stations.is_empty() {
+                return Err(UseCaseError::NotFound {
+                    entity_type: "Station",
+                    entity_id: format!("line_group_id: {}", line_group_id),
+                });
+            }
+            line_group_stations.push(stations);
+        }
+        let second = line_group_stations.pop().unwrap_or_default();
+        let first = line_group_stations.pop().unwrap_or_default();
+
+        let pattern = StoppingPattern::new(first, second);
+        if !pattern.has_common_station() {
+            return Err(UseCaseError::InvalidArgument(format!(
+                "Line groups {} and {} do not share any station",
+                first_line_group_id, second_line_group_id
+            )));
+        }
+
+        Ok(pattern)
+    }
+
+    async fn get_line_geojson(&self, line_id: u32) -> Result<FeatureCollection, UseCaseError> {
+        let Some(mut line) = self.line_repository.find_by_id(line_id).await? else {
+            return Err(UseCaseError::NotFound {
+                entity_type: "Line",
+                entity_id: line_id.to_string(),
+            });
+        };
+        line.line_symbols = self.get_line_symbols(&line);
+
+        // NOTE: GeoJSONには駅ナンバリングしか載せないので乗り換え路線や種別は引かない
+        let field_mask = StationFieldMask {
+            station_numbers: true,
+            ..StationFieldMask::NONE
+        };
+        let stations = self
+            .get_stations_by_line_id(line_id, None, &field_mask)
+            .await?;
+
+        Ok(FeatureCollection::from_line(&line, &stations))
+    }
+
+    async fn get_dataset_info(&self) -> Result<Option<DatasetInfo>, UseCaseError> {
+        let info = self.dataset_info_repository.find_current().await?;
+        Ok(info)
+    }
+}
+
+// 上限を超えたかどうか分かるように1件多く取る
+fn spatial_search_fetch_limit(limit: Option<u32>) -> u32 {
+    limit.unwrap_or(u32::MAX).min(MAX_SPATIAL_SEARCH_LIMIT + 1)
+}
+
+// limitより手前で上限に切られたときだけエラーにする。limitで切れたのはクライアントの指定どおり
+fn check_spatial_search_result(
+    stations: &[Station],
+    limit: Option<u32>,
+) -> Result<(), UseCaseError> {
+    let exceeded = stations.len() > MAX_SPATIAL_SEARCH_LIMIT as usize;
+    if exceeded && limit.is_none_or(|limit| limit > MAX_SPATIAL_SEARCH_LIMIT) {
+        return Err(UseCaseError::InvalidArgument(format!(
+            "More than {} stations matched; narrow the search area or use page_size",
+            MAX_SPATIAL_SEARCH_LIMIT
+        )));
+    }
+    Ok(())
 }
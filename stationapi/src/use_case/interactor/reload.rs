@@ -0,0 +1,131 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tokio::sync::Mutex;
+
+use crate::{
+    domain::entity::{dataset_changelog::DatasetChangelog, dataset_summary::DatasetSummary},
+    use_case::{
+        cache::QueryCache,
+        error::UseCaseError,
+        interactor::reloadable_query::ReloadableQueryInteractor,
+        traits::{
+            query::QueryUseCase,
+            reload::{DatasetLoader, ReloadUseCase},
+        },
+    },
+};
+
+pub struct ReloadInteractor<L: DatasetLoader> {
+    loader: L,
+    query_use_case: ReloadableQueryInteractor<L::QueryUseCase>,
+    query_cache: Arc<QueryCache>,
+    // NOTE: リロードは同時に1つだけ走らせる
+    current_source: Mutex<String>,
+}
+
+impl<L: DatasetLoader> ReloadInteractor<L> {
+    pub fn new(
+        loader: L,
+        query_use_case: ReloadableQueryInteractor<L::QueryUseCase>,
+        query_cache: Arc<QueryCache>,
+        source: String,
+    ) -> Self {
+        Self {
+            loader,
+            query_use_case,
+            query_cache,
+            current_source: Mutex::new(source),
+        }
+    }
+}
+
+#[async_trait]
+impl<L: DatasetLoader> ReloadUseCase for ReloadInteractor<L> {
+    async fn reload_dataset(&self, source: Option<String>) -> Result<DatasetSummary, UseCaseError> {
+        let mut current_source = self.current_source.lock().await;
+        let source = source.unwrap_or_else(|| current_source.clone());
+
+        let (query_use_case, summary) = self.loader.load(&source).await?;
+        let dataset_info = query_use_case.get_dataset_info().await?;
+        // NOTE: 処理中のリクエストは古いデータを最後まで使う
+        self.query_use_case.replace(query_use_case, dataset_info);
+        self.query_cache.invalidate();
+        *current_source = source;
+
+        Ok(summary)
+    }
+
+    async fn reload_dataset_if_changed(&self) -> Result<Option<DatasetSummary>, UseCaseError> {
+        // NOTE: 読み込み元に問い合わせたバージョンと、読み込んだときのバージョンを比べる
+        let latest = self.query_use_case.current().get_dataset_info().await?;
+        let loaded = self.query_use_case.get_dataset_info().await?;
+        match latest {
+            // NOTE: migrationの途中はdataset_infoがまだ入っていないので、書き終わるまで待つ
+            Some(latest) if Some(&latest) != loaded.as_ref() => {
+                self.reload_dataset(None).await.map(Some)
+            }
+            _ => Ok(None),
+        }
+    }
+
+    async fn diff_dataset(
+        &self,
+        base_source: Option<String>,
+        target_source: String,
+    ) -> Result<DatasetChangelog, UseCaseError> {
+        let base_source = match base_source {
+            Some(base_source) => base_source,
+            None => self.current_source.lock().await.clone(),
+        };
+        self.loader.diff(&base_source, &target_source).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{path::Path, sync::Arc};
+
+    use super::ReloadInteractor;
+    use crate::{
+        domain::entity::dataset_info::DatasetInfo,
+        infrastructure::memory::{dataset::Dataset, dataset_loader::InMemoryDatasetLoader},
+        use_case::{
+            cache::{CacheConfig, QueryCache},
+            interactor::reloadable_query::ReloadableQueryInteractor,
+            traits::{query::QueryUseCase, reload::ReloadUseCase},
+        },
+    };
+
+    #[tokio::test]
+    async fn reload_only_when_the_version_changed() {
+        let data_path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../data");
+        let dataset = Dataset::load(&data_path).expect("Failed to load the dataset");
+        let query_interactor = InMemoryDatasetLoader::build_query_interactor(Arc::new(dataset));
+        let current_info = query_interactor.get_dataset_info().await.unwrap();
+        let stale_info = DatasetInfo {
+            version: "stale".to_string(),
+            content_hash: "stale".to_string(),
+            created_at: 0,
+        };
+        let reloadable = ReloadableQueryInteractor::new(query_interactor, Some(stale_info));
+        let reload_interactor = ReloadInteractor::new(
+            InMemoryDatasetLoader,
+            reloadable.clone(),
+            Arc::new(QueryCache::new(CacheConfig::default())),
+            data_path.to_string_lossy().to_string(),
+        );
+
+        assert!(reload_interactor
+            .reload_dataset_if_changed()
+            .await
+            .unwrap()
+            .is_some());
+        assert_eq!(reloadable.get_dataset_info().await.unwrap(), current_info);
+        assert!(reload_interactor
+            .reload_dataset_if_changed()
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
@@ -0,0 +1,596 @@
+use std::sync::{Arc, RwLock};
+
+use async_trait::async_trait;
+
+use crate::{
+    domain::entity::{
+        company::Company,
+        dataset_info::DatasetInfo,
+        geojson::FeatureCollection,
+        journey::Journey,
+        line::Line,
+        line_symbol::LineSymbol,
+        location_tracker::LocationTracker,
+        misc::{
+            AsOf, BoundingBox, CompanyFilter, StationFieldMask, StationFilter,
+            StationIdWithDistance, TrainTypeFilter,
+        },
+        prefecture::Prefecture,
+        service_day::ServiceDay,
+        station::Station,
+        station_number::StationNumber,
+        stopping_pattern::StoppingPattern,
+        suggestion::Suggestion,
+        train_type::TrainType,
+        travel_direction::TravelDirectionEstimate,
+    },
+    station_api::{JourneySearchCriterion, Route},
+    use_case::{error::UseCaseError, traits::query::QueryUseCase},
+};
+
+// 読み込んだデータと、そのバージョン
+struct Snapshot<T> {
+    query_use_case: Arc<T>,
+    dataset_info: Option<DatasetInfo>,
+}
+
+// データを入れ替えられるQueryUseCase。呼び出しごとにその時点のデータを掴む
+pub struct ReloadableQueryInteractor<T: QueryUseCase> {
+    current: Arc<RwLock<Snapshot<T>>>,
+}
+
+// NOTE: クローンしたものどうしは同じデータを指す
+impl<T: QueryUseCase> Clone for ReloadableQueryInteractor<T> {
+    fn clone(&self) -> Self {
+        Self {
+            current: Arc::clone(&self.current),
+        }
+    }
+}
+
+impl<T: QueryUseCase> ReloadableQueryInteractor<T> {
+    // NOTE: dataset_infoは読み込んだときに一度だけ取り、リクエストごとには問い合わせない
+    pub fn new(query_use_case: T, dataset_info: Option<DatasetInfo>) -> Self {
+        Self {
+            current: Arc::new(RwLock::new(Snapshot {
+                query_use_case: Arc::new(query_use_case),
+                dataset_info,
+            })),
+        }
+    }
+
+    pub fn current(&self) -> Arc<T> {
+        Arc::clone(&self.current.read().unwrap().query_use_case)
+    }
+
+    pub fn replace(&self, query_use_case: T, dataset_info: Option<DatasetInfo>) {
+        *self.current.write().unwrap() = Snapshot {
+            query_use_case: Arc::new(query_use_case),
+            dataset_info,
+        };
+    }
+}
+
+#[async_trait]
+impl<T: QueryUseCase> QueryUseCase for ReloadableQueryInteractor<T> {
+    async fn find_station_by_id(
+        &self,
+        station_id: u32,
+        field_mask: &StationFieldMask,
+    ) -> Result<Option<Station>, UseCaseError> {
+        self.current()
+            .find_station_by_id(station_id, field_mask)
+            .await
+    }
+
+    async fn get_stations_by_id_vec(
+        &self,
+        station_ids: &[u32],
+        field_mask: &StationFieldMask,
+    ) -> Result<Vec<Station>, UseCaseError> {
+        self.current()
+            .get_stations_by_id_vec(station_ids, field_mask)
+            .await
+    }
+
+    async fn get_stations_by_id_vec_without_attributes(
+        &self,
+        station_ids: &[u32],
+    ) -> Result<Vec<Station>, UseCaseError> {
+        self.current()
+            .get_stations_by_id_vec_without_attributes(station_ids)
+            .await
+    }
+
+    async fn get_stations_by_group_id(
+        &self,
+        station_group_id: u32,
+        field_mask: &StationFieldMask,
+    ) -> Result<Vec<Station>, UseCaseError> {
+        self.current()
+            .get_stations_by_group_id(station_group_id, field_mask)
+            .await
+    }
+
+    async fn get_stations_by_group_id_vec(
+        &self,
+        station_group_id_vec: &[u32],
+    ) -> Result<Vec<Station>, UseCaseError> {
+        self.current()
+            .get_stations_by_group_id_vec(station_group_id_vec)
+            .await
+    }
+
+    async fn get_stations_by_coordinates(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        limit: Option<u32>,
+        field_mask: &StationFieldMask,
+    ) -> Result<Vec<Station>, UseCaseError> {
+        self.current()
+            .get_stations_by_coordinates(latitude, longitude, limit, field_mask)
+            .await
+    }
+
+    async fn get_stations_by_coordinates_without_attributes(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        limit: Option<u32>,
+    ) -> Result<Vec<Station>, UseCaseError> {
+        self.current()
+            .get_stations_by_coordinates_without_attributes(latitude, longitude, limit)
+            .await
+    }
+
+    async fn get_stations_by_radius(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        radius_in_km: f64,
+        filter: &StationFilter,
+        limit: Option<u32>,
+        field_mask: &StationFieldMask,
+    ) -> Result<Vec<Station>, UseCaseError> {
+        self.current()
+            .get_stations_by_radius(latitude, longitude, radius_in_km, filter, limit, field_mask)
+            .await
+    }
+
+    async fn get_stations_by_bounding_box(
+        &self,
+        bounding_box: &BoundingBox,
+        filter: &StationFilter,
+        limit: Option<u32>,
+        field_mask: &StationFieldMask,
+    ) -> Result<Vec<Station>, UseCaseError> {
+        self.current()
+            .get_stations_by_bounding_box(bounding_box, filter, limit, field_mask)
+            .await
+    }
+
+    async fn get_stations_by_line_id(
+        &self,
+        line_id: u32,
+        station_id: Option<u32>,
+        field_mask: &StationFieldMask,
+    ) -> Result<Vec<Station>, UseCaseError> {
+        self.current()
+            .get_stations_by_line_id(line_id, station_id, field_mask)
+            .await
+    }
+
+    async fn get_stations_by_line_id_without_attributes(
+        &self,
+        line_id: u32,
+    ) -> Result<Vec<Station>, UseCaseError> {
+        self.current()
+            .get_stations_by_line_id_without_attributes(line_id)
+            .await
+    }
+
+    async fn get_stations_by_line_id_vec_without_attributes(
+        &self,
+        line_id_vec: &[u32],
+    ) -> Result<Vec<Station>, UseCaseError> {
+        self.current()
+            .get_stations_by_line_id_vec_without_attributes(line_id_vec)
+            .await
+    }
+
+    async fn get_stations_by_line_id_as_of(
+        &self,
+        line_id: u32,
+        as_of: &AsOf,
+        field_mask: &StationFieldMask,
+    ) -> Result<Vec<Station>, UseCaseError> {
+        self.current()
+            .get_stations_by_line_id_as_of(line_id, as_of, field_mask)
+            .await
+    }
+
+    async fn get_stations_by_name(
+        &self,
+        station_name: String,
+        get_stations_by_name: Option<u32>,
+        from_station_group_id: Option<u32>,
+        field_mask: &StationFieldMask,
+    ) -> Result<Vec<Station>, UseCaseError> {
+        self.current()
+            .get_stations_by_name(
+                station_name,
+                get_stations_by_name,
+                from_station_group_id,
+                field_mask,
+            )
+            .await
+    }
+
+    async fn find_company_by_id_vec(
+        &self,
+        company_id_vec: &[u32],
+    ) -> Result<Vec<Company>, UseCaseError> {
+        self.current().find_company_by_id_vec(company_id_vec).await
+    }
+
+    async fn update_station_vec_with_attributes(
+        &self,
+        stations: Vec<Station>,
+        line_group_id: Option<u32>,
+        field_mask: &StationFieldMask,
+    ) -> Result<Vec<Station>, UseCaseError> {
+        self.current()
+            .update_station_vec_with_attributes(stations, line_group_id, field_mask)
+            .await
+    }
+
+    async fn get_lines_by_station_group_id(
+        &self,
+        station_group_id: u32,
+    ) -> Result<Vec<Line>, UseCaseError> {
+        self.current()
+            .get_lines_by_station_group_id(station_group_id)
+            .await
+    }
+
+    async fn get_lines_by_station_group_id_vec(
+        &self,
+        station_group_id_vec: &[u32],
+    ) -> Result<Vec<Line>, UseCaseError> {
+        self.current()
+            .get_lines_by_station_group_id_vec(station_group_id_vec)
+            .await
+    }
+
+    fn get_station_numbers(&self, station: &Station) -> Vec<StationNumber> {
+        self.current().get_station_numbers(station)
+    }
+
+    fn get_line_symbols(&self, line: &Line) -> Vec<LineSymbol> {
+        self.current().get_line_symbols(line)
+    }
+
+    fn extract_line_from_station(&self, station: &Station) -> Line {
+        self.current().extract_line_from_station(station)
+    }
+
+    async fn get_stations_by_line_group_id(
+        &self,
+        line_group_id: u32,
+        service_day: Option<ServiceDay>,
+        field_mask: &StationFieldMask,
+    ) -> Result<Vec<Station>, UseCaseError> {
+        self.current()
+            .get_stations_by_line_group_id(line_group_id, service_day, field_mask)
+            .await
+    }
+
+    async fn get_stations_by_line_group_id_without_attributes(
+        &self,
+        line_group_id: u32,
+        service_day: Option<ServiceDay>,
+    ) -> Result<Vec<Station>, UseCaseError> {
+        self.current()
+            .get_stations_by_line_group_id_without_attributes(line_group_id, service_day)
+            .await
+    }
+
+    async fn get_stations_by_line_group_id_vec_without_attributes(
+        &self,
+        line_group_id_vec: &[u32],
+    ) -> Result<Vec<Station>, UseCaseError> {
+        self.current()
+            .get_stations_by_line_group_id_vec_without_attributes(line_group_id_vec)
+            .await
+    }
+
+    async fn get_lines_by_line_group_id_vec(
+        &self,
+        line_group_id_vec: &[u32],
+    ) -> Result<Vec<Line>, UseCaseError> {
+        self.current()
+            .get_lines_by_line_group_id_vec(line_group_id_vec)
+            .await
+    }
+
+    async fn get_train_types_by_station_id(
+        &self,
+        station_id: u32,
+    ) -> Result<Vec<TrainType>, UseCaseError> {
+        self.current()
+            .get_train_types_by_station_id(station_id)
+            .await
+    }
+
+    async fn get_all_train_types_by_station_id_vec(
+        &self,
+        station_id_vec: &[u32],
+    ) -> Result<Vec<TrainType>, UseCaseError> {
+        self.current()
+            .get_all_train_types_by_station_id_vec(station_id_vec)
+            .await
+    }
+
+    async fn get_train_types_by_station_id_vec(
+        &self,
+        station_id_vec: &[u32],
+        line_group_id: Option<u32>,
+    ) -> Result<Vec<TrainType>, UseCaseError> {
+        self.current()
+            .get_train_types_by_station_id_vec(station_id_vec, line_group_id)
+            .await
+    }
+
+    async fn get_station_id_and_distance_by_coordinates(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        line_id: Option<u32>,
+    ) -> Result<StationIdWithDistance, UseCaseError> {
+        self.current()
+            .get_station_id_and_distance_by_coordinates(latitude, longitude, line_id)
+            .await
+    }
+
+    async fn create_location_tracker(
+        &self,
+        line_id: u32,
+        line_group_id: Option<u32>,
+    ) -> Result<LocationTracker, UseCaseError> {
+        self.current()
+            .create_location_tracker(line_id, line_group_id)
+            .await
+    }
+
+    async fn get_travel_direction(
+        &self,
+        coordinates: &[(f64, f64)],
+        line_id: u32,
+        line_group_id: Option<u32>,
+    ) -> Result<Option<TravelDirectionEstimate>, UseCaseError> {
+        self.current()
+            .get_travel_direction(coordinates, line_id, line_group_id)
+            .await
+    }
+
+    async fn get_autocomplete_suggestions(
+        &self,
+        query: &str,
+        limit: Option<u32>,
+    ) -> Result<Vec<Suggestion>, UseCaseError> {
+        self.current()
+            .get_autocomplete_suggestions(query, limit)
+            .await
+    }
+
+    async fn get_routes(
+        &self,
+        from_station_id: u32,
+        to_station_id: u32,
+        service_day: Option<ServiceDay>,
+    ) -> Result<Vec<Route>, UseCaseError> {
+        self.current()
+            .get_routes(from_station_id, to_station_id, service_day)
+            .await
+    }
+
+    async fn get_journeys(
+        &self,
+        from_station_group_id: u32,
+        to_station_group_id: u32,
+        criterion: JourneySearchCriterion,
+        limit: Option<u32>,
+    ) -> Result<Vec<Journey>, UseCaseError> {
+        self.current()
+            .get_journeys(from_station_group_id, to_station_group_id, criterion, limit)
+            .await
+    }
+
+    async fn find_line_by_id(
+        &self,
+        line_id: u32,
+        as_of: Option<&AsOf>,
+    ) -> Result<Option<Line>, UseCaseError> {
+        self.current().find_line_by_id(line_id, as_of).await
+    }
+
+    async fn get_lines_by_name(
+        &self,
+        line_name: String,
+        limit: Option<u32>,
+    ) -> Result<Vec<Line>, UseCaseError> {
+        self.current().get_lines_by_name(line_name, limit).await
+    }
+
+    async fn find_prefecture_by_id(
+        &self,
+        prefecture_id: u32,
+    ) -> Result<Option<Prefecture>, UseCaseError> {
+        self.current().find_prefecture_by_id(prefecture_id).await
+    }
+
+    async fn get_prefectures(&self) -> Result<Vec<Prefecture>, UseCaseError> {
+        self.current().get_prefectures().await
+    }
+
+    async fn get_stations_by_prefecture_id(
+        &self,
+        prefecture_id: u32,
+        as_of: Option<&AsOf>,
+        field_mask: &StationFieldMask,
+    ) -> Result<Vec<Station>, UseCaseError> {
+        self.current()
+            .get_stations_by_prefecture_id(prefecture_id, as_of, field_mask)
+            .await
+    }
+
+    async fn get_lines_by_prefecture_id(
+        &self,
+        prefecture_id: u32,
+        as_of: Option<&AsOf>,
+    ) -> Result<Vec<Line>, UseCaseError> {
+        self.current()
+            .get_lines_by_prefecture_id(prefecture_id, as_of)
+            .await
+    }
+
+    async fn find_company_by_id(&self, company_id: u32) -> Result<Option<Company>, UseCaseError> {
+        self.current().find_company_by_id(company_id).await
+    }
+
+    async fn get_companies(&self, filter: &CompanyFilter) -> Result<Vec<Company>, UseCaseError> {
+        self.current().get_companies(filter).await
+    }
+
+    async fn get_lines_by_company_id(
+        &self,
+        company_id: u32,
+        as_of: Option<&AsOf>,
+    ) -> Result<Vec<Line>, UseCaseError> {
+        self.current()
+            .get_lines_by_company_id(company_id, as_of)
+            .await
+    }
+
+    async fn get_stations_by_company_id(
+        &self,
+        company_id: u32,
+        as_of: Option<&AsOf>,
+        field_mask: &StationFieldMask,
+    ) -> Result<Vec<Station>, UseCaseError> {
+        self.current()
+            .get_stations_by_company_id(company_id, as_of, field_mask)
+            .await
+    }
+
+    async fn find_train_type_by_id(&self, type_id: u32) -> Result<Option<TrainType>, UseCaseError> {
+        self.current().find_train_type_by_id(type_id).await
+    }
+
+    async fn get_train_types(
+        &self,
+        filter: &TrainTypeFilter,
+    ) -> Result<Vec<TrainType>, UseCaseError> {
+        self.current().get_train_types(filter).await
+    }
+
+    async fn get_stations_by_train_type(
+        &self,
+        type_id: u32,
+        line_group_id: Option<u32>,
+        field_mask: &StationFieldMask,
+    ) -> Result<Vec<Station>, UseCaseError> {
+        self.current()
+            .get_stations_by_train_type(type_id, line_group_id, field_mask)
+            .await
+    }
+
+    async fn get_stopping_pattern_diff(
+        &self,
+        first_line_group_id: u32,
+        second_line_group_id: u32,
+    ) -> Result<StoppingPattern, UseCaseError> {
+        self.current()
+            .get_stopping_pattern_diff(first_line_group_id, second_line_group_id)
+            .await
+    }
+
+    async fn get_line_geojson(&self, line_id: u32) -> Result<FeatureCollection, UseCaseError> {
+        self.current().get_line_geojson(line_id).await
+    }
+
+    async fn get_dataset_info(&self) -> Result<Option<DatasetInfo>, UseCaseError> {
+        Ok(self.current.read().unwrap().dataset_info.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{path::Path, sync::Arc};
+
+    use tokio::sync::oneshot;
+
+    use super::ReloadableQueryInteractor;
+    use crate::{
+        domain::entity::misc::StationFieldMask,
+        infrastructure::memory::{dataset::Dataset, dataset_loader::InMemoryDatasetLoader},
+        use_case::traits::query::QueryUseCase,
+    };
+
+    #[tokio::test]
+    async fn reload_while_request_is_running() {
+        let dataset = Dataset::load(&Path::new(env!("CARGO_MANIFEST_DIR")).join("../data"))
+            .expect("Failed to load the dataset");
+        let empty_dataset = Dataset::new(
+            vec![],
+            vec![],
+            vec![],
+            vec![],
+            vec![],
+            vec![],
+            vec![],
+            vec![],
+        );
+        let dataset_info = dataset.info().cloned();
+        let reloadable = ReloadableQueryInteractor::new(
+            InMemoryDatasetLoader::build_query_interactor(Arc::new(dataset)),
+            dataset_info.clone(),
+        );
+        let field_mask = StationFieldMask::NONE;
+        let (started_tx, started_rx) = oneshot::channel();
+        let (release_tx, release_rx) = oneshot::channel();
+
+        let (station, _) = tokio::join!(
+            async {
+                // NOTE: 各メソッドと同じく、呼び出した時点のデータを掴んでから問い合わせる
+                let current = reloadable.current();
+                started_tx.send(()).unwrap();
+                release_rx.await.unwrap();
+                current.find_station_by_id(1130208, &field_mask).await
+            },
+            async {
+                started_rx.await.unwrap();
+                reloadable.replace(
+                    InMemoryDatasetLoader::build_query_interactor(Arc::new(empty_dataset)),
+                    None,
+                );
+                release_tx.send(()).unwrap();
+            },
+        );
+
+        // 処理中のリクエストは古いデータを最後まで使う
+        assert_eq!(station.unwrap().unwrap().station_name, "新宿");
+        // 新しいリクエストは、クローンしたものからでも入れ替えたデータを見る
+        for query_use_case in [&reloadable, &reloadable.clone()] {
+            let station = query_use_case
+                .find_station_by_id(1130208, &field_mask)
+                .await
+                .unwrap();
+            assert_eq!(station, None);
+            // バージョンもデータと一緒に入れ替わる
+            assert_eq!(query_use_case.get_dataset_info().await.unwrap(), None);
+        }
+        assert!(dataset_info.is_some());
+    }
+}
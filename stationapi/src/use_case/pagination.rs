@@ -0,0 +1,268 @@
+use crate::use_case::error::UseCaseError;
+
+// page_tokenだけ指定されたときの1ページの件数
+pub const DEFAULT_PAGE_SIZE: u32 = 100;
+// これより大きいpage_sizeはこの件数に切り詰める
+pub const MAX_PAGE_SIZE: u32 = 1000;
+const PAGE_TOKEN_VERSION: &str = "v1";
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PageRequest {
+    pub page_size: Option<u32>,
+    pub page_token: Option<String>,
+}
+
+impl PageRequest {
+    pub fn new(page_size: Option<u32>, page_token: Option<String>) -> Self {
+        Self {
+            page_size,
+            // NOTE: proto3のoptionalでも空文字が送られてくることがあるので未指定として扱う
+            page_token: page_token.filter(|token| !token.is_empty()),
+        }
+    }
+
+    // どちらも未指定なら従来どおり全件を返す
+    pub fn is_unpaged(&self) -> bool {
+        self.page_size.is_none() && self.page_token.is_none()
+    }
+
+    // 件数に上限がある検索で、page_sizeもlimitも指定されていなければ1ページ目として扱う。
+    // NOTE: 黙って切り詰めず、続きがあればnext_page_tokenを返すため
+    pub fn or_first_page(self, limit: Option<u32>) -> Self {
+        if self.is_unpaged() && limit.is_none() {
+            return Self::new(Some(DEFAULT_PAGE_SIZE), None);
+        }
+        self
+    }
+
+    fn page_size(&self) -> usize {
+        self.page_size
+            .filter(|size| *size > 0)
+            .unwrap_or(DEFAULT_PAGE_SIZE)
+            .min(MAX_PAGE_SIZE) as usize
+    }
+
+    // 並び順の位置をキーにするページで、次のページの有無を判定するのに必要な件数。limitはリクエストの上限
+    pub fn fetch_limit(&self, limit: Option<u32>) -> Result<Option<u32>, UseCaseError> {
+        if self.is_unpaged() {
+            return Ok(limit);
+        }
+        let offset = match &self.page_token {
+            Some(token) => decode_position(token)?,
+            None => 0,
+        };
+        let fetch_limit = offset.saturating_add((self.page_size() as u32).saturating_add(1));
+        Ok(Some(
+            limit.map_or(fetch_limit, |limit| limit.min(fetch_limit)),
+        ))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next_page_token: Option<String>,
+}
+
+// itemsはkeyの昇順に並んでいること。キーが変わらない限り、途中で件数が変わってもページがずれない
+pub fn paginate<T, F>(
+    items: Vec<T>,
+    page_request: &PageRequest,
+    key: F,
+) -> Result<Page<T>, UseCaseError>
+where
+    F: Fn(&T) -> Vec<u32>,
+{
+    if page_request.is_unpaged() {
+        return Ok(Page {
+            items,
+            next_page_token: None,
+        });
+    }
+
+    let cursor = match &page_request.page_token {
+        Some(token) => Some(decode_page_token(token)?),
+        None => None,
+    };
+    let page_size = page_request.page_size();
+
+    let mut rest = items
+        .into_iter()
+        .filter(|item| cursor.as_ref().is_none_or(|cursor| &key(item) > cursor));
+    let items: Vec<T> = rest.by_ref().take(page_size).collect();
+    let has_next = rest.next().is_some();
+
+    let next_page_token = match items.last() {
+        Some(last) if has_next => Some(encode_page_token(&key(last))),
+        _ => None,
+    };
+
+    Ok(Page {
+        items,
+        next_page_token,
+    })
+}
+
+// 検索結果の順位や距離順など、並び順の位置をキーにする
+pub fn paginate_in_order<T>(
+    items: Vec<T>,
+    page_request: &PageRequest,
+) -> Result<Page<T>, UseCaseError> {
+    let page = paginate(
+        items.into_iter().enumerate().collect(),
+        page_request,
+        |(index, _)| vec![*index as u32],
+    )?;
+
+    Ok(Page {
+        items: page.items.into_iter().map(|(_, item)| item).collect(),
+        next_page_token: page.next_page_token,
+    })
+}
+
+fn encode_page_token(key: &[u32]) -> String {
+    let key = key
+        .iter()
+        .map(|value| value.to_string())
+        .collect::<Vec<_>>()
+        .join(".");
+    format!("{}:{}", PAGE_TOKEN_VERSION, key)
+        .bytes()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+fn decode_page_token(token: &str) -> Result<Vec<u32>, UseCaseError> {
+    let invalid = || UseCaseError::InvalidArgument(format!(r#"Invalid page token "{}""#, token));
+
+    if !token.len().is_multiple_of(2) || !token.is_ascii() {
+        return Err(invalid());
+    }
+    let bytes = (0..token.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(&token[index..index + 2], 16))
+        .collect::<Result<Vec<u8>, _>>()
+        .map_err(|_| invalid())?;
+    let decoded = String::from_utf8(bytes).map_err(|_| invalid())?;
+
+    let key = decoded
+        .strip_prefix(PAGE_TOKEN_VERSION)
+        .and_then(|rest| rest.strip_prefix(':'))
+        .ok_or_else(invalid)?;
+    key.split('.')
+        .map(|value| value.parse::<u32>().map_err(|_| invalid()))
+        .collect()
+}
+
+// 並び順の位置をキーにしたトークンから、次のページの先頭の位置を求める
+fn decode_position(token: &str) -> Result<u32, UseCaseError> {
+    match decode_page_token(token)?.as_slice() {
+        [index] => Ok(index.saturating_add(1)),
+        _ => Err(UseCaseError::InvalidArgument(format!(
+            r#"Invalid page token "{}""#,
+            token
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_page_token, paginate, paginate_in_order, PageRequest, MAX_PAGE_SIZE};
+    use crate::use_case::error::UseCaseError;
+
+    #[test]
+    fn unpaged_returns_everything() {
+        let page = paginate_in_order(vec![1, 2, 3], &PageRequest::new(None, None)).unwrap();
+        assert_eq!(page.items, vec![1, 2, 3]);
+        assert_eq!(page.next_page_token, None);
+
+        // 空文字のトークンは未指定と同じ
+        let page_request = PageRequest::new(None, Some("".to_string()));
+        assert!(page_request.is_unpaged());
+        assert_eq!(page_request.fetch_limit(None).unwrap(), None);
+        assert_eq!(page_request.fetch_limit(Some(5)).unwrap(), Some(5));
+    }
+
+    #[test]
+    fn paginate_by_key() {
+        // (e_sort, station_cd)
+        let stations = vec![(1, 100), (2, 200), (2, 201), (3, 300), (4, 400)];
+        let key = |station: &(u32, u32)| vec![station.0, station.1];
+
+        let page = paginate(stations.clone(), &PageRequest::new(Some(2), None), key).unwrap();
+        assert_eq!(page.items, vec![(1, 100), (2, 200)]);
+
+        let page = paginate(
+            stations.clone(),
+            &PageRequest::new(Some(2), page.next_page_token),
+            key,
+        )
+        .unwrap();
+        assert_eq!(page.items, vec![(2, 201), (3, 300)]);
+
+        // 前のページの駅が消えても続きから返す
+        let stations: Vec<_> = stations
+            .into_iter()
+            .filter(|station| station.1 != 201)
+            .collect();
+        let page = paginate(
+            stations,
+            &PageRequest::new(Some(2), page.next_page_token),
+            key,
+        )
+        .unwrap();
+        assert_eq!(page.items, vec![(4, 400)]);
+        assert_eq!(page.next_page_token, None);
+    }
+
+    #[test]
+    fn paginate_by_position() {
+        let items: Vec<u32> = (0..5).collect();
+
+        let page_request = PageRequest::new(Some(3), None);
+        assert_eq!(page_request.fetch_limit(None).unwrap(), Some(4));
+        assert_eq!(page_request.fetch_limit(Some(2)).unwrap(), Some(2));
+        let page = paginate_in_order(items.clone(), &page_request).unwrap();
+        assert_eq!(page.items, vec![0, 1, 2]);
+
+        let page_request = PageRequest::new(Some(3), page.next_page_token);
+        assert_eq!(page_request.fetch_limit(None).unwrap(), Some(7));
+        let page = paginate_in_order(items, &page_request).unwrap();
+        assert_eq!(page.items, vec![3, 4]);
+        assert_eq!(page.next_page_token, None);
+    }
+
+    #[test]
+    fn clamp_page_size() {
+        let page_request = PageRequest::new(Some(u32::MAX), None);
+        assert_eq!(
+            page_request.fetch_limit(None).unwrap(),
+            Some(MAX_PAGE_SIZE + 1)
+        );
+
+        let items: Vec<u32> = (0..MAX_PAGE_SIZE + 5).collect();
+        let page = paginate_in_order(items, &page_request).unwrap();
+        assert_eq!(page.items.len(), MAX_PAGE_SIZE as usize);
+        let page = paginate_in_order(
+            (0..MAX_PAGE_SIZE + 5).collect(),
+            &PageRequest::new(Some(u32::MAX), page.next_page_token),
+        )
+        .unwrap();
+        assert_eq!(
+            page.items,
+            (MAX_PAGE_SIZE..MAX_PAGE_SIZE + 5).collect::<Vec<_>>()
+        );
+        assert_eq!(page.next_page_token, None);
+    }
+
+    #[test]
+    fn invalid_page_token() {
+        for token in ["zz", "abc", "76313a", "76323a31", "76313a61"] {
+            assert!(matches!(
+                decode_page_token(token),
+                Err(UseCaseError::InvalidArgument(_))
+            ));
+        }
+        assert_eq!(decode_page_token("76313a312e32").unwrap(), vec![1, 2]);
+    }
+}
@@ -2,23 +2,50 @@ use async_trait::async_trait;
 
 use crate::{
     domain::entity::{
-        company::Company, line::Line, line_symbol::LineSymbol, misc::StationIdWithDistance,
-        station::Station, station_number::StationNumber, train_type::TrainType,
+        company::Company,
+        dataset_info::DatasetInfo,
+        geojson::FeatureCollection,
+        journey::Journey,
+        line::Line,
+        line_symbol::LineSymbol,
+        location_tracker::LocationTracker,
+        misc::{
+            AsOf, BoundingBox, CompanyFilter, StationFieldMask, StationFilter,
+            StationIdWithDistance, TrainTypeFilter,
+        },
+        prefecture::Prefecture,
+        service_day::ServiceDay,
+        station::Station,
+        station_number::StationNumber,
+        stopping_pattern::StoppingPattern,
+        suggestion::Suggestion,
+        train_type::TrainType,
+        travel_direction::TravelDirectionEstimate,
     },
-    station_api::Route,
+    station_api::{JourneySearchCriterion, Route},
     use_case::error::UseCaseError,
 };
 
 #[async_trait]
 pub trait QueryUseCase: Send + Sync + 'static {
-    async fn find_station_by_id(&self, station_id: u32) -> Result<Option<Station>, UseCaseError>;
+    async fn find_station_by_id(
+        &self,
+        station_id: u32,
+        field_mask: &StationFieldMask,
+    ) -> Result<Option<Station>, UseCaseError>;
     async fn get_stations_by_id_vec(
         &self,
         station_ids: &[u32],
+        field_mask: &StationFieldMask,
+    ) -> Result<Vec<Station>, UseCaseError>;
+    async fn get_stations_by_id_vec_without_attributes(
+        &self,
+        station_ids: &[u32],
     ) -> Result<Vec<Station>, UseCaseError>;
     async fn get_stations_by_group_id(
         &self,
         station_group_id: u32,
+        field_mask: &StationFieldMask,
     ) -> Result<Vec<Station>, UseCaseError>;
     async fn get_stations_by_group_id_vec(
         &self,
@@ -29,17 +56,56 @@ pub trait QueryUseCase: Send + Sync + 'static {
         latitude: f64,
         longitude: f64,
         limit: Option<u32>,
+        field_mask: &StationFieldMask,
+    ) -> Result<Vec<Station>, UseCaseError>;
+    async fn get_stations_by_coordinates_without_attributes(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        limit: Option<u32>,
+    ) -> Result<Vec<Station>, UseCaseError>;
+    async fn get_stations_by_radius(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        radius_in_km: f64,
+        filter: &StationFilter,
+        limit: Option<u32>,
+        field_mask: &StationFieldMask,
+    ) -> Result<Vec<Station>, UseCaseError>;
+    async fn get_stations_by_bounding_box(
+        &self,
+        bounding_box: &BoundingBox,
+        filter: &StationFilter,
+        limit: Option<u32>,
+        field_mask: &StationFieldMask,
     ) -> Result<Vec<Station>, UseCaseError>;
     async fn get_stations_by_line_id(
         &self,
         line_id: u32,
         station_id: Option<u32>,
+        field_mask: &StationFieldMask,
+    ) -> Result<Vec<Station>, UseCaseError>;
+    async fn get_stations_by_line_id_without_attributes(
+        &self,
+        line_id: u32,
+    ) -> Result<Vec<Station>, UseCaseError>;
+    async fn get_stations_by_line_id_vec_without_attributes(
+        &self,
+        line_id_vec: &[u32],
+    ) -> Result<Vec<Station>, UseCaseError>;
+    async fn get_stations_by_line_id_as_of(
+        &self,
+        line_id: u32,
+        as_of: &AsOf,
+        field_mask: &StationFieldMask,
     ) -> Result<Vec<Station>, UseCaseError>;
     async fn get_stations_by_name(
         &self,
         station_name: String,
         get_stations_by_name: Option<u32>,
         from_station_group_id: Option<u32>,
+        field_mask: &StationFieldMask,
     ) -> Result<Vec<Station>, UseCaseError>;
     async fn find_company_by_id_vec(
         &self,
@@ -49,6 +115,7 @@ pub trait QueryUseCase: Send + Sync + 'static {
         &self,
         stations: Vec<Station>,
         line_group_id: Option<u32>,
+        field_mask: &StationFieldMask,
     ) -> Result<Vec<Station>, UseCaseError>;
     async fn get_lines_by_station_group_id(
         &self,
@@ -64,11 +131,31 @@ pub trait QueryUseCase: Send + Sync + 'static {
     async fn get_stations_by_line_group_id(
         &self,
         line_group_id: u32,
+        service_day: Option<ServiceDay>,
+        field_mask: &StationFieldMask,
+    ) -> Result<Vec<Station>, UseCaseError>;
+    async fn get_stations_by_line_group_id_without_attributes(
+        &self,
+        line_group_id: u32,
+        service_day: Option<ServiceDay>,
+    ) -> Result<Vec<Station>, UseCaseError>;
+    async fn get_stations_by_line_group_id_vec_without_attributes(
+        &self,
+        line_group_id_vec: &[u32],
     ) -> Result<Vec<Station>, UseCaseError>;
+    async fn get_lines_by_line_group_id_vec(
+        &self,
+        line_group_id_vec: &[u32],
+    ) -> Result<Vec<Line>, UseCaseError>;
     async fn get_train_types_by_station_id(
         &self,
         station_id: u32,
     ) -> Result<Vec<TrainType>, UseCaseError>;
+    // 駅ごとに停車するすべての種別をまとめて引く
+    async fn get_all_train_types_by_station_id_vec(
+        &self,
+        station_id_vec: &[u32],
+    ) -> Result<Vec<TrainType>, UseCaseError>;
     async fn get_train_types_by_station_id_vec(
         &self,
         station_id_vec: &[u32],
@@ -80,15 +167,90 @@ pub trait QueryUseCase: Send + Sync + 'static {
         longitude: f64,
         line_id: Option<u32>,
     ) -> Result<StationIdWithDistance, UseCaseError>;
+    async fn create_location_tracker(
+        &self,
+        line_id: u32,
+        line_group_id: Option<u32>,
+    ) -> Result<LocationTracker, UseCaseError>;
+    async fn get_travel_direction(
+        &self,
+        coordinates: &[(f64, f64)],
+        line_id: u32,
+        line_group_id: Option<u32>,
+    ) -> Result<Option<TravelDirectionEstimate>, UseCaseError>;
+    async fn get_autocomplete_suggestions(
+        &self,
+        query: &str,
+        limit: Option<u32>,
+    ) -> Result<Vec<Suggestion>, UseCaseError>;
     async fn get_routes(
         &self,
         from_station_id: u32,
         to_station_id: u32,
+        service_day: Option<ServiceDay>,
     ) -> Result<Vec<Route>, UseCaseError>;
-    async fn find_line_by_id(&self, line_id: u32) -> Result<Option<Line>, UseCaseError>;
+    async fn get_journeys(
+        &self,
+        from_station_group_id: u32,
+        to_station_group_id: u32,
+        criterion: JourneySearchCriterion,
+        limit: Option<u32>,
+    ) -> Result<Vec<Journey>, UseCaseError>;
+    async fn find_line_by_id(
+        &self,
+        line_id: u32,
+        as_of: Option<&AsOf>,
+    ) -> Result<Option<Line>, UseCaseError>;
     async fn get_lines_by_name(
         &self,
         line_name: String,
         limit: Option<u32>,
     ) -> Result<Vec<Line>, UseCaseError>;
+    async fn find_prefecture_by_id(
+        &self,
+        prefecture_id: u32,
+    ) -> Result<Option<Prefecture>, UseCaseError>;
+    async fn get_prefectures(&self) -> Result<Vec<Prefecture>, UseCaseError>;
+    async fn get_stations_by_prefecture_id(
+        &self,
+        prefecture_id: u32,
+        as_of: Option<&AsOf>,
+        field_mask: &StationFieldMask,
+    ) -> Result<Vec<Station>, UseCaseError>;
+    async fn get_lines_by_prefecture_id(
+        &self,
+        prefecture_id: u32,
+        as_of: Option<&AsOf>,
+    ) -> Result<Vec<Line>, UseCaseError>;
+    async fn find_company_by_id(&self, company_id: u32) -> Result<Option<Company>, UseCaseError>;
+    async fn get_companies(&self, filter: &CompanyFilter) -> Result<Vec<Company>, UseCaseError>;
+    async fn get_lines_by_company_id(
+        &self,
+        company_id: u32,
+        as_of: Option<&AsOf>,
+    ) -> Result<Vec<Line>, UseCaseError>;
+    async fn get_stations_by_company_id(
+        &self,
+        company_id: u32,
+        as_of: Option<&AsOf>,
+        field_mask: &StationFieldMask,
+    ) -> Result<Vec<Station>, UseCaseError>;
+    async fn find_train_type_by_id(&self, type_id: u32) -> Result<Option<TrainType>, UseCaseError>;
+    async fn get_train_types(
+        &self,
+        filter: &TrainTypeFilter,
+    ) -> Result<Vec<TrainType>, UseCaseError>;
+    async fn get_stations_by_train_type(
+        &self,
+        type_id: u32,
+        line_group_id: Option<u32>,
+        field_mask: &StationFieldMask,
+    ) -> Result<Vec<Station>, UseCaseError>;
+    async fn get_stopping_pattern_diff(
+        &self,
+        first_line_group_id: u32,
+        second_line_group_id: u32,
+    ) -> Result<StoppingPattern, UseCaseError>;
+    async fn get_line_geojson(&self, line_id: u32) -> Result<FeatureCollection, UseCaseError>;
+    async fn get_dataset_info(&self) -> Result<Option<DatasetInfo>, UseCaseError>;
 }
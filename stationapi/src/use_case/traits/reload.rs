@@ -0,0 +1,40 @@
+use async_trait::async_trait;
+
+use crate::{
+    domain::entity::{dataset_changelog::DatasetChangelog, dataset_summary::DatasetSummary},
+    use_case::{error::UseCaseError, traits::query::QueryUseCase},
+};
+
+#[async_trait]
+pub trait DatasetLoader: Send + Sync + 'static {
+    type QueryUseCase: QueryUseCase;
+
+    // sourceからデータを読み込んで検証する。失敗したら今のデータを使い続ける
+    async fn load(
+        &self,
+        source: &str,
+    ) -> Result<(Self::QueryUseCase, DatasetSummary), UseCaseError>;
+
+    // 2つのsourceを読み込み、station_cd/line_cd/type_cdごとの差分を返す
+    async fn diff(
+        &self,
+        base_source: &str,
+        target_source: &str,
+    ) -> Result<DatasetChangelog, UseCaseError>;
+}
+
+#[async_trait]
+pub trait ReloadUseCase: Send + Sync + 'static {
+    // sourceを省略したら前回と同じところから読み込む
+    async fn reload_dataset(&self, source: Option<String>) -> Result<DatasetSummary, UseCaseError>;
+
+    // 前回読み込んだところのバージョンが変わっていたときだけ読み込み直す
+    async fn reload_dataset_if_changed(&self) -> Result<Option<DatasetSummary>, UseCaseError>;
+
+    // base_sourceを省略したら前回読み込んだところと比べる
+    async fn diff_dataset(
+        &self,
+        base_source: Option<String>,
+        target_source: String,
+    ) -> Result<DatasetChangelog, UseCaseError>;
+}